@@ -0,0 +1,49 @@
+#![feature(stdsimd, test)]
+
+#[cfg(test)] extern crate test;
+extern crate faster;
+
+#[cfg(test)]
+mod checksum {
+    use faster::checksum::*;
+    use test::{Bencher, black_box};
+
+    fn payload() -> Vec<u8> {
+        (0..65536u32).map(|x| (x.wrapping_mul(2654435761) >> 24) as u8).collect()
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn adler32_simd(b: &mut Bencher) {
+        let data = payload();
+        b.iter(|| black_box(adler32(&data)))
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn adler32_scalar(b: &mut Bencher) {
+        let data = payload();
+        b.iter(|| {
+            let (mut x, mut y) = (1u32, 0u32);
+            for &byte in &data {
+                x = (x + byte as u32) % 65521;
+                y = (y + x) % 65521;
+            }
+            black_box(y << 16 | x)
+        })
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn fletcher32_simd(b: &mut Bencher) {
+        let data = payload();
+        b.iter(|| black_box(fletcher32(&data)))
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn crc32c_simd(b: &mut Bencher) {
+        let data = payload();
+        b.iter(|| black_box(crc32c(&data)))
+    }
+}
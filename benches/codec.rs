@@ -0,0 +1,98 @@
+#![feature(stdsimd, test)]
+
+#[cfg(test)] extern crate test;
+extern crate faster;
+
+#[cfg(test)]
+mod codec {
+    use faster::codec::*;
+    use test::{Bencher, black_box};
+
+    fn payload() -> Vec<u8> {
+        (0..12288u32).map(|x| (x.wrapping_mul(2654435761) >> 24) as u8).collect()
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn base64_encode_simd(b: &mut Bencher) {
+        let input = payload();
+        let mut out = vec![0u8; base64_encoded_len(input.len(), Base64Config::STANDARD)];
+        b.iter(|| black_box(base64_encode(&input, &mut out, Base64Config::STANDARD)))
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn base64_decode_simd(b: &mut Bencher) {
+        let input = payload();
+        let mut encoded = vec![0u8; base64_encoded_len(input.len(), Base64Config::STANDARD)];
+        base64_encode(&input, &mut encoded, Base64Config::STANDARD);
+        let mut out = vec![0u8; base64_decoded_max_len(encoded.len())];
+        b.iter(|| black_box(base64_decode(&encoded, &mut out, Base64Config::STANDARD)))
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn hex_encode_simd(b: &mut Bencher) {
+        let input = payload();
+        let mut out = vec![0u8; input.len() * 2];
+        b.iter(|| black_box(hex_encode(&input, &mut out)))
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn hex_decode_simd(b: &mut Bencher) {
+        let input = payload();
+        let mut encoded = vec![0u8; input.len() * 2];
+        hex_encode(&input, &mut encoded);
+        let mut out = vec![0u8; input.len()];
+        b.iter(|| black_box(hex_decode(&encoded, &mut out)))
+    }
+
+    fn column() -> Vec<u32> {
+        (0..4096u32).map(|x| x.wrapping_mul(2654435761) >> (8 * (x % 4))).collect()
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn bitpack_simd(b: &mut Bencher) {
+        let input = column();
+        let mut out = vec![0u8; bitpacked_len(input.len(), 24)];
+        b.iter(|| black_box(bitpack(&input, 24, &mut out)))
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn bitunpack_simd(b: &mut Bencher) {
+        let input = column();
+        let mut packed = vec![0u8; bitpacked_len(input.len(), 24)];
+        bitpack(&input, 24, &mut packed);
+        let mut out = vec![0u32; input.len()];
+        b.iter(|| black_box(bitunpack(&packed, 24, &mut out)))
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn delta_decode_simd(b: &mut Bencher) {
+        let input = column();
+        let mut out = vec![0u32; input.len()];
+        b.iter(|| { delta_decode(&input, &mut out); black_box(out[0]) })
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn streamvbyte_encode_simd(b: &mut Bencher) {
+        let input = column();
+        let mut out = vec![0u8; streamvbyte_max_len(input.len())];
+        b.iter(|| black_box(streamvbyte_encode(&input, &mut out)))
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn streamvbyte_decode_simd(b: &mut Bencher) {
+        let input = column();
+        let mut encoded = vec![0u8; streamvbyte_max_len(input.len())];
+        let len = streamvbyte_encode(&input, &mut encoded);
+        let mut out = vec![0u32; input.len()];
+        b.iter(|| black_box(streamvbyte_decode(&encoded[..len], &mut out)))
+    }
+}
@@ -0,0 +1,58 @@
+#![feature(stdsimd, test)]
+
+#[cfg(test)] extern crate test;
+extern crate faster;
+
+#[cfg(test)]
+mod dsp {
+    use faster::dsp::*;
+    use test::{Bencher, black_box};
+
+    fn signal() -> Vec<f32> {
+        (0..16384u32).map(|x| (x.wrapping_mul(2654435761) >> 24) as f32 / 255.0).collect()
+    }
+
+    fn kernel() -> Vec<f32> {
+        (0..31).map(|x| 1.0 / (1.0 + x as f32)).collect()
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn convolve_simd(b: &mut Bencher) {
+        let (input, kernel) = (signal(), kernel());
+        let mut out = vec![0.0; input.len() + kernel.len() - 1];
+        b.iter(|| black_box(convolve(&input, &kernel, &mut out, ConvolveMode::Full)))
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn convolve_scalar(b: &mut Bencher) {
+        let (input, kernel) = (signal(), kernel());
+        let mut out = vec![0.0; input.len() + kernel.len() - 1];
+        b.iter(|| {
+            for o in out.iter_mut() {
+                *o = 0.0;
+            }
+            for (i, &x) in input.iter().enumerate() {
+                for (j, &c) in kernel.iter().enumerate() {
+                    out[i + j] += x * c;
+                }
+            }
+            black_box(&out);
+        })
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn fir_filter_blocks(b: &mut Bencher) {
+        let (input, kernel) = (signal(), kernel());
+        let mut filter = FirFilter::new(&kernel);
+        let mut out = vec![0.0; input.len()];
+        b.iter(|| {
+            for (i, o) in input.chunks(256).zip(out.chunks_mut(256)) {
+                filter.process(i, o);
+            }
+            black_box(&out);
+        })
+    }
+}
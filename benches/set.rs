@@ -0,0 +1,94 @@
+#![feature(stdsimd, test)]
+
+#[cfg(test)] extern crate test;
+extern crate faster;
+
+#[cfg(test)]
+mod set {
+    use faster::set::*;
+    use test::{Bencher, black_box};
+
+    // Two posting lists of 4096 elements which share roughly half of their
+    // elements
+    fn lists() -> (Vec<u32>, Vec<u32>) {
+        ((0..4096u32).map(|x| x * 2).collect(), (0..4096u32).map(|x| x * 3 + (x & 1)).collect())
+    }
+
+    fn intersect_scalar(a: &[u32], b: &[u32], out: &mut [u32]) -> usize {
+        let (mut i, mut j, mut o) = (0, 0, 0);
+        while i < a.len() && j < b.len() {
+            if a[i] < b[j] {
+                i += 1;
+            } else if b[j] < a[i] {
+                j += 1;
+            } else {
+                out[o] = a[i];
+                o += 1;
+                i += 1;
+                j += 1;
+            }
+        }
+        o
+    }
+
+    fn union_scalar(a: &[u32], b: &[u32], out: &mut [u32]) -> usize {
+        let (mut i, mut j, mut o) = (0, 0, 0);
+        while i < a.len() || j < b.len() {
+            let x = if j >= b.len() || (i < a.len() && a[i] <= b[j]) { i += 1; a[i - 1] }
+                    else { j += 1; b[j - 1] };
+            if o == 0 || out[o - 1] != x {
+                out[o] = x;
+                o += 1;
+            }
+        }
+        o
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn intersect_simd(b: &mut Bencher) {
+        let (x, y) = lists();
+        let mut out = vec![0u32; 8192];
+        b.iter(|| black_box(intersect(&x, &y, &mut out)))
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn intersect_merge(b: &mut Bencher) {
+        let (x, y) = lists();
+        let mut out = vec![0u32; 8192];
+        b.iter(|| black_box(intersect_scalar(&x, &y, &mut out)))
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn union_simd(b: &mut Bencher) {
+        let (x, y) = lists();
+        let mut out = vec![0u32; 8192];
+        b.iter(|| black_box(union(&x, &y, &mut out)))
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn union_merge(b: &mut Bencher) {
+        let (x, y) = lists();
+        let mut out = vec![0u32; 8192];
+        b.iter(|| black_box(union_scalar(&x, &y, &mut out)))
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn difference_simd(b: &mut Bencher) {
+        let (x, y) = lists();
+        let mut out = vec![0u32; 8192];
+        b.iter(|| black_box(difference(&x, &y, &mut out)))
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn dedup_sorted_simd(b: &mut Bencher) {
+        let x = (0..8192u32).map(|x| x / 3).collect::<Vec<_>>();
+        let mut out = vec![0u32; 8192];
+        b.iter(|| black_box(dedup_sorted(&x, &mut out)))
+    }
+}
@@ -124,3 +124,31 @@ rust_fallback_impl_binary! {
         max => __undefined(), [0, 1, 2, 3];
     }
 }
+
+rust_fallback_impl_binary! {
+    impl Cmp for u64x2 where "__undefined" {
+        min => __undefined(), [0, 1];
+        max => __undefined(), [0, 1];
+    }
+}
+
+rust_fallback_impl_binary! {
+    impl Cmp for i64x2 where "__undefined" {
+        min => __undefined(), [0, 1];
+        max => __undefined(), [0, 1];
+    }
+}
+
+rust_fallback_impl_binary! {
+    impl Cmp for u64x4 where "__undefined" {
+        min => __undefined(), [0, 1, 2, 3];
+        max => __undefined(), [0, 1, 2, 3];
+    }
+}
+
+rust_fallback_impl_binary! {
+    impl Cmp for i64x4 where "__undefined" {
+        min => __undefined(), [0, 1, 2, 3];
+        max => __undefined(), [0, 1, 2, 3];
+    }
+}
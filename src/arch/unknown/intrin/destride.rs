@@ -81,3 +81,6 @@ impl_destride!(u32x8, 0, 2, 4, 6);
 impl_destride!(u32x4, 0, 2);
 impl_destride!(i32x8, 0, 2, 4, 6);
 impl_destride!(i32x4, 0, 2);
+
+impl_destride!(f32x8, 0, 2, 4, 6);
+impl_destride!(f32x4, 0, 2);
@@ -0,0 +1,18 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::arch::current::vecs::*;
+use crate::vecs::*;
+use crate::intrin::cmp::*;
+use crate::intrin::eq::*;
+use crate::intrin::float_ops::*;
+use crate::intrin::transmute::*;
+
+impl_packed_float_ops!(f32x4, u32x4, i32x4, be_u32s, be_i32s, be_f32s_unchecked, 31,
+                       0x8000_0000u32, 0x7F80_0000u32, 0x3F80_0000u32;
+                       f64x2, u64x2, i64x2, be_u64s, be_i64s, be_f64s_unchecked, 63,
+                       0x8000_0000_0000_0000u64, 0x7FF0_0000_0000_0000u64, 0x3FF0_0000_0000_0000u64);
@@ -0,0 +1,15 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::arch::current::vecs::*;
+use crate::vecs::*;
+use crate::intrin::hash_lanes::*;
+
+impl_hash_word!(lanes u32x4, u32; u64x2, u64);
+
+impl_packed_hash_lanes!(u32x4, u32, fmix32, xxh32;
+                        u64x2, u64, fmix64, xxh64);
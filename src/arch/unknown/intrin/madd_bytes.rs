@@ -0,0 +1,13 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::arch::current::vecs::*;
+use crate::vecs::*;
+use crate::intrin::madd_bytes::*;
+use crate::intrin::transmute::*;
+
+impl_packed_madd_bytes!(u8x16, i8x16, i16x8, "__undefined", __undefined);
@@ -0,0 +1,22 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::arch::current::vecs::*;
+use crate::vecs::*;
+use crate::intrin::overflowing_add::*;
+
+// The operators of this backend panic on overflow in debug builds, so the
+// sums are computed per element.
+impl_packed_overflowing_add!(scalar
+    u8x16, u8x16, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+    i8x16, u8x16, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+    u16x8, u16x8, [0, 1, 2, 3, 4, 5, 6, 7];
+    i16x8, u16x8, [0, 1, 2, 3, 4, 5, 6, 7];
+    u32x4, u32x4, [0, 1, 2, 3];
+    i32x4, u32x4, [0, 1, 2, 3];
+    u64x2, u64x2, [0, 1];
+    i64x2, u64x2, [0, 1]);
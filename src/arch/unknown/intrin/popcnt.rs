@@ -0,0 +1,30 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::arch::current::vecs::*;
+use crate::intrin::popcnt::*;
+use crate::intrin::transmute::*;
+use crate::vecs::*;
+
+#[inline(always)]
+fn popcnt128(v: u8x16) -> usize {
+    fallback!();
+    v.be_u64s().scalar_reduce(0, |acc, s| acc + (s.count_ones() as usize))
+}
+
+impl_popcnt!(u8x16, popcnt128, i8x16, popcnt128, u16x8, popcnt128, i16x8, popcnt128,
+             u32x4, popcnt128, i32x4, popcnt128, u64x2, popcnt128, i64x2, popcnt128);
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::arch::current::vecs::*;
+
+    test_popcnt!((u8, i8, u16, i16, u32, i32, u64, i64),
+                 (u8x16, i8x16, u16x8, i16x8, u32x4, i32x4, u64x2, i64x2),
+                 (popcnt_u8x16, popcnt_i8x16, popcnt_u16x8, popcnt_i16x8, popcnt_u32x4, popcnt_i32x4, popcnt_u64x2, popcnt_i64x2));
+}
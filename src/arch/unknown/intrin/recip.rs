@@ -7,10 +7,10 @@
 
 use crate::arch::current::vecs::*;
 use crate::vecs::*;
+use crate::intrin::eq::*;
+use crate::intrin::float_ops::*;
 use crate::intrin::recip::Recip;
+use crate::intrin::transmute::*;
 
-rust_fallback_impl! {
-    impl Recip for f32x4 where "__undefined" {
-        recip => __undefined(), [0, 1, 2, 3];
-    }
-}
+impl_packed_recip!(f32x4, u32x4, "__undefined", __undefined, [0, 1, 2, 3]);
+impl_packed_recip_exact!(f64x2);
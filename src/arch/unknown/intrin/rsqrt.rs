@@ -5,35 +5,27 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+use crate::intrin::eq::*;
+use crate::intrin::float_ops::*;
 use crate::intrin::rsqrt::*;
+use crate::intrin::sqrt::*;
+use crate::intrin::transmute::*;
 use crate::arch::current::vecs::*;
 use crate::vecs::*;
 
-// TODO: Guards and non-simd
-//
-//rust_fallback_impl! {
-//    impl Rsqrt for f32x8 where "__undefined" {
-//        rsqrt => _mm256_rsqrt_ps(), [0, 1, 2, 3, 4, 5, 6, 7];
-//    }
-//}
-
-rust_fallback_impl! {
-    impl Rsqrt for f32x4 where "__undefined" {
-        rsqrt => __undefined(), [0, 1, 2, 3];
-    }
-}
-
-rust_fallback_impl! {
-    impl Rsqrt for f64x2 where "__undefined" {
-        rsqrt => __undefined(), [0, 1];
-    }
-}
+impl_packed_rsqrt!(f32x4, u32x4, "__undefined", __undefined, [0, 1, 2, 3]);
+impl_packed_rsqrt_exact!(f64x2);
 
 impl Rsqrt for f32 {
     #[inline(always)]
     fn rsqrt(&self) -> Self {
         self.sqrt().recip()
     }
+
+    #[inline(always)]
+    fn rsqrt_nr(&self) -> Self {
+        self.rsqrt()
+    }
 }
 
 impl Rsqrt for f64 {
@@ -41,4 +33,9 @@ impl Rsqrt for f64 {
     fn rsqrt(&self) -> Self {
         self.sqrt().recip()
     }
+
+    #[inline(always)]
+    fn rsqrt_nr(&self) -> Self {
+        self.rsqrt()
+    }
 }
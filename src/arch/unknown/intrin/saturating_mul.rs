@@ -0,0 +1,24 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::arch::current::vecs::*;
+use crate::vecs::*;
+use crate::intrin::downcast::*;
+use crate::intrin::eq::*;
+use crate::intrin::saturating_mul::*;
+use crate::intrin::transmute::*;
+use crate::intrin::upcast::*;
+use crate::intrin::wrapping::WrappingLanes;
+
+impl_packed_saturating_mul!(upcast
+    u32x4, u64x2; i32x4, i64x2);
+
+impl_packed_saturating_mul!(halves unsigned
+    u64x2);
+
+impl_packed_saturating_mul!(halves signed
+    i64x2, u64x2);
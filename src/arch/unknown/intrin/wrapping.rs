@@ -0,0 +1,12 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::arch::current::vecs::*;
+use crate::vecs::*;
+use crate::intrin::wrapping::*;
+
+impl_wrapping_lanes!(lanes u8x16, i8x16, u16x8, i16x8, u32x4, i32x4, u64x2, i64x2);
@@ -126,3 +126,25 @@ rust_fallback_impl_binary! {
         max => _mm256_max_pd(), [0, 1, 2, 3];
     }
 }
+
+// There are no 64-bit integer min/max instructions before AVX-512, so we
+// compare and blend instead.
+macro_rules! impl_cmp_select {
+    ($($vec:tt),*) => {
+        $(
+            impl Cmp for $vec {
+                #[inline(always)]
+                fn max(&self, other: Self) -> Self {
+                    (*self).gt(other).select(*self, other)
+                }
+
+                #[inline(always)]
+                fn min(&self, other: Self) -> Self {
+                    (*self).lt(other).select(*self, other)
+                }
+            }
+        )*
+    }
+}
+
+impl_cmp_select!(u64x2, i64x2, u64x4, i64x4);
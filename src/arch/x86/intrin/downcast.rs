@@ -52,9 +52,9 @@ impl Downcast<u32x4> for u64x2 {
     fn saturating_downcast(self, other: Self) -> u32x4 {
         fallback!();
         u32x4::new(self.extract(0).min(0xFFFFFFFF) as u32,
-                   self.extract(1).min(0x7FFFFFFF) as u32,
-                   other.extract(0).min(0x7FFFFFFF) as u32,
-                   other.extract(1).min(0x7FFFFFFF) as u32)
+                   self.extract(1).min(0xFFFFFFFF) as u32,
+                   other.extract(0).min(0xFFFFFFFF) as u32,
+                   other.extract(1).min(0xFFFFFFFF) as u32)
     }
 }
 
@@ -159,7 +159,9 @@ impl Downcast<i16x16> for i32x8 {
     #[cfg(target_feature = "avx2")]
     fn saturating_downcast(self, other: Self) -> i16x16 {
         optimized!();
-        unsafe { _mm256_packs_epi32(self, other) }
+        // The pack works within each 128-bit half, so put the halves back in
+        // order afterwards
+        unsafe { _mm256_permute4x64_epi64(_mm256_packs_epi32(self, other).be_i64s(), 0xD8).be_i16s() }
     }
 
     #[inline(always)]
@@ -190,7 +192,7 @@ impl Downcast<i8x32> for i16x16 {
     #[cfg(target_feature = "avx2")]
     fn saturating_downcast(self, other: Self) -> i8x32 {
         optimized!();
-        unsafe { _mm256_packs_epi16(self.be_i16s(), other) }
+        unsafe { _mm256_permute4x64_epi64(_mm256_packs_epi16(self.be_i16s(), other).be_i64s(), 0xD8).be_i8s() }
     }
 
     #[inline(always)]
@@ -237,7 +239,7 @@ impl Downcast<u16x16> for u32x8 {
     #[cfg(target_feature = "avx2")]
     fn saturating_downcast(self, other: Self) -> u16x16 {
         optimized!();
-        unsafe { transmute(_mm256_packus_epi32(transmute(self), transmute(other))) }
+        unsafe { _mm256_permute4x64_epi64(transmute(_mm256_packus_epi32(transmute(self), transmute(other))), 0xD8).be_u16s() }
     }
 
     #[inline(always)]
@@ -268,7 +270,7 @@ impl Downcast<u8x32> for u16x16 {
     #[cfg(target_feature = "avx2")]
     fn saturating_downcast(self, other: Self) -> u8x32 {
         optimized!();
-        unsafe { _mm256_packus_epi16(self.be_i16s(), other.be_i16s()).be_u8s() }
+        unsafe { _mm256_permute4x64_epi64(_mm256_packus_epi16(self.be_i16s(), other.be_i16s()).be_i64s(), 0xD8).be_u8s() }
     }
 
     #[inline(always)]
@@ -330,13 +332,13 @@ impl Downcast<u32x8> for u64x4 {
     fn saturating_downcast(self, other: Self) -> u32x8 {
         fallback!();
         u32x8::new(self.extract(0).min(0xFFFFFFFF) as u32,
-                   self.extract(1).min(0x7FFFFFFF) as u32,
+                   self.extract(1).min(0xFFFFFFFF) as u32,
                    self.extract(2).min(0xFFFFFFFF) as u32,
-                   self.extract(3).min(0x7FFFFFFF) as u32,
-                   other.extract(0).min(0x7FFFFFFF) as u32,
-                   other.extract(1).min(0x7FFFFFFF) as u32,
-                   other.extract(2).min(0x7FFFFFFF) as u32,
-                   other.extract(3).min(0x7FFFFFFF) as u32)
+                   self.extract(3).min(0xFFFFFFFF) as u32,
+                   other.extract(0).min(0xFFFFFFFF) as u32,
+                   other.extract(1).min(0xFFFFFFFF) as u32,
+                   other.extract(2).min(0xFFFFFFFF) as u32,
+                   other.extract(3).min(0xFFFFFFFF) as u32)
     }
 }
 
@@ -354,3 +356,46 @@ impl Downcast<f32x8> for f64x4 {
                    other.extract(3) as f32)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::arch::current::vecs::*;
+
+    // The first half of the result comes from `self` and the second from
+    // `other`, in lane order. Build with `-C target-feature=+avx2` to check
+    // the packs of the 256-bit vectors.
+    macro_rules! test_downcast_order {
+        ($($from:ident, $to:ident, $el:ty, $name:ident);*) => (
+            $(
+                #[test]
+                fn $name() {
+                    let (mut a, mut b) = ($from::splat(0), $from::splat(0));
+                    for i in 0..$from::WIDTH {
+                        a = a.replace(i, i as _);
+                        b = b.replace(i, ($from::WIDTH + i) as _);
+                    }
+                    let c: $to = a.saturating_downcast(b);
+                    for i in 0..$to::WIDTH {
+                        assert_eq!(c.extract(i), i as $el);
+                    }
+                }
+            )*
+        )
+    }
+
+    test_downcast_order!(i32x4, i16x8, i16, downcast_order_i32x4;
+                         u32x4, u16x8, u16, downcast_order_u32x4;
+                         i16x8, i8x16, i8, downcast_order_i16x8;
+                         u16x8, u8x16, u8, downcast_order_u16x8;
+                         i32x8, i16x16, i16, downcast_order_i32x8;
+                         u32x8, u16x16, u16, downcast_order_u32x8;
+                         i16x16, i8x32, i8, downcast_order_i16x16;
+                         u16x16, u8x32, u8, downcast_order_u16x16);
+
+    #[test]
+    fn downcast_saturates_u16x16() {
+        let c: u8x32 = u16x16::splat(300).saturating_downcast(u16x16::splat(7));
+        assert_eq!(c, u8x32::halfs(255, 7));
+    }
+}
@@ -0,0 +1,49 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::arch::current::vecs::*;
+use crate::vecs::*;
+use crate::intrin::hash_lanes::*;
+
+impl_hash_word!(vector u32x4, u32; u32x8, u32; u32x16, u32; u64x2, u64; u64x4, u64; u64x8, u64);
+
+impl_packed_hash_lanes!(u32x4, u32, fmix32, xxh32;
+                        u32x8, u32, fmix32, xxh32;
+                        u32x16, u32, fmix32, xxh32;
+                        u64x2, u64, fmix64, xxh64;
+                        u64x4, u64, fmix64, xxh64;
+                        u64x8, u64, fmix64, xxh64);
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::arch::current::vecs::*;
+    use crate::intrin::hash_lanes::{fmix32, fmix64, xxh32, xxh64};
+    use crate::test_util::rng;
+
+    #[test]
+    fn hash_lanes_512() {
+        let mut seed = 0x9E3779B97F4A7C15;
+        let (mut a, mut b) = (u32x16::splat(0), u64x8::splat(0));
+        for i in 0..u32x16::WIDTH {
+            a = a.replace(i, rng(&mut seed) as u32);
+        }
+        for i in 0..u64x8::WIDTH {
+            b = b.replace(i, rng(&mut seed));
+        }
+        let (murmur, xxh) = (a.hash_murmur3(), a.hash_xxh(7));
+        for i in 0..u32x16::WIDTH {
+            assert_eq!(murmur.extract(i), fmix32(a.extract(i)));
+            assert_eq!(xxh.extract(i), xxh32(a.extract(i), 7));
+        }
+        let (murmur, xxh) = (b.hash_murmur3(), b.hash_xxh(7));
+        for i in 0..u64x8::WIDTH {
+            assert_eq!(murmur.extract(i), fmix64(b.extract(i)));
+            assert_eq!(xxh.extract(i), xxh64(b.extract(i), 7));
+        }
+    }
+}
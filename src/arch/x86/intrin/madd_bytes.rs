@@ -0,0 +1,46 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::vektor::x86_64::*;
+use crate::vektor::x86::*;
+use crate::arch::current::vecs::*;
+use crate::vecs::*;
+use crate::intrin::madd_bytes::*;
+use crate::intrin::transmute::*;
+use crate::core::mem::transmute;
+
+impl_packed_madd_bytes!(u8x16, i8x16, i16x8, "ssse3", _mm_maddubs_epi16;
+                        u8x32, i8x32, i16x16, "avx2", _mm256_maddubs_epi16);
+
+impl_packed_madd_bytes!(halves u8x64, i8x64, i16x32, u8x32, i8x32, i16x16);
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::arch::current::vecs::*;
+    use crate::test_util::rng;
+
+    #[test]
+    fn madd_bytes_u8x64() {
+        let mut seed = 0x9E3779B97F4A7C15u64;
+        for _ in 0..100 {
+            let (mut v, mut w) = (u8x64::splat(0), i8x64::splat(0));
+            for i in 0..u8x64::WIDTH {
+                let x = rng(&mut seed);
+                v = v.replace(i, x as u8);
+                w = w.replace(i, (x >> 8) as i8);
+            }
+            let sums = v.madd_bytes(w);
+            for i in 0..i16x32::WIDTH {
+                let sum = (i * 2..i * 2 + 2)
+                    .map(|j| v.extract(j) as i32 * w.extract(j) as i32)
+                    .sum::<i32>();
+                assert_eq!(sums.extract(i) as i32, sum.max(-32768).min(32767));
+            }
+        }
+    }
+}
@@ -9,48 +9,69 @@
 
 mod abs;
 mod addsub;
+mod cast;
 mod cmp;
 mod destride;
 mod downcast;
 mod endian;
 mod eq;
+mod float_ops;
 mod hadd;
+mod hash_lanes;
 mod hsub;
+mod lookup;
+mod madd_bytes;
 mod merge;
+mod movemask;
+mod overflowing_add;
 mod popcnt;
+mod prefix_xor;
 mod recip;
 mod round;
 mod sum;
+mod sum_bytes;
 mod rsqrt;
 mod saturating_add;
 mod saturating_hadd;
+mod saturating_mul;
 mod saturating_sub;
 mod saturating_hsub;
 mod sqrt;
 mod transmute;
 mod upcast;
+mod wrapping;
 
 // We use an internal prelude not to clutter the namespace when we import
 // from actual prelude.
 pub mod prelude {
     pub use super::abs::*;
     pub use super::addsub::*;
+    pub use super::cast::*;
     pub use super::cmp::*;
     pub use super::destride::*;
     pub use super::downcast::*;
     pub use super::endian::*;
     pub use super::eq::*;
+    pub use super::float_ops::*;
     pub use super::hadd::*;
+    pub use super::hash_lanes::*;
     pub use super::hsub::*;
+    pub use super::lookup::*;
+    pub use super::madd_bytes::*;
     pub use super::merge::*;
+    pub use super::movemask::*;
+    pub use super::overflowing_add::*;
     pub use super::popcnt::*;
+    pub use super::prefix_xor::*;
     pub use super::recip::*;
     pub use super::round::*;
     pub use super::rsqrt::*;
     pub use super::sum::*;
+    pub use super::sum_bytes::*;
     pub use super::saturating_add::*;
     pub use super::saturating_hadd::*;
     pub use super::saturating_hsub::*;
+    pub use super::saturating_mul::*;
     pub use super::saturating_sub::*;
     pub use super::transmute::*;
     pub use super::upcast::*;
@@ -0,0 +1,18 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::vektor::x86_64::*;
+use crate::vektor::x86::*;
+use crate::arch::current::vecs::*;
+use crate::vecs::*;
+use crate::intrin::movemask::*;
+use crate::intrin::transmute::*;
+
+impl_packed_movemask!(u8x16, "sse2", _mm_movemask_epi8;
+                      i8x16, "sse2", _mm_movemask_epi8;
+                      u8x32, "avx2", _mm256_movemask_epi8;
+                      i8x32, "avx2", _mm256_movemask_epi8);
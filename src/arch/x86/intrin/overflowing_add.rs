@@ -0,0 +1,21 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::arch::current::vecs::*;
+use crate::vecs::*;
+use crate::intrin::overflowing_add::*;
+use crate::intrin::transmute::*;
+
+impl_packed_overflowing_add!(unsigned
+    u8x16, 7; u16x8, 15; u32x4, 31; u64x2, 63;
+    u8x32, 7; u16x16, 15; u32x8, 31; u64x4, 63);
+
+impl_packed_overflowing_add!(signed
+    i8x16, u8x16, be_u8s, 7; i16x8, u16x8, be_u16s, 15;
+    i32x4, u32x4, be_u32s, 31; i64x2, u64x2, be_u64s, 63;
+    i8x32, u8x32, be_u8s, 7; i16x16, u16x16, be_u16s, 15;
+    i32x8, u32x8, be_u32s, 31; i64x4, u64x4, be_u64s, 63);
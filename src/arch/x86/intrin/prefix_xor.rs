@@ -0,0 +1,12 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::vektor::x86::*;
+use crate::arch::current::vecs::*;
+use crate::intrin::prefix_xor::*;
+
+impl_prefix_xor!(u64, i64x2, "pclmulqdq", _mm_clmulepi64_si128);
@@ -9,17 +9,11 @@ use crate::vektor::x86_64::*;
 use crate::vektor::x86::*;
 use crate::arch::current::vecs::*;
 use crate::vecs::*;
+use crate::intrin::eq::*;
+use crate::intrin::float_ops::*;
 use crate::intrin::recip::Recip;
+use crate::intrin::transmute::*;
 
-rust_fallback_impl! {
-    impl Recip for f32x8 where "avx" {
-        recip => _mm256_rcp_ps(), [0, 1, 2, 3, 4, 5, 6, 7];
-    }
-}
-
-rust_fallback_impl! {
-    impl Recip for f32x4 where "sse" {
-        recip => _mm_rcp_ps(), [0, 1, 2, 3];
-    }
-}
-
+impl_packed_recip!(f32x8, u32x8, "avx", _mm256_rcp_ps, [0, 1, 2, 3, 4, 5, 6, 7]);
+impl_packed_recip!(f32x4, u32x4, "sse", _mm_rcp_ps, [0, 1, 2, 3]);
+impl_packed_recip_exact!(f64x4, f64x2);
@@ -7,29 +7,28 @@
 
 use crate::vektor::x86_64::*;
 use crate::vektor::x86::*;
+use crate::intrin::eq::*;
+use crate::intrin::float_ops::*;
 use crate::intrin::rsqrt::*;
+use crate::intrin::sqrt::*;
+use crate::intrin::transmute::*;
 use crate::arch::current::vecs::*;
 use crate::vecs::*;
 
-// TODO: Guards and non-simd
-
-rust_fallback_impl! {
-    impl Rsqrt for f32x8 where "avx" {
-        rsqrt => _mm256_rsqrt_ps(), [0, 1, 2, 3, 4, 5, 6, 7];
-    }
-}
-
-rust_fallback_impl! {
-    impl Rsqrt for f32x4 where "sse" {
-        rsqrt => _mm_rsqrt_ps(), [0, 1, 2, 3];
-    }
-}
+impl_packed_rsqrt!(f32x8, u32x8, "avx", _mm256_rsqrt_ps, [0, 1, 2, 3, 4, 5, 6, 7]);
+impl_packed_rsqrt!(f32x4, u32x4, "sse", _mm_rsqrt_ps, [0, 1, 2, 3]);
+impl_packed_rsqrt_exact!(f64x4, f64x2);
 
 impl Rsqrt for f32 {
     #[inline(always)]
     fn rsqrt(&self) -> Self {
         self.sqrt().recip()
     }
+
+    #[inline(always)]
+    fn rsqrt_nr(&self) -> Self {
+        self.rsqrt()
+    }
 }
 
 impl Rsqrt for f64 {
@@ -37,4 +36,9 @@ impl Rsqrt for f64 {
     fn rsqrt(&self) -> Self {
         self.sqrt().recip()
     }
+
+    #[inline(always)]
+    fn rsqrt_nr(&self) -> Self {
+        self.rsqrt()
+    }
 }
@@ -61,3 +61,11 @@ rust_fallback_impl_binary! {
         saturating_add => _mm256_adds_epi16(), [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
     }
 }
+
+impl_packed_saturating_add!(unsigned
+    u32x4, 31; u64x2, 63;
+    u32x8, 31; u64x4, 63);
+
+impl_packed_saturating_add!(signed
+    i32x4, 31, 0x7FFF_FFFF; i64x2, 63, 0x7FFF_FFFF_FFFF_FFFF;
+    i32x8, 31, 0x7FFF_FFFF; i64x4, 63, 0x7FFF_FFFF_FFFF_FFFF);
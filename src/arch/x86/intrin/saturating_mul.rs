@@ -0,0 +1,29 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::vektor::x86_64::*;
+use crate::vektor::x86::*;
+use crate::arch::current::vecs::*;
+use crate::vecs::*;
+use crate::intrin::eq::*;
+use crate::intrin::saturating_mul::*;
+use crate::intrin::transmute::*;
+use crate::intrin::wrapping::WrappingLanes;
+
+impl_packed_saturating_mul!(widening unsigned
+    u32x4, u64x2, "sse2", _mm_mul_epu32, [0, 1, 2, 3];
+    u32x8, u64x4, "avx2", _mm256_mul_epu32, [0, 1, 2, 3, 4, 5, 6, 7]);
+
+impl_packed_saturating_mul!(widening signed
+    i32x4, u64x2, "sse4.1", _mm_mul_epi32, [0, 1, 2, 3];
+    i32x8, u64x4, "avx2", _mm256_mul_epi32, [0, 1, 2, 3, 4, 5, 6, 7]);
+
+impl_packed_saturating_mul!(halves unsigned
+    u64x2, u64x4);
+
+impl_packed_saturating_mul!(halves signed
+    i64x2, u64x2; i64x4, u64x4);
@@ -0,0 +1,38 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::vektor::x86_64::*;
+use crate::vektor::x86::*;
+use crate::arch::current::vecs::*;
+use crate::vecs::*;
+use crate::intrin::sum_bytes::*;
+use crate::intrin::transmute::*;
+use crate::core::mem::transmute;
+
+impl_packed_sum_bytes!(u8x16, u64x2, "sse2", _mm_sad_epu8;
+                       u8x32, u64x4, "avx2", _mm256_sad_epu8);
+
+impl_packed_sum_bytes!(halves u8x64, u64x8, u8x32, u64x4);
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::arch::current::vecs::*;
+
+    #[test]
+    fn sum_bytes_u8x64() {
+        let mut v = u8x64::splat(0);
+        for i in 0..u8x64::WIDTH {
+            v = v.replace(i, (i * 37) as u8);
+        }
+        let sums = v.sum_bytes();
+        for i in 0..u64x8::WIDTH {
+            let expected = (i * 8..i * 8 + 8).map(|j| (j * 37) as u8 as u64).sum::<u64>();
+            assert_eq!(sums.extract(i), expected);
+        }
+    }
+}
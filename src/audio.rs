@@ -0,0 +1,436 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Conversion and mixing of audio samples.
+//!
+//! Floating-point samples range from -1 to 1, and integer samples are
+//! scaled such that their most negative value is -1. Integers convert to
+//! floats exactly. Floats convert back to integers with the same rounding,
+//! dither and clipping in the vectorized and scalar loops, so the result
+//! doesn't depend on the length of the input or on the target features.
+
+use crate::arch::current::vecs::*;
+use crate::vecs::*;
+use crate::intrin::cast::*;
+use crate::intrin::cmp::*;
+use crate::intrin::destride::*;
+use crate::intrin::downcast::*;
+use crate::intrin::hash_lanes::HashWord;
+use crate::intrin::lookup::*;
+use crate::intrin::merge::*;
+use crate::intrin::round::*;
+use crate::intrin::saturating_add::*;
+use crate::intrin::shuffle::{gather, permute, repeat_groups};
+use crate::intrin::transmute::*;
+use crate::intrin::upcast::*;
+use crate::intrin::wrapping::WrappingLanes;
+
+// The magnitude of the most negative sample of each integer format
+const I16_SCALE: f32 = 32768.0;
+const S24_SCALE: f32 = 8_388_608.0;
+
+/// Convert each 16-bit sample of `input` to a float in `output`.
+///
+/// # Panics
+///
+/// This function will panic if `input` and `output` have different lengths.
+///
+/// ```
+/// extern crate faster;
+/// use faster::audio::i16_to_f32;
+///
+/// # fn main() {
+/// let mut out = [0.0; 3];
+/// i16_to_f32(&[-32768, 16384, 32767], &mut out);
+/// assert_eq!(out, [-1.0, 0.5, 32767.0 / 32768.0]);
+/// # }
+/// ```
+pub fn i16_to_f32(input: &[i16], output: &mut [f32]) {
+    assert_eq!(input.len(), output.len(), "every sample needs a place in the output");
+    let (width, half) = (i16s::WIDTH, f32s::WIDTH);
+    let scale = f32s(1.0 / I16_SCALE);
+    let mut i = 0;
+    while i + width <= input.len() {
+        let (lo, hi): (i32s, i32s) = i16s::load(input, i).upcast();
+        (lo.as_f32s() * scale).store(output, i);
+        (hi.as_f32s() * scale).store(output, i + half);
+        i += width;
+    }
+    for (o, &x) in output[i..].iter_mut().zip(&input[i..]) {
+        *o = f32::from(x) * (1.0 / I16_SCALE);
+    }
+}
+
+// Return well-mixed bits for the sample at `index`, or for each sample in
+// a vector of indices.
+#[inline(always)]
+fn dither_bits<W : HashWord<u32>>(index: W) -> W {
+    index.wrapping_mul_word(0x9E37_79B1)
+        .xor_shr(15).wrapping_mul_word(0x85EB_CA77)
+        .xor_shr(13)
+}
+
+// Return the dither added to the sample at `index`, in units of the last
+// bit. The sum of two uniform 16-bit halves has a triangular distribution
+// from -1 to 1.
+#[inline(always)]
+fn dither(index: u32) -> f32 {
+    let bits = dither_bits(index);
+    ((bits & 0xFFFF) + (bits >> 16)) as f32 * (1.0 / 65536.0) - 1.0
+}
+
+// Return `x`, which is dithered and offset by a half, rounded down and
+// clipped to the range of an i16.
+#[inline(always)]
+fn clip_i16(x: f32) -> i16 {
+    let x = x.max(-I16_SCALE).min(I16_SCALE - 1.0);
+    let i = x as i32;
+    (if i as f32 > x { i - 1 } else { i }) as i16
+}
+
+/// Convert each float sample of `input` to a 16-bit sample in `output`,
+/// with triangular dither of one bit. Samples are rounded to the nearest
+/// integer after dithering, and clipped to the range of an i16.
+///
+/// The dither of each sample is a hash of `seed` plus its index, so
+/// converting a stream in blocks gives the same samples as converting it at
+/// once, if each block's seed is the number of samples before it.
+///
+/// # Panics
+///
+/// This function will panic if `input` and `output` have different lengths.
+///
+/// ```
+/// extern crate faster;
+/// use faster::audio::f32_to_i16_dither;
+///
+/// # fn main() {
+/// let mut out = [0; 4];
+/// f32_to_i16_dither(&[0.5, -0.25, 2.0, -7.0], &mut out, 0);
+/// assert!((out[0] - 16384).abs() <= 1);
+/// assert!((out[1] + 8192).abs() <= 1);
+/// assert_eq!(&out[2..], &[32767, -32768]);
+/// # }
+/// ```
+pub fn f32_to_i16_dither(input: &[f32], output: &mut [i16], seed: u32) {
+    assert_eq!(input.len(), output.len(), "every sample needs a place in the output");
+    let (width, half) = (i16s::WIDTH, f32s::WIDTH);
+    let mut lanes = u32s(0);
+    for i in 0..half {
+        lanes = lanes.replace(i, i as u32);
+    }
+    let convert = |x: f32s, index: u32s| {
+        let bits = dither_bits(index);
+        let dither = ((bits & u32s(0xFFFF)) + (bits >> 16)).as_f32s() * f32s(1.0 / 65536.0) - f32s(1.0);
+        let x = Cmp::min(&Cmp::max(&(x * f32s(I16_SCALE) + dither + f32s(0.5)), f32s(-I16_SCALE)), f32s(I16_SCALE - 1.0));
+        x.floor().as_i32s()
+    };
+
+    let mut i = 0;
+    while i + width <= input.len() {
+        let index = u32s(seed.wrapping_add(i as u32)).wrapping_add_lanes(lanes);
+        let lo = convert(f32s::load(input, i), index);
+        let hi = convert(f32s::load(input, i + half), index.wrapping_add_word(half as u32));
+        let samples: i16s = lo.saturating_downcast(hi);
+        samples.store(output, i);
+        i += width;
+    }
+    for (k, (o, &x)) in output.iter_mut().zip(input).enumerate().skip(i) {
+        *o = clip_i16(x * I16_SCALE + dither(seed.wrapping_add(k as u32)) + 0.5);
+    }
+}
+
+/// Convert each packed, little-endian 24-bit sample of `input` to a float
+/// in `output`.
+///
+/// # Panics
+///
+/// This function will panic if `input` isn't three times as long as
+/// `output`.
+///
+/// ```
+/// extern crate faster;
+/// use faster::audio::s24_to_f32;
+///
+/// # fn main() {
+/// let mut out = [0.0; 3];
+/// s24_to_f32(&[0x00, 0x00, 0x80, 0x00, 0x00, 0x40, 0xFF, 0xFF, 0xFF], &mut out);
+/// assert_eq!(out, [-1.0, 0.5, -1.0 / 8388608.0]);
+/// # }
+/// ```
+pub fn s24_to_f32(input: &[u8], output: &mut [f32]) {
+    assert_eq!(input.len(), output.len() * 3, "every sample needs a place in the output");
+    let width = f32s::WIDTH;
+    let scale = f32s(1.0 / S24_SCALE);
+    let sample = |s: &[u8]| i32::from(s[0]) << 8 | i32::from(s[1]) << 16 | i32::from(s[2]) << 24;
+    // Move the three bytes of each sample to the top of a lane; the low
+    // byte is shifted out below
+    let spread = repeat_groups(&[0, 0, 1, 2, 0, 3, 4, 5, 0, 6, 7, 8, 0, 9, 10, 11]);
+    let mut i = 0;
+    while i + width <= output.len() {
+        // Each group of 16 bytes holds four samples, and shifting them back
+        // down extends their sign
+        let samples = gather(input, 3 * i, 12).shuffle_bytes(spread).be_i32s();
+        ((samples >> 8).as_f32s() * scale).store(output, i);
+        i += width;
+    }
+    for (o, s) in output[i..].iter_mut().zip(input[3 * i..].chunks_exact(3)) {
+        *o = (sample(s) >> 8) as f32 * (1.0 / S24_SCALE);
+    }
+}
+
+/// Write the samples of `left` and `right` to `output`, alternating
+/// between the channels. This is the inverse of [`deinterleave_stereo`].
+///
+/// # Panics
+///
+/// This function will panic if `left` and `right` have different lengths,
+/// or if `output` isn't twice as long as them.
+///
+/// ```
+/// extern crate faster;
+/// use faster::audio::interleave_stereo;
+///
+/// # fn main() {
+/// let mut out = [0.0; 6];
+/// interleave_stereo(&[1.0f32, 2.0, 3.0], &[-1.0, -2.0, -3.0], &mut out);
+/// assert_eq!(out, [1.0, -1.0, 2.0, -2.0, 3.0, -3.0]);
+/// # }
+/// ```
+///
+/// [`deinterleave_stereo`]: fn.deinterleave_stereo.html
+pub fn interleave_stereo<T>(left: &[T], right: &[T], output: &mut [T])
+    where T : Packable, T::Vector : Merge {
+    assert_eq!(left.len(), right.len(), "both channels must have the same length");
+    assert_eq!(output.len(), left.len() * 2, "every sample needs a place in the output");
+    let width = T::Vector::WIDTH;
+    let mut i = 0;
+    while i + width <= left.len() {
+        let (l, r) = (T::Vector::load(left, i), T::Vector::load(right, i));
+        // Repeat each sample of one half of both channels, and take the
+        // even lanes from the left and the odd lanes from the right
+        let lo = permute(l, |n| n / 2).merge_interleaved(permute(r, |n| n / 2));
+        let hi = permute(l, |n| (n + width) / 2).merge_interleaved(permute(r, |n| (n + width) / 2));
+        lo.store(output, 2 * i);
+        hi.store(output, 2 * i + width);
+        i += width;
+    }
+    for ((&l, &r), pair) in left[i..].iter().zip(&right[i..]).zip(output[2 * i..].chunks_exact_mut(2)) {
+        pair[0] = l;
+        pair[1] = r;
+    }
+}
+
+/// Write the alternating samples of `input` to `left` and `right`.
+///
+/// # Panics
+///
+/// This function will panic if `left` and `right` have different lengths,
+/// or if `input` isn't twice as long as them.
+///
+/// ```
+/// extern crate faster;
+/// use faster::audio::deinterleave_stereo;
+///
+/// # fn main() {
+/// let (mut left, mut right) = ([0i16; 3], [0i16; 3]);
+/// deinterleave_stereo(&[1, -1, 2, -2, 3, -3], &mut left, &mut right);
+/// assert_eq!((left, right), ([1, 2, 3], [-1, -2, -3]));
+/// # }
+/// ```
+pub fn deinterleave_stereo<T>(input: &[T], left: &mut [T], right: &mut [T])
+    where T : Packable, T::Vector : Destride {
+    assert_eq!(left.len(), right.len(), "both channels must have the same length");
+    assert_eq!(input.len(), left.len() * 2, "every sample needs a place in the output");
+    let width = T::Vector::WIDTH;
+    let mut i = 0;
+    while i + width <= left.len() {
+        let (l, r) = T::Vector::load(input, 2 * i).destride_two(T::Vector::load(input, 2 * i + width));
+        l.store(left, i);
+        r.store(right, i);
+        i += width;
+    }
+    for ((l, r), pair) in left[i..].iter_mut().zip(&mut right[i..]).zip(input[2 * i..].chunks_exact(2)) {
+        *l = pair[0];
+        *r = pair[1];
+    }
+}
+
+/// Add each sample of `input` to the sample at the same index of `output`,
+/// clipping sums to the range of an i16.
+///
+/// # Panics
+///
+/// This function will panic if `input` and `output` have different lengths.
+///
+/// ```
+/// extern crate faster;
+/// use faster::audio::mix_saturating;
+///
+/// # fn main() {
+/// let mut mix = [1000, 30000, -30000];
+/// mix_saturating(&[-500, 5000, -5000], &mut mix);
+/// assert_eq!(mix, [500, 32767, -32768]);
+/// # }
+/// ```
+pub fn mix_saturating(input: &[i16], output: &mut [i16]) {
+    assert_eq!(input.len(), output.len(), "only tracks of the same length can be mixed");
+    let width = i16s::WIDTH;
+    let mut i = 0;
+    while i + width <= input.len() {
+        i16s::load(output, i).saturating_add(i16s::load(input, i)).store(output, i);
+        i += width;
+    }
+    for (o, &x) in output[i..].iter_mut().zip(&input[i..]) {
+        *o = o.saturating_add(x);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::rng;
+
+    #[test]
+    fn i16_to_f32_is_exact() {
+        let input = (i16::min_value()..=i16::max_value()).collect::<Vec<_>>();
+        let mut output = vec![0.0; input.len()];
+        i16_to_f32(&input, &mut output);
+        for (&x, &y) in input.iter().zip(&output) {
+            assert_eq!(y, x as f32 / 32768.0);
+        }
+        for len in 0..40 {
+            let mut output = vec![0.0; len];
+            i16_to_f32(&input[..len], &mut output);
+            assert_eq!(output[..], (0..len).map(|x| input[x] as f32 / 32768.0).collect::<Vec<_>>()[..]);
+        }
+    }
+
+    #[test]
+    fn f32_to_i16_dither_matches_scalar() {
+        let mut seed = 0x9E3779B97F4A7C15;
+        for len in (0..70).chain(vec![1000]) {
+            let input = (0..len).map(|_| match rng(&mut seed) % 8 {
+                0 => 1.0,
+                1 => -1.0,
+                2 => (rng(&mut seed) % 1000) as f32 / 100.0 - 5.0,
+                _ => (rng(&mut seed) % 200001) as f32 / 100000.0 - 1.0,
+            }).collect::<Vec<_>>();
+            let start = rng(&mut seed) as u32;
+            let expected = input.iter().enumerate().map(|(k, &x)| {
+                let y = (x * 32768.0 + dither(start.wrapping_add(k as u32)) + 0.5).floor();
+                y.max(-32768.0).min(32767.0) as i16
+            }).collect::<Vec<_>>();
+
+            let mut output = vec![0; len];
+            f32_to_i16_dither(&input, &mut output, start);
+            assert_eq!(output, expected);
+
+            // Blocks whose seeds count the samples before them
+            let mut blocks = vec![0; len];
+            let split = len / 3;
+            f32_to_i16_dither(&input[..split], &mut blocks[..split], start);
+            f32_to_i16_dither(&input[split..], &mut blocks[split..], start.wrapping_add(split as u32));
+            assert_eq!(blocks, expected);
+        }
+    }
+
+    #[test]
+    fn f32_to_i16_dither_wraps_indices() {
+        // The indices of these samples wrap past u32::MAX within a vector
+        let input = (0..100).map(|x| x as f32 / 50.0 - 1.0).collect::<Vec<_>>();
+        for &start in &[u32::max_value(), u32::max_value() - 2, u32::max_value() - 40] {
+            let expected = input.iter().enumerate().map(|(k, &x)| {
+                let y = (x * 32768.0 + dither(start.wrapping_add(k as u32)) + 0.5).floor();
+                y.max(-32768.0).min(32767.0) as i16
+            }).collect::<Vec<_>>();
+            let mut output = vec![0; input.len()];
+            f32_to_i16_dither(&input, &mut output, start);
+            assert_eq!(output, expected);
+        }
+    }
+
+    #[test]
+    fn f32_to_i16_dither_keeps_order() {
+        // A ramp with steps much larger than the dither, which catches packs
+        // that swap the halves of a vector, as on AVX2
+        let input = (0..200).map(|x| x as f32 / 128.0 - 0.75).collect::<Vec<_>>();
+        let mut output = vec![0; input.len()];
+        f32_to_i16_dither(&input, &mut output, 0);
+        for (k, &y) in output.iter().enumerate() {
+            assert!((i32::from(y) - (k as i32 * 256 - 24576)).abs() <= 1, "{}: {}", k, y);
+        }
+    }
+
+    #[test]
+    fn dither_is_triangular() {
+        let (mut sum, mut ends) = (0.0f64, 0);
+        for index in 0..100000 {
+            let d = dither(index);
+            assert!(d >= -1.0 && d < 1.0);
+            sum += f64::from(d);
+            if d.abs() > 0.9 {
+                ends += 1;
+            }
+        }
+        assert!((sum / 100000.0).abs() < 0.01);
+        // A triangular distribution puts 1% of its samples past +-0.9
+        assert!(ends > 500 && ends < 1500, "{}", ends);
+    }
+
+    #[test]
+    fn s24_to_f32_is_exact() {
+        let mut seed = 0xDA942042E4DD58B5;
+        for len in 0..70 {
+            let input = (0..len * 3).map(|_| rng(&mut seed) as u8).collect::<Vec<_>>();
+            let mut output = vec![0.0; len];
+            s24_to_f32(&input, &mut output);
+            for (s, &y) in input.chunks(3).zip(&output) {
+                let x = i32::from(s[0]) | i32::from(s[1]) << 8 | i32::from(s[2] as i8) << 16;
+                assert_eq!(y, x as f32 / 8388608.0);
+            }
+        }
+    }
+
+    #[test]
+    fn stereo_round_trips() {
+        let mut seed = 0x2545F4914F6CDD1D;
+        for len in 0..70 {
+            let left = (0..len).map(|_| rng(&mut seed) as i16).collect::<Vec<_>>();
+            let right = (0..len).map(|_| rng(&mut seed) as i16).collect::<Vec<_>>();
+            let mut stereo = vec![0; len * 2];
+            interleave_stereo(&left, &right, &mut stereo);
+            assert_eq!(stereo, left.iter().zip(&right).flat_map(|(&l, &r)| vec![l, r]).collect::<Vec<_>>());
+
+            let (mut l, mut r) = (vec![0; len], vec![0; len]);
+            deinterleave_stereo(&stereo, &mut l, &mut r);
+            assert_eq!((l, r), (left.clone(), right.clone()));
+
+            let left = left.iter().map(|&x| f32::from(x)).collect::<Vec<_>>();
+            let right = right.iter().map(|&x| f32::from(x)).collect::<Vec<_>>();
+            let mut stereo = vec![0.0; len * 2];
+            interleave_stereo(&left, &right, &mut stereo);
+            let (mut l, mut r) = (vec![0.0; len], vec![0.0; len]);
+            deinterleave_stereo(&stereo, &mut l, &mut r);
+            assert_eq!((l, r), (left, right));
+        }
+    }
+
+    #[test]
+    fn mix_saturating_matches_scalar() {
+        let mut seed = 0x853C49E6748FEA9B;
+        for len in 0..70 {
+            let input = (0..len).map(|_| rng(&mut seed) as i16).collect::<Vec<_>>();
+            let mut output = (0..len).map(|_| rng(&mut seed) as i16).collect::<Vec<_>>();
+            let expected = input.iter().zip(&output).map(|(&x, &y)| {
+                (i32::from(x) + i32::from(y)).max(-32768).min(32767) as i16
+            }).collect::<Vec<_>>();
+            mix_saturating(&input, &mut output);
+            assert_eq!(output, expected);
+        }
+    }
+}
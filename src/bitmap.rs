@@ -0,0 +1,394 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Operations on bitmaps stored as slices of `u64`s.
+//!
+//! Bit `j` of word `i` of a bitmap stands for the position `64 * i + j`, so
+//! the least significant bit of the first word is position zero.
+//!
+//! Population counts use the carry-save adder scheme of Harley and Seal, as
+//! described by Muła, Kurz and Lemire: sixteen vectors are summed bitwise
+//! into counters of ones, twos, fours, eights and sixteens, and only the
+//! sixteens are counted with [`Popcnt`] for every block.
+//!
+//! [`Popcnt`]: ../intrin/popcnt/trait.Popcnt.html
+
+use crate::arch::current::vecs::*;
+use crate::vecs::*;
+use crate::intrin::popcnt::*;
+use crate::core::iter::Iterator;
+
+// Add three vectors bitwise, returning the carries and the sums.
+#[inline(always)]
+fn csa(a: u64s, b: u64s, c: u64s) -> (u64s, u64s) {
+    let u = a ^ b;
+    ((a & b) | (u & c), u ^ c)
+}
+
+// Return the number of set bits in the first `vectors` vectors returned by
+// `load`.
+#[inline(always)]
+fn harley_seal<F>(vectors: usize, load: F) -> u64 where F : Fn(usize) -> u64s {
+    let zero = u64s(0);
+    let (mut ones, mut twos, mut fours, mut eights) = (zero, zero, zero, zero);
+    let mut sixteens = 0;
+    let mut i = 0;
+    while i + 16 <= vectors {
+        let (twos_a, o) = csa(ones, load(i), load(i + 1));
+        let (twos_b, o) = csa(o, load(i + 2), load(i + 3));
+        let (fours_a, t) = csa(twos, twos_a, twos_b);
+        let (twos_a, o) = csa(o, load(i + 4), load(i + 5));
+        let (twos_b, o) = csa(o, load(i + 6), load(i + 7));
+        let (fours_b, t) = csa(t, twos_a, twos_b);
+        let (eights_a, f) = csa(fours, fours_a, fours_b);
+        let (twos_a, o) = csa(o, load(i + 8), load(i + 9));
+        let (twos_b, o) = csa(o, load(i + 10), load(i + 11));
+        let (fours_a, t) = csa(t, twos_a, twos_b);
+        let (twos_a, o) = csa(o, load(i + 12), load(i + 13));
+        let (twos_b, o) = csa(o, load(i + 14), load(i + 15));
+        let (fours_b, t) = csa(t, twos_a, twos_b);
+        let (eights_b, f) = csa(f, fours_a, fours_b);
+        let (s, e) = csa(eights, eights_a, eights_b);
+        sixteens += s.count_ones() as u64;
+        ones = o;
+        twos = t;
+        fours = f;
+        eights = e;
+        i += 16;
+    }
+
+    let mut total = 16 * sixteens
+        + 8 * eights.count_ones() as u64
+        + 4 * fours.count_ones() as u64
+        + 2 * twos.count_ones() as u64
+        + ones.count_ones() as u64;
+    for i in i..vectors {
+        total += load(i).count_ones() as u64;
+    }
+    total
+}
+
+// Write `vf` of the vectors of `a` and `b`, and `sf` of their remaining
+// words, to `out`.
+#[inline(always)]
+fn combine<V, S>(a: &[u64], b: &[u64], out: &mut [u64], vf: V, sf: S)
+    where V : Fn(u64s, u64s) -> u64s, S : Fn(u64, u64) -> u64 {
+    assert_eq!(a.len(), b.len(), "bitmaps must have the same length");
+    assert!(out.len() >= a.len(), "the output bitmap doesn't fit");
+    let width = u64s::WIDTH;
+    let mut i = 0;
+    while i + width <= a.len() {
+        vf(u64s::load(a, i), u64s::load(b, i)).store(out, i);
+        i += width;
+    }
+    for i in i..a.len() {
+        out[i] = sf(a[i], b[i]);
+    }
+}
+
+/// Write the intersection of the bitmaps `a` and `b` to `out`.
+///
+/// # Panics
+///
+/// This function will panic if `a` and `b` have different lengths, or if
+/// `out` is shorter than them.
+///
+/// ```
+/// extern crate faster;
+/// use faster::bitmap::and;
+///
+/// # fn main() {
+/// let mut out = [0; 2];
+/// and(&[0b1100, 1], &[0b1010, 3], &mut out);
+/// assert_eq!(out, [0b1000, 1]);
+/// # }
+/// ```
+pub fn and(a: &[u64], b: &[u64], out: &mut [u64]) {
+    combine(a, b, out, |x, y| x & y, |x, y| x & y);
+}
+
+/// Write the union of the bitmaps `a` and `b` to `out`.
+///
+/// # Panics
+///
+/// This function will panic if `a` and `b` have different lengths, or if
+/// `out` is shorter than them.
+///
+/// ```
+/// extern crate faster;
+/// use faster::bitmap::or;
+///
+/// # fn main() {
+/// let mut out = [0; 2];
+/// or(&[0b1100, 1], &[0b1010, 3], &mut out);
+/// assert_eq!(out, [0b1110, 3]);
+/// # }
+/// ```
+pub fn or(a: &[u64], b: &[u64], out: &mut [u64]) {
+    combine(a, b, out, |x, y| x | y, |x, y| x | y);
+}
+
+/// Write the symmetric difference of the bitmaps `a` and `b` to `out`.
+///
+/// # Panics
+///
+/// This function will panic if `a` and `b` have different lengths, or if
+/// `out` is shorter than them.
+///
+/// ```
+/// extern crate faster;
+/// use faster::bitmap::xor;
+///
+/// # fn main() {
+/// let mut out = [0; 2];
+/// xor(&[0b1100, 1], &[0b1010, 3], &mut out);
+/// assert_eq!(out, [0b0110, 2]);
+/// # }
+/// ```
+pub fn xor(a: &[u64], b: &[u64], out: &mut [u64]) {
+    combine(a, b, out, |x, y| x ^ y, |x, y| x ^ y);
+}
+
+/// Write the bits of `a` which aren't set in `b` to `out`.
+///
+/// # Panics
+///
+/// This function will panic if `a` and `b` have different lengths, or if
+/// `out` is shorter than them.
+///
+/// ```
+/// extern crate faster;
+/// use faster::bitmap::andnot;
+///
+/// # fn main() {
+/// let mut out = [0; 2];
+/// andnot(&[0b1100, 1], &[0b1010, 3], &mut out);
+/// assert_eq!(out, [0b0100, 0]);
+/// # }
+/// ```
+pub fn andnot(a: &[u64], b: &[u64], out: &mut [u64]) {
+    // Not every backend implements `Not` for its vectors
+    combine(a, b, out, |x, y| x & (y ^ u64s(!0)), |x, y| x & !y);
+}
+
+/// Return the number of set bits in `data`.
+///
+/// ```
+/// extern crate faster;
+/// use faster::bitmap::count_ones;
+///
+/// # fn main() {
+/// assert_eq!(count_ones(&[0b1011, !0, 0]), 67);
+/// # }
+/// ```
+pub fn count_ones(data: &[u64]) -> u64 {
+    let width = u64s::WIDTH;
+    let vectors = data.len() / width;
+    harley_seal(vectors, |i| u64s::load(data, i * width))
+        + data[vectors * width..].iter().map(|x| x.count_ones() as u64).sum::<u64>()
+}
+
+/// Return the number of bits set in both `a` and `b`, without writing their
+/// intersection anywhere.
+///
+/// # Panics
+///
+/// This function will panic if `a` and `b` have different lengths.
+///
+/// ```
+/// extern crate faster;
+/// use faster::bitmap::and_count;
+///
+/// # fn main() {
+/// assert_eq!(and_count(&[0b1100, !0], &[0b1010, 7]), 4);
+/// # }
+/// ```
+pub fn and_count(a: &[u64], b: &[u64]) -> u64 {
+    assert_eq!(a.len(), b.len(), "bitmaps must have the same length");
+    let width = u64s::WIDTH;
+    let vectors = a.len() / width;
+    harley_seal(vectors, |i| u64s::load(a, i * width) & u64s::load(b, i * width))
+        + a[vectors * width..].iter().zip(&b[vectors * width..])
+            .map(|(x, y)| (x & y).count_ones() as u64).sum::<u64>()
+}
+
+/// An iterator over the positions of the set bits of a bitmap, in ascending
+/// order. Positions can be taken one at a time, or written to a buffer in
+/// bulk with [`fill`].
+///
+/// [`fill`]: #method.fill
+#[derive(Clone, Debug)]
+pub struct SetBits<'a> {
+    data: &'a [u64],
+    // The index of the next word to load
+    word: usize,
+    // The bits of the last word loaded which haven't been returned yet
+    bits: u64,
+}
+
+/// Return an iterator over the positions of the set bits of `data`.
+///
+/// # Panics
+///
+/// This function will panic if `data` has positions which don't fit in a
+/// `u32`.
+///
+/// ```
+/// extern crate faster;
+/// use faster::bitmap::set_bits;
+///
+/// # fn main() {
+/// let mut out = [0u32; 4];
+/// let mut bits = set_bits(&[0b1001, 0, 1 << 63]);
+/// assert_eq!(bits.fill(&mut out), 3);
+/// assert_eq!(&out[..3], &[0, 3, 191]);
+/// assert_eq!(bits.fill(&mut out), 0);
+/// # }
+/// ```
+pub fn set_bits(data: &[u64]) -> SetBits {
+    assert!(data.len() <= 1 << 26, "bit positions of the bitmap don't fit in a u32");
+    SetBits { data, word: 0, bits: 0 }
+}
+
+impl<'a> SetBits<'a> {
+    // Load the next word with any bits set, returning false if there is none.
+    #[inline(always)]
+    fn refill(&mut self) -> bool {
+        let width = u64s::WIDTH;
+        while self.word < self.data.len() {
+            // Skip over whole vectors of empty words
+            while self.word + width <= self.data.len()
+                && u64s::load(self.data, self.word) == u64s(0) {
+                self.word += width;
+            }
+            if self.word == self.data.len() {
+                break;
+            }
+            self.bits = self.data[self.word];
+            self.word += 1;
+            if self.bits != 0 {
+                return true;
+            }
+        }
+        false
+    }
+
+    // Return the position of the lowest bit of `bits`.
+    #[inline(always)]
+    fn position(&self) -> u32 {
+        (self.word as u32 - 1) * 64 + self.bits.trailing_zeros()
+    }
+
+    /// Write the next positions to `out` until it is full or every position
+    /// has been written, and return the number of positions written.
+    pub fn fill(&mut self, out: &mut [u32]) -> usize {
+        let mut o = 0;
+        while o < out.len() && (self.bits != 0 || self.refill()) {
+            while self.bits != 0 && o < out.len() {
+                out[o] = self.position();
+                self.bits &= self.bits - 1;
+                o += 1;
+            }
+        }
+        o
+    }
+}
+
+impl<'a> Iterator for SetBits<'a> {
+    type Item = u32;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<u32> {
+        if self.bits != 0 || self.refill() {
+            let ret = self.position();
+            self.bits &= self.bits - 1;
+            Some(ret)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::rng;
+
+    // Return a bitmap with runs of empty, full and random words
+    fn bitmap(len: usize, seed: &mut u64) -> Vec<u64> {
+        (0..len).map(|_| match rng(seed) % 4 {
+            0 => 0,
+            1 => !0,
+            _ => rng(seed) & rng(seed),
+        }).collect()
+    }
+
+    #[test]
+    fn ops_match_scalar() {
+        let mut seed = 0x9E3779B97F4A7C15;
+        for len in 0..40 {
+            let (a, b) = (bitmap(len, &mut seed), bitmap(len, &mut seed));
+            let mut out = vec![0x5555; len + 2];
+            and(&a, &b, &mut out);
+            assert!(out[..len].iter().zip(a.iter().zip(&b)).all(|(&o, (&x, &y))| o == x & y));
+            or(&a, &b, &mut out);
+            assert!(out[..len].iter().zip(a.iter().zip(&b)).all(|(&o, (&x, &y))| o == x | y));
+            xor(&a, &b, &mut out);
+            assert!(out[..len].iter().zip(a.iter().zip(&b)).all(|(&o, (&x, &y))| o == x ^ y));
+            andnot(&a, &b, &mut out);
+            assert!(out[..len].iter().zip(a.iter().zip(&b)).all(|(&o, (&x, &y))| o == x & !y));
+            assert_eq!(&out[len..], &[0x5555, 0x5555]);
+        }
+    }
+
+    #[test]
+    fn counts_match_scalar() {
+        let mut seed = 0xDA942042E4DD58B5;
+        for len in (0..100).chain(vec![255, 256, 257, 1000]) {
+            let (a, b) = (bitmap(len, &mut seed), bitmap(len, &mut seed));
+            assert_eq!(count_ones(&a), a.iter().map(|x| x.count_ones() as u64).sum::<u64>());
+            assert_eq!(and_count(&a, &b),
+                       a.iter().zip(&b).map(|(x, y)| (x & y).count_ones() as u64).sum::<u64>());
+        }
+        assert_eq!(count_ones(&[!0; 333]), 333 * 64);
+    }
+
+    #[test]
+    #[should_panic]
+    fn ops_check_lengths() {
+        and(&[1, 2], &[1], &mut [0, 0]);
+    }
+
+    #[test]
+    fn set_bits_match_scalar() {
+        let mut seed = 0x2545F4914F6CDD1D;
+        for len in 0..70 {
+            // Mostly empty bitmaps, so whole vectors are skipped
+            let data = (0..len).map(|_| if rng(&mut seed) % 5 == 0 { rng(&mut seed) } else { 0 })
+                .collect::<Vec<_>>();
+            let expected = (0..len * 64)
+                .filter(|&p| data[p / 64] & (1 << (p % 64)) != 0)
+                .map(|p| p as u32)
+                .collect::<Vec<_>>();
+            assert_eq!(set_bits(&data).collect::<Vec<_>>(), expected);
+
+            for size in 1..10 {
+                let mut bits = set_bits(&data);
+                let mut buf = vec![0; size];
+                let mut positions = Vec::new();
+                loop {
+                    let n = bits.fill(&mut buf);
+                    if n == 0 {
+                        break;
+                    }
+                    positions.extend_from_slice(&buf[..n]);
+                }
+                assert_eq!(positions, expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,539 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Searching within byte slices.
+//!
+//! These functions compare a whole vector of the haystack against the needle
+//! at once, and use [`Movemask`] to find the position of a match. Loads are
+//! aligned to the width of the vector after the first one, and the inner loop
+//! is unrolled four times.
+//!
+//! [`Movemask`]: ../intrin/movemask/trait.Movemask.html
+
+use crate::arch::current::vecs::*;
+use crate::vecs::*;
+use crate::intrin::eq::*;
+use crate::intrin::movemask::*;
+
+const UNROLL: usize = 4;
+
+#[inline(always)]
+fn load(haystack: &[u8], offset: usize) -> u8s {
+    debug_assert!(offset + u8s::WIDTH <= haystack.len());
+    unsafe { u8s::load_unchecked(haystack, offset) }
+}
+
+// Return the number of bytes to skip from the start of `haystack` to reach
+// the next address aligned to the width of a vector.
+#[inline(always)]
+fn align_offset(haystack: &[u8]) -> usize {
+    let misalignment = haystack.as_ptr() as usize % u8s::WIDTH;
+    if misalignment == 0 { 0 } else { u8s::WIDTH - misalignment }
+}
+
+// Return the index of the first byte of `haystack` whose lane is set in
+// `vector`, or which satisfies `scalar` if `haystack` is shorter than a
+// vector.
+#[inline(always)]
+fn find_by<V, S>(haystack: &[u8], vector: V, scalar: S) -> Option<usize>
+    where V : Fn(u8s) -> u8s, S : Fn(u8) -> bool {
+    let width = u8s::WIDTH;
+    let len = haystack.len();
+    if len < width {
+        return haystack.iter().position(|&b| scalar(b));
+    }
+
+    // Check the first vector unaligned, then skip to an aligned boundary
+    let mask = vector(load(haystack, 0)).movemask();
+    if mask != 0 {
+        return Some(mask.trailing_zeros() as usize);
+    }
+    let mut i = width - haystack.as_ptr() as usize % width;
+
+    while i + UNROLL * width <= len {
+        let a = vector(load(haystack, i));
+        let b = vector(load(haystack, i + width));
+        let c = vector(load(haystack, i + width * 2));
+        let d = vector(load(haystack, i + width * 3));
+        if (a | b | c | d).movemask() != 0 {
+            for (n, v) in [a, b, c, d].iter().enumerate() {
+                let mask = v.movemask();
+                if mask != 0 {
+                    return Some(i + n * width + mask.trailing_zeros() as usize);
+                }
+            }
+        }
+        i += UNROLL * width;
+    }
+
+    while i + width <= len {
+        let mask = vector(load(haystack, i)).movemask();
+        if mask != 0 {
+            return Some(i + mask.trailing_zeros() as usize);
+        }
+        i += width;
+    }
+
+    // The final vector overlaps bytes we've already searched, which can't
+    // contain a match
+    if i < len {
+        let mask = vector(load(haystack, len - width)).movemask();
+        if mask != 0 {
+            return Some(len - width + mask.trailing_zeros() as usize);
+        }
+    }
+    None
+}
+
+// Return the index of the last byte of `haystack` whose lane is set in
+// `vector`, or which satisfies `scalar` if `haystack` is shorter than a
+// vector.
+#[inline(always)]
+fn rfind_by<V, S>(haystack: &[u8], vector: V, scalar: S) -> Option<usize>
+    where V : Fn(u8s) -> u8s, S : Fn(u8) -> bool {
+    let width = u8s::WIDTH;
+    let len = haystack.len();
+    if len < width {
+        return haystack.iter().rposition(|&b| scalar(b));
+    }
+
+    let last = |mask: u32| 31 - mask.leading_zeros() as usize;
+
+    // Check the last vector unaligned, then skip to an aligned boundary
+    let mask = vector(load(haystack, len - width)).movemask();
+    if mask != 0 {
+        return Some(len - width + last(mask));
+    }
+    let mut end = len - (haystack.as_ptr() as usize + len) % width;
+
+    while end >= UNROLL * width {
+        let a = vector(load(haystack, end - width));
+        let b = vector(load(haystack, end - width * 2));
+        let c = vector(load(haystack, end - width * 3));
+        let d = vector(load(haystack, end - width * 4));
+        if (a | b | c | d).movemask() != 0 {
+            for (n, v) in [a, b, c, d].iter().enumerate() {
+                let mask = v.movemask();
+                if mask != 0 {
+                    return Some(end - (n + 1) * width + last(mask));
+                }
+            }
+        }
+        end -= UNROLL * width;
+    }
+
+    while end >= width {
+        let mask = vector(load(haystack, end - width)).movemask();
+        if mask != 0 {
+            return Some(end - width + last(mask));
+        }
+        end -= width;
+    }
+
+    // The first vector overlaps bytes we've already searched, which can't
+    // contain a match
+    if end > 0 {
+        let mask = vector(load(haystack, 0)).movemask();
+        if mask != 0 {
+            return Some(last(mask));
+        }
+    }
+    None
+}
+
+/// Return the index of the first occurrence of `needle` in `haystack`.
+///
+/// ```
+/// extern crate faster;
+/// use faster::bytes::find_byte;
+///
+/// # fn main() {
+/// assert_eq!(find_byte(b"key=value", b'='), Some(3));
+/// assert_eq!(find_byte(b"key value", b'='), None);
+/// # }
+/// ```
+pub fn find_byte(haystack: &[u8], needle: u8) -> Option<usize> {
+    let n = u8s(needle);
+    find_by(haystack, |v| v.eq_mask(n), |b| b == needle)
+}
+
+/// Return the index of the last occurrence of `needle` in `haystack`.
+///
+/// ```
+/// extern crate faster;
+/// use faster::bytes::rfind_byte;
+///
+/// # fn main() {
+/// assert_eq!(rfind_byte(b"/usr/local/bin", b'/'), Some(10));
+/// assert_eq!(rfind_byte(b"bin", b'/'), None);
+/// # }
+/// ```
+pub fn rfind_byte(haystack: &[u8], needle: u8) -> Option<usize> {
+    let n = u8s(needle);
+    rfind_by(haystack, |v| v.eq_mask(n), |b| b == needle)
+}
+
+/// Return the index of the first occurrence of either `n1` or `n2` in
+/// `haystack`.
+///
+/// ```
+/// extern crate faster;
+/// use faster::bytes::find_any_of2;
+///
+/// # fn main() {
+/// assert_eq!(find_any_of2(b"a,b;c", b';', b','), Some(1));
+/// # }
+/// ```
+pub fn find_any_of2(haystack: &[u8], n1: u8, n2: u8) -> Option<usize> {
+    let (v1, v2) = (u8s(n1), u8s(n2));
+    find_by(haystack, |v| v.eq_mask(v1) | v.eq_mask(v2), |b| b == n1 || b == n2)
+}
+
+/// Return the index of the first occurrence of any of `n1`, `n2` or `n3` in
+/// `haystack`.
+///
+/// ```
+/// extern crate faster;
+/// use faster::bytes::find_any_of3;
+///
+/// # fn main() {
+/// assert_eq!(find_any_of3(b"plain text\r\n", b'\r', b'\n', b'\t'), Some(10));
+/// # }
+/// ```
+pub fn find_any_of3(haystack: &[u8], n1: u8, n2: u8, n3: u8) -> Option<usize> {
+    let (v1, v2, v3) = (u8s(n1), u8s(n2), u8s(n3));
+    find_by(haystack, |v| v.eq_mask(v1) | v.eq_mask(v2) | v.eq_mask(v3),
+            |b| b == n1 || b == n2 || b == n3)
+}
+
+/// Return the number of occurrences of `needle` in `haystack`.
+///
+/// ```
+/// extern crate faster;
+/// use faster::bytes::count_byte;
+///
+/// # fn main() {
+/// assert_eq!(count_byte(b"one\ntwo\nthree\n", b'\n'), 3);
+/// # }
+/// ```
+pub fn count_byte(haystack: &[u8], needle: u8) -> usize {
+    let width = u8s::WIDTH;
+    let len = haystack.len();
+    let n = u8s(needle);
+
+    let head = align_offset(haystack).min(len);
+    let mut count = haystack[..head].iter().filter(|&&b| b == needle).count();
+    let mut i = head;
+
+    while i + UNROLL * width <= len {
+        count += load(haystack, i).eq_mask(n).movemask().count_ones() as usize
+            + load(haystack, i + width).eq_mask(n).movemask().count_ones() as usize
+            + load(haystack, i + width * 2).eq_mask(n).movemask().count_ones() as usize
+            + load(haystack, i + width * 3).eq_mask(n).movemask().count_ones() as usize;
+        i += UNROLL * width;
+    }
+
+    while i + width <= len {
+        count += load(haystack, i).eq_mask(n).movemask().count_ones() as usize;
+        i += width;
+    }
+
+    count + haystack[i..].iter().filter(|&&b| b == needle).count()
+}
+
+/// A substring searcher for a fixed needle, which may be reused across many
+/// haystacks.
+///
+/// Candidate positions are found by comparing the first and last bytes of the
+/// needle against a vector of the haystack at once, and are then verified
+/// with a scalar comparison.
+///
+/// ```
+/// extern crate faster;
+/// use faster::bytes::Finder;
+///
+/// # fn main() {
+/// let finder = Finder::new(b"needle");
+/// assert_eq!(finder.find(b"haystack with a needle in it"), Some(16));
+/// assert_eq!(finder.find(b"haystack without one"), None);
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Finder<'n> {
+    needle: &'n [u8],
+    first: u8s,
+    last: u8s,
+}
+
+impl<'n> Finder<'n> {
+    /// Create a new searcher for `needle`.
+    pub fn new(needle: &'n [u8]) -> Self {
+        Self {
+            needle,
+            first: u8s(needle.first().cloned().unwrap_or(0)),
+            last: u8s(needle.last().cloned().unwrap_or(0)),
+        }
+    }
+
+    /// Return the needle this searcher looks for.
+    pub fn needle(&self) -> &'n [u8] {
+        self.needle
+    }
+
+    /// Return the index of the first occurrence of the needle in `haystack`.
+    /// An empty needle is found at index 0.
+    pub fn find(&self, haystack: &[u8]) -> Option<usize> {
+        let width = u8s::WIDTH;
+        let needle = self.needle;
+        let n = needle.len();
+        if n == 0 {
+            return Some(0);
+        } else if n > haystack.len() {
+            return None;
+        } else if n == 1 {
+            return find_byte(haystack, needle[0]);
+        }
+
+        let mut i = 0;
+        while i + n - 1 + width <= haystack.len() {
+            let mut mask = (load(haystack, i).eq_mask(self.first)
+                            & load(haystack, i + n - 1).eq_mask(self.last)).movemask();
+            while mask != 0 {
+                let start = i + mask.trailing_zeros() as usize;
+                if haystack[start + 1..start + n - 1] == needle[1..n - 1] {
+                    return Some(start);
+                }
+                // Clear the lowest set bit
+                mask &= mask - 1;
+            }
+            i += width;
+        }
+
+        (i..haystack.len() - n + 1).find(|&start| &haystack[start..start + n] == needle)
+    }
+
+    /// Return an iterator over the indices of the non-overlapping occurrences
+    /// of the needle in `haystack`.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::bytes::Finder;
+    ///
+    /// # fn main() {
+    /// let finder = Finder::new(b"aa");
+    /// assert_eq!(finder.find_iter(b"aaaxaa").collect::<Vec<_>>(), vec![0, 4]);
+    /// # }
+    /// ```
+    pub fn find_iter<'h>(&self, haystack: &'h [u8]) -> FindIter<'h, 'n> {
+        FindIter { finder: *self, haystack, pos: 0 }
+    }
+}
+
+/// An iterator over the indices of the non-overlapping occurrences of a
+/// needle in a haystack, created by [`Finder::find_iter`] or
+/// [`find_substring_iter`].
+///
+/// [`Finder::find_iter`]: struct.Finder.html#method.find_iter
+/// [`find_substring_iter`]: fn.find_substring_iter.html
+#[derive(Clone, Debug)]
+pub struct FindIter<'h, 'n> {
+    finder: Finder<'n>,
+    haystack: &'h [u8],
+    pos: usize,
+}
+
+impl<'h, 'n> Iterator for FindIter<'h, 'n> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        if self.pos > self.haystack.len() {
+            return None;
+        }
+        match self.finder.find(&self.haystack[self.pos..]) {
+            Some(idx) => {
+                let start = self.pos + idx;
+                // Step over empty matches so we always make progress
+                self.pos = start + self.finder.needle.len().max(1);
+                Some(start)
+            },
+            None => {
+                self.pos = self.haystack.len() + 1;
+                None
+            }
+        }
+    }
+}
+
+/// Return the index of the first occurrence of `needle` in `haystack`.
+///
+/// ```
+/// extern crate faster;
+/// use faster::bytes::find_substring;
+///
+/// # fn main() {
+/// assert_eq!(find_substring(b"GET /index.html HTTP/1.1", b"HTTP/"), Some(16));
+/// assert_eq!(find_substring(b"GET /index.html", b"HTTP/"), None);
+/// # }
+/// ```
+pub fn find_substring(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    Finder::new(needle).find(haystack)
+}
+
+/// Return an iterator over the indices of the non-overlapping occurrences of
+/// `needle` in `haystack`.
+///
+/// ```
+/// extern crate faster;
+/// use faster::bytes::find_substring_iter;
+///
+/// # fn main() {
+/// let matches = find_substring_iter(b"a, b, c", b", ").collect::<Vec<_>>();
+/// assert_eq!(matches, vec![1, 4]);
+/// # }
+/// ```
+pub fn find_substring_iter<'h, 'n>(haystack: &'h [u8], needle: &'n [u8]) -> FindIter<'h, 'n> {
+    Finder::new(needle).find_iter(haystack)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::rng;
+
+    // Run `f` on subslices of a buffer at every alignment and tail length up
+    // to twice the width of a vector, with every byte set to `fill`.
+    fn for_each_slice<F>(fill: u8, mut f: F) where F : FnMut(&mut [u8]) {
+        let width = u8s::WIDTH;
+        let mut buf = vec![fill; width * 8];
+        for offset in 0..width {
+            for len in 0..=(2 * width) {
+                f(&mut buf[offset..offset + len]);
+            }
+        }
+        // Lengths long enough to exercise the unrolled loops
+        for len in (2 * width..width * 7).step_by(width / 2 + 1) {
+            f(&mut buf[1..1 + len]);
+        }
+    }
+
+    #[test]
+    fn find_byte_every_position() {
+        for_each_slice(0, |s| {
+            assert_eq!(find_byte(s, 1), None);
+            assert_eq!(rfind_byte(s, 1), None);
+            for i in 0..s.len() {
+                s[i] = 1;
+                assert_eq!(find_byte(s, 1), Some(i));
+                assert_eq!(rfind_byte(s, 1), Some(i));
+                s[i] = 0;
+            }
+        });
+    }
+
+    #[test]
+    fn find_byte_first_and_last() {
+        for_each_slice(7, |s| {
+            let len = s.len();
+            assert_eq!(find_byte(s, 7), if len > 0 { Some(0) } else { None });
+            assert_eq!(rfind_byte(s, 7), if len > 0 { Some(len - 1) } else { None });
+        });
+    }
+
+    #[test]
+    fn find_any_every_position() {
+        for_each_slice(0, |s| {
+            assert_eq!(find_any_of2(s, 1, 2), None);
+            assert_eq!(find_any_of3(s, 1, 2, 3), None);
+            for i in 0..s.len() {
+                for j in i + 1..s.len() {
+                    s[i] = 2;
+                    s[j] = 3;
+                    assert_eq!(find_any_of2(s, 1, 2), Some(i));
+                    assert_eq!(find_any_of2(s, 3, 1), Some(j));
+                    assert_eq!(find_any_of3(s, 1, 2, 3), Some(i));
+                    assert_eq!(find_any_of3(s, 3, 1, 4), Some(j));
+                    s[i] = 0;
+                    s[j] = 0;
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn count_byte_every_length() {
+        for_each_slice(0, |s| {
+            assert_eq!(count_byte(s, 1), 0);
+            assert_eq!(count_byte(s, 0), s.len());
+            for i in (0..s.len()).step_by(3) {
+                s[i] = 1;
+            }
+            assert_eq!(count_byte(s, 1), (s.len() + 2) / 3);
+            for b in s.iter_mut() {
+                *b = 0;
+            }
+        });
+    }
+
+    fn find_naive(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+        if needle.len() > haystack.len() {
+            return None;
+        }
+        (0..haystack.len() - needle.len() + 1).find(|&i| &haystack[i..i + needle.len()] == needle)
+    }
+
+    fn find_iter_naive(haystack: &[u8], needle: &[u8]) -> Vec<usize> {
+        let mut ret = Vec::new();
+        let mut pos = 0;
+        while let Some(i) = find_naive(&haystack[pos..], needle) {
+            ret.push(pos + i);
+            pos += i + needle.len().max(1);
+            if pos > haystack.len() {
+                break;
+            }
+        }
+        ret
+    }
+
+    #[test]
+    fn find_substring_random() {
+        let width = u8s::WIDTH;
+        let mut seed = 0x853C49E6748FEA9B;
+        // A small alphabet makes partial matches common
+        let haystack = (0..width * 12).map(|_| b'a' + (rng(&mut seed) % 3) as u8).collect::<Vec<u8>>();
+
+        for n in 0..=(2 * width + 3) {
+            for _ in 0..8 {
+                let start = rng(&mut seed) as usize % (haystack.len() - n);
+                let needle = haystack[start..start + n].to_vec();
+                let finder = Finder::new(&needle);
+                for len in (0..haystack.len()).step_by(7) {
+                    let h = &haystack[len % width..len];
+                    assert_eq!(finder.find(h), find_naive(h, &needle));
+                    assert_eq!(find_substring(h, &needle), find_naive(h, &needle));
+                }
+                assert_eq!(finder.find_iter(&haystack).collect::<Vec<_>>(),
+                           find_iter_naive(&haystack, &needle));
+            }
+        }
+    }
+
+    #[test]
+    fn find_substring_edges() {
+        assert_eq!(find_substring(b"", b""), Some(0));
+        assert_eq!(find_substring(b"", b"a"), None);
+        assert_eq!(find_substring(b"abc", b"abcd"), None);
+        assert_eq!(find_substring(b"abc", b"abc"), Some(0));
+        assert_eq!(find_substring_iter(b"abc", b"").collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+        assert_eq!(find_substring_iter(b"aaaa", b"aa").collect::<Vec<_>>(), vec![0, 2]);
+
+        let mut long = vec![b'x'; 300];
+        long[299] = b'y';
+        assert_eq!(find_substring(&long, b"xy"), Some(298));
+        assert_eq!(find_substring(&long, b"yx"), None);
+    }
+}
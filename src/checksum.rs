@@ -0,0 +1,476 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Streaming checksums of byte slices.
+//!
+//! Each checksum is a small state struct: create it with `new`, feed it
+//! slices of any length with `update`, and read the checksum of everything
+//! so far with `finish`.
+//!
+//! Adler-32 and the Fletcher checksums keep a sum of the bytes and a sum of
+//! those sums. Both are computed a vector at a time: [`SumBytes`] adds up the
+//! bytes, and [`MaddBytes`] weights each byte by its distance from the end
+//! of the vector. CRC32C uses the `crc32` instruction of SSE4.2 where it's
+//! available, and a lookup table otherwise.
+//!
+//! [`SumBytes`]: ../intrin/sum_bytes/trait.SumBytes.html
+//! [`MaddBytes`]: ../intrin/madd_bytes/trait.MaddBytes.html
+
+use crate::arch::current::vecs::*;
+use crate::vecs::*;
+use crate::intrin::madd_bytes::*;
+use crate::intrin::sum_bytes::*;
+use crate::intrin::upcast::*;
+
+// The most bytes we sum at once; small enough that no lane of the weighted
+// sums overflows an i32.
+const BLOCK: usize = 4096;
+
+// The sums of a run of n bytes b_i, split by the parity of i: `sum[p]` is the
+// sum of the b_i, and `weighted[p]` the sum of (n - i) * b_i, over each i
+// with i % 2 == p. Without splitting, every byte counts towards parity 0.
+#[derive(Default)]
+struct Sums {
+    sum: [u64; 2],
+    weighted: [u64; 2],
+}
+
+#[inline(always)]
+fn sums(data: &[u8], split: bool) -> Sums {
+    debug_assert!(data.len() <= BLOCK);
+    let width = u8s::WIDTH;
+    let vectors = data.len() / width;
+    let parities = if split { 2 } else { 1 };
+    let mut ret = Sums::default();
+
+    for p in 0..parities {
+        // Weight each lane by its distance from the end of the vector, and
+        // zero the lanes of the other parity
+        let mut lanes = [0i8; 64];
+        let mut mask = [0u8; 64];
+        for l in (0..width).filter(|l| !split || l % 2 == p) {
+            lanes[l] = (width - l) as i8;
+            mask[l] = 0xFF;
+        }
+        let weights = i8s::load(&lanes, 0);
+        let mask = u8s::load(&mask, 0);
+
+        // `before` adds up the sum of the vectors before each vector, which
+        // is the weight each of them gains from that vector
+        let (mut sum, mut before, mut weighted) = (u64s(0), u64s(0), i32s(0));
+        for k in 0..vectors {
+            let v = u8s::load(data, k * width);
+            before = before + sum;
+            sum = sum + (v & mask).sum_bytes();
+            let (lo, hi) = v.madd_bytes(weights).upcast();
+            weighted = weighted + lo + hi;
+        }
+
+        let tail = (data.len() - vectors * width) as u64;
+        let (mut s, mut b, mut w) = (0u64, 0u64, 0u64);
+        for i in 0..u64s::WIDTH {
+            s += sum.extract(i);
+            b += before.extract(i);
+        }
+        for i in 0..i32s::WIDTH {
+            w += weighted.extract(i) as u64;
+        }
+        ret.sum[p] = s;
+        ret.weighted[p] = b * width as u64 + w + s * tail;
+    }
+
+    let n = data.len();
+    for (i, &b) in data.iter().enumerate().skip(vectors * width) {
+        let p = if split { i % 2 } else { 0 };
+        ret.sum[p] += b as u64;
+        ret.weighted[p] += (n - i) as u64 * b as u64;
+    }
+    ret
+}
+
+/// The Adler-32 checksum of RFC 1950, as used by zlib.
+///
+/// ```
+/// extern crate faster;
+/// use faster::checksum::Adler32;
+///
+/// # fn main() {
+/// let mut adler = Adler32::new();
+/// adler.update(b"Wiki");
+/// adler.update(b"pedia");
+/// assert_eq!(adler.finish(), 0x11E6_0398);
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Adler32 {
+    a: u32,
+    b: u32,
+}
+
+impl Adler32 {
+    const MOD: u64 = 65521;
+
+    /// Return the state of a checksum of no bytes.
+    pub fn new() -> Self {
+        Adler32 { a: 1, b: 0 }
+    }
+
+    /// Add `data` to the checksum.
+    pub fn update(&mut self, data: &[u8]) {
+        let (mut a, mut b) = (self.a as u64, self.b as u64);
+        for block in data.chunks(BLOCK) {
+            let sums = sums(block, false);
+            b = (b + block.len() as u64 * a + sums.weighted[0]) % Self::MOD;
+            a = (a + sums.sum[0]) % Self::MOD;
+        }
+        self.a = a as u32;
+        self.b = b as u32;
+    }
+
+    /// Return the checksum of the bytes added so far.
+    pub fn finish(&self) -> u32 {
+        self.b << 16 | self.a
+    }
+}
+
+impl Default for Adler32 {
+    fn default() -> Self {
+        Adler32::new()
+    }
+}
+
+/// The Fletcher-16 checksum, which sums bytes modulo 255.
+///
+/// ```
+/// extern crate faster;
+/// use faster::checksum::Fletcher16;
+///
+/// # fn main() {
+/// let mut fletcher = Fletcher16::new();
+/// fletcher.update(b"abcde");
+/// assert_eq!(fletcher.finish(), 0xC8F0);
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Fletcher16 {
+    a: u16,
+    b: u16,
+}
+
+impl Fletcher16 {
+    const MOD: u64 = 255;
+
+    /// Return the state of a checksum of no bytes.
+    pub fn new() -> Self {
+        Fletcher16 { a: 0, b: 0 }
+    }
+
+    /// Add `data` to the checksum.
+    pub fn update(&mut self, data: &[u8]) {
+        let (mut a, mut b) = (self.a as u64, self.b as u64);
+        for block in data.chunks(BLOCK) {
+            let sums = sums(block, false);
+            b = (b + block.len() as u64 * a + sums.weighted[0]) % Self::MOD;
+            a = (a + sums.sum[0]) % Self::MOD;
+        }
+        self.a = a as u16;
+        self.b = b as u16;
+    }
+
+    /// Return the checksum of the bytes added so far.
+    pub fn finish(&self) -> u16 {
+        self.b << 8 | self.a
+    }
+}
+
+impl Default for Fletcher16 {
+    fn default() -> Self {
+        Fletcher16::new()
+    }
+}
+
+/// The Fletcher-32 checksum, which sums little-endian 16-bit words modulo
+/// 65535. An odd byte at the end is summed as if followed by a zero.
+///
+/// ```
+/// extern crate faster;
+/// use faster::checksum::Fletcher32;
+///
+/// # fn main() {
+/// let mut fletcher = Fletcher32::new();
+/// fletcher.update(b"abc");
+/// fletcher.update(b"de");
+/// assert_eq!(fletcher.finish(), 0xF04F_C729);
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Fletcher32 {
+    a: u32,
+    b: u32,
+    // The first byte of a word whose second byte we haven't seen
+    pending: Option<u8>,
+}
+
+impl Fletcher32 {
+    const MOD: u64 = 65535;
+
+    /// Return the state of a checksum of no bytes.
+    pub fn new() -> Self {
+        Fletcher32 { a: 0, b: 0, pending: None }
+    }
+
+    #[inline(always)]
+    fn add_word(&mut self, word: u16) {
+        self.a = ((self.a as u64 + word as u64) % Self::MOD) as u32;
+        self.b = ((self.b as u64 + self.a as u64) % Self::MOD) as u32;
+    }
+
+    /// Add `data` to the checksum.
+    pub fn update(&mut self, mut data: &[u8]) {
+        if let (Some(lo), Some(&hi)) = (self.pending, data.first()) {
+            self.add_word(u16::from(lo) | u16::from(hi) << 8);
+            self.pending = None;
+            data = &data[1..];
+        }
+
+        let (mut a, mut b) = (self.a as u64, self.b as u64);
+        let words = data.len() / 2 * 2;
+        for block in data[..words].chunks(BLOCK) {
+            // Byte i of the block is half of word i / 2, and its weight
+            // n - i is twice the weight of the word, or one less for the
+            // high byte
+            let sums = sums(block, true);
+            let sum = sums.sum[0] + (sums.sum[1] << 8);
+            let weighted = sums.weighted[0] / 2 + (((sums.weighted[1] + sums.sum[1]) / 2) << 8);
+            b = (b + (block.len() / 2) as u64 * a + weighted) % Self::MOD;
+            a = (a + sum) % Self::MOD;
+        }
+        self.a = a as u32;
+        self.b = b as u32;
+
+        if words < data.len() {
+            self.pending = Some(data[words]);
+        }
+    }
+
+    /// Return the checksum of the bytes added so far.
+    pub fn finish(&self) -> u32 {
+        let mut state = *self;
+        if let Some(lo) = state.pending {
+            state.add_word(u16::from(lo));
+        }
+        state.b << 16 | state.a
+    }
+}
+
+impl Default for Fletcher32 {
+    fn default() -> Self {
+        Fletcher32::new()
+    }
+}
+
+/// The CRC-32C (Castagnoli) checksum, as used by iSCSI, SCTP and ext4.
+///
+/// ```
+/// extern crate faster;
+/// use faster::checksum::Crc32c;
+///
+/// # fn main() {
+/// let mut crc = Crc32c::new();
+/// crc.update(b"12345");
+/// crc.update(b"6789");
+/// assert_eq!(crc.finish(), 0xE306_9283);
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Crc32c {
+    state: u32,
+}
+
+impl Crc32c {
+    /// Return the state of a checksum of no bytes.
+    pub fn new() -> Self {
+        Crc32c { state: !0 }
+    }
+
+    /// Add `data` to the checksum.
+    pub fn update(&mut self, data: &[u8]) {
+        self.state = crc32c_update(self.state, data);
+    }
+
+    /// Return the checksum of the bytes added so far.
+    pub fn finish(&self) -> u32 {
+        !self.state
+    }
+}
+
+impl Default for Crc32c {
+    fn default() -> Self {
+        Crc32c::new()
+    }
+}
+
+#[inline(always)]
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse4.2"))]
+fn crc32c_update(mut crc: u32, data: &[u8]) -> u32 {
+    use crate::vektor::x86::{_mm_crc32_u32, _mm_crc32_u8};
+    optimized!();
+    let mut words = data.chunks_exact(4);
+    for word in &mut words {
+        let word = u32::from(word[0]) | u32::from(word[1]) << 8
+            | u32::from(word[2]) << 16 | u32::from(word[3]) << 24;
+        crc = unsafe { _mm_crc32_u32(crc, word) };
+    }
+    for &b in words.remainder() {
+        crc = unsafe { _mm_crc32_u8(crc, b) };
+    }
+    crc
+}
+
+#[inline(always)]
+#[cfg(not(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse4.2")))]
+fn crc32c_update(mut crc: u32, data: &[u8]) -> u32 {
+    fallback!();
+    for &b in data {
+        crc = CRC32C_TABLE[((crc ^ b as u32) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    crc
+}
+
+#[cfg(not(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse4.2")))]
+const CRC32C_TABLE: [u32; 256] = crc32c_table();
+
+#[cfg(any(test, not(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse4.2"))))]
+const fn crc32c_table() -> [u32; 256] {
+    // The reflected Castagnoli polynomial
+    const POLY: u32 = 0x82F6_3B78;
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 { crc >> 1 ^ POLY } else { crc >> 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+/// Return the Adler-32 checksum of `data`.
+pub fn adler32(data: &[u8]) -> u32 {
+    let mut adler = Adler32::new();
+    adler.update(data);
+    adler.finish()
+}
+
+/// Return the Fletcher-16 checksum of `data`.
+pub fn fletcher16(data: &[u8]) -> u16 {
+    let mut fletcher = Fletcher16::new();
+    fletcher.update(data);
+    fletcher.finish()
+}
+
+/// Return the Fletcher-32 checksum of `data`.
+pub fn fletcher32(data: &[u8]) -> u32 {
+    let mut fletcher = Fletcher32::new();
+    fletcher.update(data);
+    fletcher.finish()
+}
+
+/// Return the CRC-32C checksum of `data`.
+pub fn crc32c(data: &[u8]) -> u32 {
+    let mut crc = Crc32c::new();
+    crc.update(data);
+    crc.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::rng;
+
+    fn adler32_scalar(data: &[u8]) -> u32 {
+        let (mut a, mut b) = (1u32, 0u32);
+        for &x in data {
+            a = (a + x as u32) % 65521;
+            b = (b + a) % 65521;
+        }
+        b << 16 | a
+    }
+
+    fn fletcher16_scalar(data: &[u8]) -> u16 {
+        let (mut a, mut b) = (0u16, 0u16);
+        for &x in data {
+            a = (a + x as u16) % 255;
+            b = (b + a) % 255;
+        }
+        b << 8 | a
+    }
+
+    fn fletcher32_scalar(data: &[u8]) -> u32 {
+        let (mut a, mut b) = (0u32, 0u32);
+        for word in data.chunks(2) {
+            let word = word[0] as u32 | word.get(1).map_or(0, |&x| x as u32) << 8;
+            a = (a + word) % 65535;
+            b = (b + a) % 65535;
+        }
+        b << 16 | a
+    }
+
+    fn crc32c_scalar(data: &[u8]) -> u32 {
+        let table = crc32c_table();
+        !data.iter().fold(!0u32, |crc, &b| table[((crc ^ b as u32) & 0xFF) as usize] ^ (crc >> 8))
+    }
+
+    #[test]
+    fn known_values() {
+        assert_eq!(adler32(b""), 1);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+        assert_eq!(fletcher16(b"abcdef"), 0x2057);
+        assert_eq!(fletcher32(b"abcdef"), 0x5650_2D2A);
+        assert_eq!(crc32c(b""), 0);
+        assert_eq!(crc32c(b"123456789"), 0xE306_9283);
+        assert_eq!(crc32c(&[0; 32]), 0x8A91_36AA);
+    }
+
+    #[test]
+    fn checksums_match_scalar() {
+        let mut seed = 0xDA942042E4DD58B5;
+        for len in (0..300).chain(vec![4095, 4096, 4097, 10000]) {
+            // Mostly high bytes, to stress the sums
+            let data = (0..len).map(|_| rng(&mut seed) as u8 | 0xC0).collect::<Vec<_>>();
+            assert_eq!(adler32(&data), adler32_scalar(&data));
+            assert_eq!(fletcher16(&data), fletcher16_scalar(&data));
+            assert_eq!(fletcher32(&data), fletcher32_scalar(&data));
+            assert_eq!(crc32c(&data), crc32c_scalar(&data));
+        }
+    }
+
+    #[test]
+    fn checksums_streaming() {
+        let mut seed = 0x2545F4914F6CDD1D;
+        let data = (0..5000).map(|_| rng(&mut seed) as u8).collect::<Vec<_>>();
+        let (mut adler, mut f16, mut f32, mut crc) =
+            (Adler32::new(), Fletcher16::new(), Fletcher32::new(), Crc32c::new());
+        let mut pos = 0;
+        while pos < data.len() {
+            let end = (pos + rng(&mut seed) as usize % 100).min(data.len());
+            adler.update(&data[pos..end]);
+            f16.update(&data[pos..end]);
+            f32.update(&data[pos..end]);
+            crc.update(&data[pos..end]);
+            pos = end;
+        }
+        assert_eq!(adler.finish(), adler32(&data));
+        assert_eq!(f16.finish(), fletcher16(&data));
+        assert_eq!(f32.finish(), fletcher32(&data));
+        assert_eq!(crc.finish(), crc32c(&data));
+    }
+}
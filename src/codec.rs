@@ -0,0 +1,1774 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Base64 and hexadecimal encoding of byte slices, and compression of
+//! integer columns with bit-packing, delta coding and stream-vbyte.
+//!
+//! Each function writes into a caller-provided buffer and returns the number
+//! of bytes written. The vectorized paths work on groups of 16 bytes, using
+//! [`Lookup`] to move bytes within a group and to map symbols through small
+//! tables. Decoding falls back to a scalar loop at the first vector which
+//! contains a byte outside of the alphabet, so errors are reported at their
+//! exact position.
+//!
+//! The integer encodings are defined independently of the vector width, so
+//! data encoded with one set of target features decodes with any other.
+//!
+//! [`Lookup`]: ../intrin/lookup/trait.Lookup.html
+
+use crate::arch::current::vecs::*;
+use crate::core::fmt;
+use crate::vecs::*;
+use crate::intrin::cmp::*;
+use crate::intrin::eq::*;
+use crate::intrin::lookup::*;
+use crate::intrin::saturating_add::*;
+use crate::intrin::saturating_sub::*;
+use crate::intrin::shuffle::{gather, repeat_groups, scatter, MAX_WIDTH};
+use crate::intrin::transmute::*;
+use crate::intrin::wrapping::WrappingLanes;
+use crate::text::in_range;
+
+/// The set of symbols used to encode base64.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Alphabet {
+    /// The alphabet of RFC 4648 section 4, ending in `+` and `/`.
+    Standard,
+    /// The alphabet of RFC 4648 section 5, ending in `-` and `_`.
+    UrlSafe,
+}
+
+/// Whether base64 output ends in `=` padding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Padding {
+    /// Write padding when encoding, and require it when decoding.
+    Required,
+    /// Write no padding when encoding, and reject it when decoding.
+    Omitted,
+    /// Write padding when encoding, and accept input with or without it.
+    Optional,
+}
+
+/// The alphabet and padding policy of a base64 encoding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Base64Config {
+    pub alphabet: Alphabet,
+    pub padding: Padding,
+}
+
+impl Base64Config {
+    /// The standard alphabet, with padding.
+    pub const STANDARD: Base64Config =
+        Base64Config { alphabet: Alphabet::Standard, padding: Padding::Required };
+    /// The URL-safe alphabet, with padding.
+    pub const URL_SAFE: Base64Config =
+        Base64Config { alphabet: Alphabet::UrlSafe, padding: Padding::Required };
+    /// The URL-safe alphabet, without padding.
+    pub const URL_SAFE_NO_PAD: Base64Config =
+        Base64Config { alphabet: Alphabet::UrlSafe, padding: Padding::Omitted };
+}
+
+/// An error returned when decoding invalid input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The byte at the given index isn't part of the encoding, or is padding
+    /// where padding isn't allowed.
+    InvalidByte(usize, u8),
+    /// The length of the input can't be produced by the encoding.
+    InvalidLength,
+    /// The last symbol, at the given index, has bits set which don't belong
+    /// to any decoded byte.
+    InvalidLastSymbol(usize, u8),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DecodeError::InvalidByte(idx, b) =>
+                write!(f, "invalid byte {:#04x} at index {}", b, idx),
+            DecodeError::InvalidLength =>
+                write!(f, "invalid input length"),
+            DecodeError::InvalidLastSymbol(idx, b) =>
+                write!(f, "invalid last symbol {:#04x} at index {}", b, idx),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+const STANDARD_SYMBOLS: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const URL_SAFE_SYMBOLS: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+const HEX_SYMBOLS: &[u8; 16] = b"0123456789abcdef";
+
+impl Alphabet {
+    #[inline(always)]
+    fn symbols(self) -> &'static [u8; 64] {
+        match self {
+            Alphabet::Standard => STANDARD_SYMBOLS,
+            Alphabet::UrlSafe => URL_SAFE_SYMBOLS,
+        }
+    }
+
+    // The symbols for the values 62 and 63
+    #[inline(always)]
+    fn last_symbols(self) -> (u8, u8) {
+        let symbols = self.symbols();
+        (symbols[62], symbols[63])
+    }
+}
+
+/// Return the length of the base64 encoding of `len` bytes.
+pub fn base64_encoded_len(len: usize, config: Base64Config) -> usize {
+    match config.padding {
+        Padding::Omitted => len / 3 * 4 + [0, 2, 3][len % 3],
+        _ => (len + 2) / 3 * 4,
+    }
+}
+
+/// Return the largest number of bytes which the base64 encoding of length
+/// `len` may decode to.
+pub fn base64_decoded_max_len(len: usize) -> usize {
+    (len + 3) / 4 * 3
+}
+
+/// Encode `input` as base64 into `output`, returning the number of bytes
+/// written.
+///
+/// # Panics
+///
+/// This function will panic if `output` is shorter than
+/// `base64_encoded_len(input.len(), config)`.
+///
+/// ```
+/// extern crate faster;
+/// use faster::codec::*;
+///
+/// # fn main() {
+/// let mut out = [0u8; 8];
+/// let len = base64_encode(b"faste", &mut out, Base64Config::STANDARD);
+/// assert_eq!(&out[..len], b"ZmFzdGU=");
+/// let len = base64_encode(b"faste", &mut out, Base64Config::URL_SAFE_NO_PAD);
+/// assert_eq!(&out[..len], b"ZmFzdGU");
+/// # }
+/// ```
+pub fn base64_encode(input: &[u8], output: &mut [u8], config: Base64Config) -> usize {
+    let chunk = u8s::WIDTH / 16 * 12;
+    let (mut pos, mut out) = (0, 0);
+
+    if input.len() >= chunk {
+        // Put the three bytes of each group of four symbols in the order
+        // [1, 0, 2, 1], so each pair of symbols shares a 16-bit lane
+        let spread = repeat_groups(&[1, 0, 2, 1, 4, 3, 5, 4, 7, 6, 8, 7, 10, 9, 11, 10]);
+        let even = u16s::interleave(0xFFFF, 0);
+        let odd = u16s::interleave(0, 0xFFFF);
+
+        // Map each value to a symbol by adding and then subtracting an
+        // offset chosen by its range, so no lane overflows
+        let (sym62, sym63) = config.alphabet.last_symbols();
+        let (add62, sub62) = if sym62 >= 62 { (sym62 - 62, 0) } else { (0, 62 - sym62) };
+        let (add63, sub63) = if sym63 >= 63 { (sym63 - 63, 0) } else { (0, 63 - sym63) };
+        let add = [b'a' - 26, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, add62, add63, b'A', 0, 0];
+        let sub = [0, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, sub62, sub63, 0, 0, 0];
+
+        while pos + chunk <= input.len() {
+            let words = gather(input, pos, 12).shuffle_bytes(spread).be_u16s();
+            let hi = ((words >> u16s(10)) & u16s(0x003F)) | ((words << u16s(4)) & u16s(0x3F00));
+            let lo = ((words >> u16s(6)) & u16s(0x003F)) | ((words << u16s(8)) & u16s(0x3F00));
+            let values = ((hi & even) | (lo & odd)).be_u8s();
+
+            let range = values.saturating_sub(u8s(51)) | (in_range(values, 0, 25) & u8s(13));
+            let symbols = (values + range.lookup_nibbles(&add)) - range.lookup_nibbles(&sub);
+            symbols.store(output, out);
+
+            pos += chunk;
+            out += u8s::WIDTH;
+        }
+    }
+
+    out + encode_scalar(&input[pos..], &mut output[out..], config)
+}
+
+fn encode_scalar(input: &[u8], output: &mut [u8], config: Base64Config) -> usize {
+    let symbols = config.alphabet.symbols();
+    let mut out = 0;
+    for triple in input.chunks(3) {
+        let b0 = triple[0] as usize;
+        let b1 = triple.get(1).cloned().unwrap_or(0) as usize;
+        let b2 = triple.get(2).cloned().unwrap_or(0) as usize;
+        let quad = [symbols[b0 >> 2],
+                    symbols[(b0 & 0x03) << 4 | b1 >> 4],
+                    symbols[(b1 & 0x0F) << 2 | b2 >> 6],
+                    symbols[b2 & 0x3F]];
+        let len = if triple.len() == 3 || config.padding != Padding::Omitted {
+            4
+        } else {
+            triple.len() + 1
+        };
+        output[out..out + len].copy_from_slice(&quad[..len]);
+        for b in &mut output[out + triple.len() + 1..out + len] {
+            *b = b'=';
+        }
+        out += len;
+    }
+    out
+}
+
+/// Decode the base64 in `input` into `output`, returning the number of bytes
+/// written.
+///
+/// # Panics
+///
+/// This function will panic if `output` is too short for the decoded bytes,
+/// which is never the case if it is at least
+/// `base64_decoded_max_len(input.len())` bytes long.
+///
+/// ```
+/// extern crate faster;
+/// use faster::codec::*;
+///
+/// # fn main() {
+/// let mut out = [0u8; 6];
+/// assert_eq!(base64_decode(b"ZmFzdGU=", &mut out, Base64Config::STANDARD), Ok(5));
+/// assert_eq!(&out[..5], b"faste");
+/// assert_eq!(base64_decode(b"ZmF*dGU=", &mut out, Base64Config::STANDARD),
+///            Err(DecodeError::InvalidByte(3, b'*')));
+/// # }
+/// ```
+pub fn base64_decode(input: &[u8], output: &mut [u8], config: Base64Config) -> Result<usize, DecodeError> {
+    let chunk = u8s::WIDTH / 16 * 12;
+    let (mut pos, mut out) = (0, 0);
+
+    if input.len() >= u8s::WIDTH {
+        let (sym62, sym63) = config.alphabet.last_symbols();
+        // Move the three bytes in the low end of each 32-bit lane to the
+        // front of its group, most significant first
+        let compact = repeat_groups(&[2, 1, 0, 6, 5, 4, 10, 9, 8, 14, 13, 12, 0, 0, 0, 0]);
+
+        while pos + u8s::WIDTH <= input.len() {
+            let symbols = u8s::load(input, pos);
+            let upper = in_range(symbols, b'A', b'Z');
+            let lower = in_range(symbols, b'a', b'z');
+            let digit = in_range(symbols, b'0', b'9');
+            let is62 = symbols.eq_mask(u8s(sym62));
+            let is63 = symbols.eq_mask(u8s(sym63));
+            if (upper | lower | digit | is62 | is63) != u8s(0xFF) {
+                break;
+            }
+
+            let values = (upper & symbols.saturating_sub(u8s(b'A')))
+                | (lower & symbols.saturating_sub(u8s(b'a' - 26)))
+                | (digit & symbols.saturating_add(u8s(52 - b'0')))
+                | (is62 & u8s(62))
+                | (is63 & u8s(63));
+
+            // Join each pair of values into 12 bits, then each pair of
+            // those into 24 bits
+            let words = values.be_u16s();
+            let halves = (((words & u16s(0x3F)) << u16s(6)) | (words >> u16s(8))).be_u32s();
+            let bytes = ((halves & u32s(0xFFFF)) << u32s(12)) | (halves >> u32s(16));
+            scatter(bytes.be_u8s().shuffle_bytes(compact), output, out, 12);
+
+            pos += u8s::WIDTH;
+            out += chunk;
+        }
+    }
+
+    // Every vector we decoded held a whole number of groups of four symbols
+    decode_scalar(&input[pos..], &mut output[out..], config)
+        .map(|len| out + len)
+        .map_err(|e| match e {
+            DecodeError::InvalidByte(idx, b) => DecodeError::InvalidByte(pos + idx, b),
+            DecodeError::InvalidLastSymbol(idx, b) => DecodeError::InvalidLastSymbol(pos + idx, b),
+            e => e,
+        })
+}
+
+#[inline(always)]
+fn decode_symbol(symbols: &[u8; 64], b: u8) -> Option<u8> {
+    match b {
+        b'A'..=b'Z' => Some(b - b'A'),
+        b'a'..=b'z' => Some(b - b'a' + 26),
+        b'0'..=b'9' => Some(b - b'0' + 52),
+        _ if b == symbols[62] => Some(62),
+        _ if b == symbols[63] => Some(63),
+        _ => None,
+    }
+}
+
+fn decode_scalar(input: &[u8], output: &mut [u8], config: Base64Config) -> Result<usize, DecodeError> {
+    let symbols = config.alphabet.symbols();
+
+    // Strip up to two bytes of padding; any other `=` is an invalid byte
+    let mut end = input.len();
+    if config.padding != Padding::Omitted {
+        while end > 0 && input.len() - end < 2 && input[end - 1] == b'=' {
+            end -= 1;
+        }
+    }
+    let padded = end < input.len();
+    if (padded || config.padding == Padding::Required) && input.len() % 4 != 0 {
+        return Err(DecodeError::InvalidLength);
+    }
+    if end % 4 == 1 {
+        return Err(DecodeError::InvalidLength);
+    }
+
+    let mut out = 0;
+    let mut values = [0u8; 4];
+    for (q, quad) in input[..end].chunks(4).enumerate() {
+        for (i, &b) in quad.iter().enumerate() {
+            values[i] = decode_symbol(symbols, b)
+                .ok_or(DecodeError::InvalidByte(q * 4 + i, b))?;
+        }
+        let bytes = [values[0] << 2 | values[1] >> 4,
+                     values[1] << 4 | values[2] >> 2,
+                     values[2] << 6 | values[3]];
+        let len = quad.len() - 1;
+        if len < 3 {
+            // The bits past the last whole byte must be zero
+            let last = quad.len() - 1;
+            let spare = if len == 1 { values[1] & 0x0F } else { values[2] & 0x03 };
+            if spare != 0 {
+                return Err(DecodeError::InvalidLastSymbol(q * 4 + last, quad[last]));
+            }
+        }
+        output[out..out + len].copy_from_slice(&bytes[..len]);
+        out += len;
+    }
+    Ok(out)
+}
+
+/// Encode `input` as lowercase hexadecimal into `output`, returning the
+/// number of bytes written.
+///
+/// # Panics
+///
+/// This function will panic if `output` is shorter than twice the length of
+/// `input`.
+///
+/// ```
+/// extern crate faster;
+/// use faster::codec::*;
+///
+/// # fn main() {
+/// let mut out = [0u8; 6];
+/// assert_eq!(hex_encode(&[0x00, 0xBE, 0xEF], &mut out), 6);
+/// assert_eq!(&out, b"00beef");
+/// # }
+/// ```
+pub fn hex_encode(input: &[u8], output: &mut [u8]) -> usize {
+    let chunk = u8s::WIDTH / 2;
+    let mut pos = 0;
+
+    if input.len() >= chunk {
+        let spread = repeat_groups(&[0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7]);
+        let even = u8s::interleave(0xFF, 0);
+        let odd = u8s::interleave(0, 0xFF);
+
+        while pos + chunk <= input.len() {
+            let bytes = gather(input, pos, 8).shuffle_bytes(spread);
+            let nibbles = ((bytes >> u8s(4)) & even) | (bytes & odd);
+            nibbles.lookup_nibbles(HEX_SYMBOLS).store(output, pos * 2);
+            pos += chunk;
+        }
+    }
+
+    for (i, &b) in input[pos..].iter().enumerate() {
+        output[(pos + i) * 2] = HEX_SYMBOLS[(b >> 4) as usize];
+        output[(pos + i) * 2 + 1] = HEX_SYMBOLS[(b & 0x0F) as usize];
+    }
+    input.len() * 2
+}
+
+/// Decode the hexadecimal in `input`, in either case, into `output`,
+/// returning the number of bytes written.
+///
+/// # Panics
+///
+/// This function will panic if `output` is shorter than half the length of
+/// `input`.
+///
+/// ```
+/// extern crate faster;
+/// use faster::codec::*;
+///
+/// # fn main() {
+/// let mut out = [0u8; 3];
+/// assert_eq!(hex_decode(b"00BEef", &mut out), Ok(3));
+/// assert_eq!(out, [0x00, 0xBE, 0xEF]);
+/// assert_eq!(hex_decode(b"00bxef", &mut out), Err(DecodeError::InvalidByte(3, b'x')));
+/// # }
+/// ```
+pub fn hex_decode(input: &[u8], output: &mut [u8]) -> Result<usize, DecodeError> {
+    if input.len() % 2 != 0 {
+        return Err(DecodeError::InvalidLength);
+    }
+    let mut pos = 0;
+
+    if input.len() >= u8s::WIDTH {
+        let compact = repeat_groups(&[0, 2, 4, 6, 8, 10, 12, 14, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+        while pos + u8s::WIDTH <= input.len() {
+            let symbols = u8s::load(input, pos);
+            let digit = in_range(symbols, b'0', b'9');
+            let lower = in_range(symbols, b'a', b'f');
+            let upper = in_range(symbols, b'A', b'F');
+            if (digit | lower | upper) != u8s(0xFF) {
+                break;
+            }
+
+            let values = (digit & symbols.saturating_sub(u8s(b'0')))
+                | (lower & symbols.saturating_sub(u8s(b'a' - 10)))
+                | (upper & symbols.saturating_sub(u8s(b'A' - 10)));
+            let words = values.be_u16s();
+            let bytes = ((words & u16s(0x0F)) << u16s(4)) | (words >> u16s(8));
+            scatter(bytes.be_u8s().shuffle_bytes(compact), output, pos / 2, 8);
+            pos += u8s::WIDTH;
+        }
+    }
+
+    for i in (pos..input.len()).step_by(2) {
+        let hi = hex_value(input[i]).ok_or(DecodeError::InvalidByte(i, input[i]))?;
+        let lo = hex_value(input[i + 1]).ok_or(DecodeError::InvalidByte(i + 1, input[i + 1]))?;
+        output[i / 2] = hi << 4 | lo;
+    }
+    Ok(input.len() / 2)
+}
+
+#[inline(always)]
+fn hex_value(b: u8) -> Option<u8> {
+    match b {
+        b'0'..=b'9' => Some(b - b'0'),
+        b'a'..=b'f' => Some(b - b'a' + 10),
+        b'A'..=b'F' => Some(b - b'A' + 10),
+        _ => None,
+    }
+}
+
+// Return a mask of the low `bits` bits of a u32.
+#[inline(always)]
+fn low_mask(bits: u32) -> u32 {
+    !0 >> (32 - bits)
+}
+
+// The shuffles and shifts which move groups of four packed values between
+// bytes and u32 lanes, for vectors whose first value starts `phase` bits into
+// its first byte. Each group of four values spans at most 13 bytes when
+// values have at most 24 bits, so a group fits in 16 bytes.
+struct Packing {
+    unpack: u8s,
+    shifts: u32s,
+    pack: [u8s; 4],
+    keep: [u8s; 4],
+}
+
+impl Packing {
+    #[inline(always)]
+    fn new(bits: u32, phase: u32) -> Self {
+        let mut unpack = [0u8; MAX_WIDTH];
+        let mut shifts = [0u32; MAX_WIDTH / 4];
+        let mut pack = [[0u8; MAX_WIDTH]; 4];
+        let mut keep = [[0u8; MAX_WIDTH]; 4];
+        for g in 0..u8s::WIDTH / 16 {
+            let start = (phase + 4 * g as u32 * bits) % 8;
+            for k in 0..4 {
+                let offset = start + k as u32 * bits;
+                let byte = (offset / 8) as usize;
+                shifts[g * 4 + k] = offset % 8;
+                for t in 0..4 {
+                    unpack[g * 16 + 4 * k + t] = (byte + t) as u8;
+                    pack[k][g * 16 + byte + t] = (4 * k + t) as u8;
+                    keep[k][g * 16 + byte + t] = 0xFF;
+                }
+            }
+        }
+        let load = |lanes: &[u8; MAX_WIDTH]| u8s::load(lanes, 0);
+        Packing {
+            unpack: load(&unpack),
+            shifts: u32s::load(&shifts, 0),
+            pack: [load(&pack[0]), load(&pack[1]), load(&pack[2]), load(&pack[3])],
+            keep: [load(&keep[0]), load(&keep[1]), load(&keep[2]), load(&keep[3])],
+        }
+    }
+}
+
+/// Return the number of bytes [`bitpack`] writes for `len` values of `bits`
+/// bits each.
+///
+/// [`bitpack`]: fn.bitpack.html
+pub fn bitpacked_len(len: usize, bits: u32) -> usize {
+    (len * bits as usize + 7) / 8
+}
+
+/// Return the fewest bits, and at least one, which can hold every value of
+/// `input`.
+///
+/// ```
+/// extern crate faster;
+/// use faster::codec::bits_needed;
+///
+/// # fn main() {
+/// assert_eq!(bits_needed(&[3, 9, 4]), 4);
+/// assert_eq!(bits_needed(&[0, 0]), 1);
+/// assert_eq!(bits_needed(&[1 << 31]), 32);
+/// # }
+/// ```
+pub fn bits_needed(input: &[u32]) -> u32 {
+    let width = u32s::WIDTH;
+    let mut acc = u32s(0);
+    let mut i = 0;
+    while i + width <= input.len() {
+        acc = acc | u32s::load(input, i);
+        i += width;
+    }
+    let any = input[i..].iter().fold(acc.scalar_reduce(0, |a, x| a | x), |a, &x| a | x);
+    (32 - any.leading_zeros()).max(1)
+}
+
+/// Pack the low `bits` bits of each value of `input` into `output`, and
+/// return the number of bytes written, which is [`bitpacked_len`]. Values are
+/// written one after another as a little-endian stream of bits, so value i
+/// starts at bit `i * bits`, counting from the least significant bit of the
+/// first byte. The high bits of the last byte are zero.
+///
+/// # Panics
+///
+/// This function will panic if `bits` isn't between 1 and 32, or if the
+/// packed values don't fit in `output`.
+///
+/// ```
+/// extern crate faster;
+/// use faster::codec::{bitpack, bitunpack};
+///
+/// # fn main() {
+/// let mut packed = [0u8; 3];
+/// assert_eq!(bitpack(&[1, 2, 3, 4, 5], 4, &mut packed), 3);
+/// assert_eq!(packed, [0x21, 0x43, 0x05]);
+///
+/// let mut values = [0u32; 5];
+/// assert_eq!(bitunpack(&packed, 4, &mut values), 3);
+/// assert_eq!(values, [1, 2, 3, 4, 5]);
+/// # }
+/// ```
+///
+/// [`bitpacked_len`]: fn.bitpacked_len.html
+pub fn bitpack(input: &[u32], bits: u32, output: &mut [u8]) -> usize {
+    assert!((1..=32).contains(&bits), "values are packed in 1 to 32 bits");
+    let len = bitpacked_len(input.len(), bits);
+    assert!(output.len() >= len, "the packed values don't fit");
+    let width = u32s::WIDTH;
+    let mask = u32s(low_mask(bits));
+    let mut i = 0;
+
+    if bits <= 24 && input.len() >= width {
+        let packings = [Packing::new(bits, 0), Packing::new(bits, 4)];
+        let mut lanes = [0u8; MAX_WIDTH];
+        while i + width <= input.len() {
+            let bit = i * bits as usize;
+            let p = &packings[bit % 8 / 4];
+            let v = ((u32s::load(input, i) & mask) << p.shifts).be_u8s();
+            let mut packed = u8s(0);
+            for (&shuffle, &keep) in p.pack.iter().zip(&p.keep) {
+                packed = packed | (v.shuffle_bytes(shuffle) & keep);
+            }
+            packed.store(&mut lanes, 0);
+
+            // Groups which start mid-byte share it with the group before
+            for g in 0..width / 4 {
+                let start = bit + 4 * g * bits as usize;
+                let (byte, phase) = (start / 8, start % 8);
+                let n = (phase + 4 * bits as usize + 7) / 8;
+                let group = &lanes[g * 16..g * 16 + n];
+                if phase == 0 {
+                    output[byte..byte + n].copy_from_slice(group);
+                } else {
+                    output[byte] |= group[0];
+                    output[byte + 1..byte + n].copy_from_slice(&group[1..]);
+                }
+            }
+            i += width;
+        }
+    }
+
+    pack_scalar(input, bits, output, i);
+    len
+}
+
+// Pack the values of `input` from index `start` on, after the bits of the
+// values before it which are already in `output`.
+fn pack_scalar(input: &[u32], bits: u32, output: &mut [u8], start: usize) {
+    let mask = low_mask(bits);
+    let bit = start * bits as usize;
+    let (mut byte, mut filled) = (bit / 8, (bit % 8) as u32);
+    let mut acc = if filled > 0 { u64::from(output[byte]) & ((1 << filled) - 1) } else { 0 };
+    for &x in &input[start..] {
+        acc |= u64::from(x & mask) << filled;
+        filled += bits;
+        while filled >= 8 {
+            output[byte] = acc as u8;
+            acc >>= 8;
+            filled -= 8;
+            byte += 1;
+        }
+    }
+    if filled > 0 {
+        output[byte] = acc as u8;
+    }
+}
+
+/// Unpack values of `bits` bits each from `input` until `output` is full,
+/// and return the number of bytes read, which is [`bitpacked_len`]. This is
+/// the inverse of [`bitpack`].
+///
+/// # Panics
+///
+/// This function will panic if `bits` isn't between 1 and 32, or if `input`
+/// is too short to hold `output.len()` values.
+///
+/// [`bitpack`]: fn.bitpack.html
+/// [`bitpacked_len`]: fn.bitpacked_len.html
+pub fn bitunpack(input: &[u8], bits: u32, output: &mut [u32]) -> usize {
+    assert!((1..=32).contains(&bits), "values are packed in 1 to 32 bits");
+    let len = bitpacked_len(output.len(), bits);
+    assert!(input.len() >= len, "the packed input is too short");
+    let width = u32s::WIDTH;
+    let mask = u32s(low_mask(bits));
+    let mut i = 0;
+
+    if bits <= 24 && output.len() >= width {
+        let packings = [Packing::new(bits, 0), Packing::new(bits, 4)];
+        let mut lanes = [0u8; MAX_WIDTH];
+        // Every group reads 16 bytes, which may run past the packed values
+        while i + width <= output.len()
+            && (i + width - 4) * bits as usize / 8 + 16 <= input.len() {
+            let bit = i * bits as usize;
+            let p = &packings[bit % 8 / 4];
+            for g in 0..width / 4 {
+                let byte = (bit + 4 * g * bits as usize) / 8;
+                lanes[g * 16..g * 16 + 16].copy_from_slice(&input[byte..byte + 16]);
+            }
+            let v = u8s::load(&lanes, 0).shuffle_bytes(p.unpack).be_u32s();
+            ((v >> p.shifts) & mask).store(output, i);
+            i += width;
+        }
+    }
+
+    unpack_scalar(input, bits, output, i);
+    len
+}
+
+// Unpack the values of `output` from index `start` on.
+fn unpack_scalar(input: &[u8], bits: u32, output: &mut [u32], start: usize) {
+    let mask = low_mask(bits);
+    for (i, out) in output.iter_mut().enumerate().skip(start) {
+        let bit = i * bits as usize;
+        let word = input[bit / 8..].iter().take(5).enumerate()
+            .fold(0u64, |w, (t, &b)| w | u64::from(b) << (8 * t));
+        *out = (word >> (bit % 8)) as u32 & mask;
+    }
+}
+
+// Return `v` moved up by `k` lanes, with zeroes shifted in. This moves one
+// lane at a time rather than using a shuffle instruction.
+#[inline(always)]
+fn shift_lanes(v: u32s, k: usize) -> u32s {
+    let mut ret = u32s(0);
+    for i in k..u32s::WIDTH {
+        ret = ret.replace(i, v.extract(i - k));
+    }
+    ret
+}
+
+// Return the inclusive prefix sum of the lanes of `v`, wrapping on overflow.
+#[inline(always)]
+fn prefix_sum(v: u32s) -> u32s {
+    let mut sum = v;
+    let mut k = 1;
+    while k < u32s::WIDTH {
+        sum = sum.wrapping_add_lanes(shift_lanes(sum, k));
+        k *= 2;
+    }
+    sum
+}
+
+// Map signed integers to unsigned ones, interleaving the negative numbers
+// with the positive ones so both have small encodings.
+#[inline(always)]
+fn zigzag(x: i32) -> u32 {
+    ((x << 1) ^ (x >> 31)) as u32
+}
+
+#[inline(always)]
+fn unzigzag(x: u32) -> i32 {
+    (x >> 1) as i32 ^ -((x & 1) as i32)
+}
+
+/// Write the difference between each value of `input` and the one before it
+/// to `output`, wrapping on overflow. The first value is written as is.
+///
+/// # Panics
+///
+/// This function will panic if `output` is shorter than `input`.
+///
+/// ```
+/// extern crate faster;
+/// use faster::codec::{delta_encode, delta_decode};
+///
+/// # fn main() {
+/// let mut deltas = [0u32; 5];
+/// delta_encode(&[10, 11, 15, 15, 20], &mut deltas);
+/// assert_eq!(deltas, [10, 1, 4, 0, 5]);
+///
+/// let mut values = [0u32; 5];
+/// delta_decode(&deltas, &mut values);
+/// assert_eq!(values, [10, 11, 15, 15, 20]);
+/// # }
+/// ```
+pub fn delta_encode(input: &[u32], output: &mut [u32]) {
+    assert!(output.len() >= input.len(), "the deltas don't fit");
+    if let Some(&first) = input.first() {
+        output[0] = first;
+    }
+    let width = u32s::WIDTH;
+    let mut i = 1;
+    while i + width <= input.len() {
+        u32s::load(input, i).wrapping_sub_lanes(u32s::load(input, i - 1)).store(output, i);
+        i += width;
+    }
+    for i in i..input.len() {
+        output[i] = input[i].wrapping_sub(input[i - 1]);
+    }
+}
+
+/// Write the running sum of `input` to `output`, wrapping on overflow. This
+/// is the inverse of [`delta_encode`].
+///
+/// # Panics
+///
+/// This function will panic if `output` is shorter than `input`.
+///
+/// [`delta_encode`]: fn.delta_encode.html
+pub fn delta_decode(input: &[u32], output: &mut [u32]) {
+    assert!(output.len() >= input.len(), "the values don't fit");
+    let width = u32s::WIDTH;
+    let mut last = 0u32;
+    let mut i = 0;
+    while i + width <= input.len() {
+        let v = prefix_sum(u32s::load(input, i)).wrapping_add_lanes(u32s(last));
+        v.store(output, i);
+        last = v.extract(width - 1);
+        i += width;
+    }
+    for (out, &d) in output[i..].iter_mut().zip(&input[i..]) {
+        last = last.wrapping_add(d);
+        *out = last;
+    }
+}
+
+/// Write the difference between each value of `input` and the one before
+/// it to `output` as a zigzag-encoded unsigned integer, wrapping on
+/// overflow. Differences of small magnitude have small encodings whatever
+/// their sign, so the output suits [`bitpack`] and [`streamvbyte_encode`].
+///
+/// # Panics
+///
+/// This function will panic if `output` is shorter than `input`.
+///
+/// ```
+/// extern crate faster;
+/// use faster::codec::{zigzag_delta_encode, zigzag_delta_decode};
+///
+/// # fn main() {
+/// let mut deltas = [0u32; 4];
+/// zigzag_delta_encode(&[-1, 1, 0, 2], &mut deltas);
+/// assert_eq!(deltas, [1, 4, 1, 4]);
+///
+/// let mut values = [0i32; 4];
+/// zigzag_delta_decode(&deltas, &mut values);
+/// assert_eq!(values, [-1, 1, 0, 2]);
+/// # }
+/// ```
+///
+/// [`bitpack`]: fn.bitpack.html
+/// [`streamvbyte_encode`]: fn.streamvbyte_encode.html
+pub fn zigzag_delta_encode(input: &[i32], output: &mut [u32]) {
+    assert!(output.len() >= input.len(), "the deltas don't fit");
+    if let Some(&first) = input.first() {
+        output[0] = zigzag(first);
+    }
+    let width = i32s::WIDTH;
+    let mut i = 1;
+    while i + width <= input.len() {
+        let d = i32s::load(input, i).wrapping_sub_lanes(i32s::load(input, i - 1));
+        ((d << 1) ^ (d >> 31)).be_u32s().store(output, i);
+        i += width;
+    }
+    for i in i..input.len() {
+        output[i] = zigzag(input[i].wrapping_sub(input[i - 1]));
+    }
+}
+
+/// Write the running sum of the zigzag-encoded differences of `input` to
+/// `output`, wrapping on overflow. This is the inverse of
+/// [`zigzag_delta_encode`].
+///
+/// # Panics
+///
+/// This function will panic if `output` is shorter than `input`.
+///
+/// [`zigzag_delta_encode`]: fn.zigzag_delta_encode.html
+pub fn zigzag_delta_decode(input: &[u32], output: &mut [i32]) {
+    assert!(output.len() >= input.len(), "the values don't fit");
+    let width = u32s::WIDTH;
+    let mut last = 0u32;
+    let mut i = 0;
+    while i + width <= input.len() {
+        let z = u32s::load(input, i);
+        let deltas = (z >> 1) ^ u32s(0).wrapping_sub_lanes(z & u32s(1));
+        let v = prefix_sum(deltas).wrapping_add_lanes(u32s(last));
+        v.be_i32s().store(output, i);
+        last = v.extract(width - 1);
+        i += width;
+    }
+    for (out, &z) in output[i..].iter_mut().zip(&input[i..]) {
+        last = last.wrapping_add(unzigzag(z) as u32);
+        *out = last as i32;
+    }
+}
+
+// The shuffle which moves the bytes of four values, whose lengths are given
+// by a control byte, into four u32 lanes. Bytes marked 0x80 are zeroed.
+static STREAMVBYTE_DECODE: [[u8; 16]; 256] = [
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x80, 0x80, 0x80, 0x02, 0x80, 0x80, 0x80, 0x03, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x80, 0x80, 0x80, 0x03, 0x80, 0x80, 0x80, 0x04, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x80, 0x80, 0x80, 0x04, 0x80, 0x80, 0x80, 0x05, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x80, 0x80, 0x80, 0x05, 0x80, 0x80, 0x80, 0x06, 0x80, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x80, 0x80, 0x03, 0x80, 0x80, 0x80, 0x04, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x80, 0x80, 0x04, 0x80, 0x80, 0x80, 0x05, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x80, 0x80, 0x05, 0x80, 0x80, 0x80, 0x06, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x80, 0x80, 0x06, 0x80, 0x80, 0x80, 0x07, 0x80, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x80, 0x04, 0x80, 0x80, 0x80, 0x05, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x80, 0x05, 0x80, 0x80, 0x80, 0x06, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x80, 0x06, 0x80, 0x80, 0x80, 0x07, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x80, 0x07, 0x80, 0x80, 0x80, 0x08, 0x80, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x04, 0x05, 0x80, 0x80, 0x80, 0x06, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x05, 0x06, 0x80, 0x80, 0x80, 0x07, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x06, 0x07, 0x80, 0x80, 0x80, 0x08, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x80, 0x80, 0x80, 0x09, 0x80, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x80, 0x80, 0x80, 0x02, 0x03, 0x80, 0x80, 0x04, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x80, 0x80, 0x80, 0x03, 0x04, 0x80, 0x80, 0x05, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x80, 0x80, 0x80, 0x04, 0x05, 0x80, 0x80, 0x06, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x80, 0x80, 0x80, 0x05, 0x06, 0x80, 0x80, 0x07, 0x80, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x80, 0x80, 0x03, 0x04, 0x80, 0x80, 0x05, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x80, 0x80, 0x04, 0x05, 0x80, 0x80, 0x06, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x80, 0x80, 0x05, 0x06, 0x80, 0x80, 0x07, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x80, 0x80, 0x06, 0x07, 0x80, 0x80, 0x08, 0x80, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x80, 0x04, 0x05, 0x80, 0x80, 0x06, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x80, 0x05, 0x06, 0x80, 0x80, 0x07, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x80, 0x06, 0x07, 0x80, 0x80, 0x08, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x80, 0x07, 0x08, 0x80, 0x80, 0x09, 0x80, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x80, 0x80, 0x07, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x80, 0x80, 0x08, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x80, 0x80, 0x09, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x80, 0x80, 0x0a, 0x80, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x80, 0x80, 0x80, 0x02, 0x03, 0x04, 0x80, 0x05, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x80, 0x80, 0x80, 0x03, 0x04, 0x05, 0x80, 0x06, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x80, 0x80, 0x80, 0x04, 0x05, 0x06, 0x80, 0x07, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x80, 0x80, 0x80, 0x05, 0x06, 0x07, 0x80, 0x08, 0x80, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x80, 0x80, 0x03, 0x04, 0x05, 0x80, 0x06, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x80, 0x80, 0x04, 0x05, 0x06, 0x80, 0x07, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x80, 0x80, 0x05, 0x06, 0x07, 0x80, 0x08, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x80, 0x80, 0x06, 0x07, 0x08, 0x80, 0x09, 0x80, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x80, 0x04, 0x05, 0x06, 0x80, 0x07, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x80, 0x05, 0x06, 0x07, 0x80, 0x08, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x80, 0x06, 0x07, 0x08, 0x80, 0x09, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x80, 0x07, 0x08, 0x09, 0x80, 0x0a, 0x80, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x80, 0x08, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x80, 0x09, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x80, 0x0a, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x80, 0x0b, 0x80, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x80, 0x80, 0x80, 0x02, 0x03, 0x04, 0x05, 0x06, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x80, 0x80, 0x80, 0x03, 0x04, 0x05, 0x06, 0x07, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x80, 0x80, 0x80, 0x04, 0x05, 0x06, 0x07, 0x08, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x80, 0x80, 0x80, 0x05, 0x06, 0x07, 0x08, 0x09, 0x80, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x80, 0x80, 0x03, 0x04, 0x05, 0x06, 0x07, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x80, 0x80, 0x04, 0x05, 0x06, 0x07, 0x08, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x80, 0x80, 0x05, 0x06, 0x07, 0x08, 0x09, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x80, 0x80, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x80, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x80, 0x04, 0x05, 0x06, 0x07, 0x08, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x80, 0x05, 0x06, 0x07, 0x08, 0x09, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x80, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x80, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x80, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x80, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x80, 0x80, 0x80, 0x02, 0x80, 0x80, 0x80, 0x03, 0x04, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x80, 0x80, 0x80, 0x03, 0x80, 0x80, 0x80, 0x04, 0x05, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x80, 0x80, 0x80, 0x04, 0x80, 0x80, 0x80, 0x05, 0x06, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x80, 0x80, 0x80, 0x05, 0x80, 0x80, 0x80, 0x06, 0x07, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x80, 0x80, 0x03, 0x80, 0x80, 0x80, 0x04, 0x05, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x80, 0x80, 0x04, 0x80, 0x80, 0x80, 0x05, 0x06, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x80, 0x80, 0x05, 0x80, 0x80, 0x80, 0x06, 0x07, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x80, 0x80, 0x06, 0x80, 0x80, 0x80, 0x07, 0x08, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x80, 0x04, 0x80, 0x80, 0x80, 0x05, 0x06, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x80, 0x05, 0x80, 0x80, 0x80, 0x06, 0x07, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x80, 0x06, 0x80, 0x80, 0x80, 0x07, 0x08, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x80, 0x07, 0x80, 0x80, 0x80, 0x08, 0x09, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x04, 0x05, 0x80, 0x80, 0x80, 0x06, 0x07, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x05, 0x06, 0x80, 0x80, 0x80, 0x07, 0x08, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x06, 0x07, 0x80, 0x80, 0x80, 0x08, 0x09, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x80, 0x80, 0x80, 0x09, 0x0a, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x80, 0x80, 0x80, 0x02, 0x03, 0x80, 0x80, 0x04, 0x05, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x80, 0x80, 0x80, 0x03, 0x04, 0x80, 0x80, 0x05, 0x06, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x80, 0x80, 0x80, 0x04, 0x05, 0x80, 0x80, 0x06, 0x07, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x80, 0x80, 0x80, 0x05, 0x06, 0x80, 0x80, 0x07, 0x08, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x80, 0x80, 0x03, 0x04, 0x80, 0x80, 0x05, 0x06, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x80, 0x80, 0x04, 0x05, 0x80, 0x80, 0x06, 0x07, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x80, 0x80, 0x05, 0x06, 0x80, 0x80, 0x07, 0x08, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x80, 0x80, 0x06, 0x07, 0x80, 0x80, 0x08, 0x09, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x80, 0x04, 0x05, 0x80, 0x80, 0x06, 0x07, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x80, 0x05, 0x06, 0x80, 0x80, 0x07, 0x08, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x80, 0x06, 0x07, 0x80, 0x80, 0x08, 0x09, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x80, 0x07, 0x08, 0x80, 0x80, 0x09, 0x0a, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x80, 0x80, 0x07, 0x08, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x80, 0x80, 0x08, 0x09, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x80, 0x80, 0x09, 0x0a, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x80, 0x80, 0x0a, 0x0b, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x80, 0x80, 0x80, 0x02, 0x03, 0x04, 0x80, 0x05, 0x06, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x80, 0x80, 0x80, 0x03, 0x04, 0x05, 0x80, 0x06, 0x07, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x80, 0x80, 0x80, 0x04, 0x05, 0x06, 0x80, 0x07, 0x08, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x80, 0x80, 0x80, 0x05, 0x06, 0x07, 0x80, 0x08, 0x09, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x80, 0x80, 0x03, 0x04, 0x05, 0x80, 0x06, 0x07, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x80, 0x80, 0x04, 0x05, 0x06, 0x80, 0x07, 0x08, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x80, 0x80, 0x05, 0x06, 0x07, 0x80, 0x08, 0x09, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x80, 0x80, 0x06, 0x07, 0x08, 0x80, 0x09, 0x0a, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x80, 0x04, 0x05, 0x06, 0x80, 0x07, 0x08, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x80, 0x05, 0x06, 0x07, 0x80, 0x08, 0x09, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x80, 0x06, 0x07, 0x08, 0x80, 0x09, 0x0a, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x80, 0x07, 0x08, 0x09, 0x80, 0x0a, 0x0b, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x80, 0x08, 0x09, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x80, 0x09, 0x0a, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x80, 0x0a, 0x0b, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x80, 0x0b, 0x0c, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x80, 0x80, 0x80, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x80, 0x80, 0x80, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x80, 0x80, 0x80, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x80, 0x80, 0x80, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x80, 0x80, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x80, 0x80, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x80, 0x80, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x80, 0x80, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x80, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x80, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x80, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x80, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x80, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x80, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x80, 0x80, 0x80, 0x02, 0x80, 0x80, 0x80, 0x03, 0x04, 0x05, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x80, 0x80, 0x80, 0x03, 0x80, 0x80, 0x80, 0x04, 0x05, 0x06, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x80, 0x80, 0x80, 0x04, 0x80, 0x80, 0x80, 0x05, 0x06, 0x07, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x80, 0x80, 0x80, 0x05, 0x80, 0x80, 0x80, 0x06, 0x07, 0x08, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x80, 0x80, 0x03, 0x80, 0x80, 0x80, 0x04, 0x05, 0x06, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x80, 0x80, 0x04, 0x80, 0x80, 0x80, 0x05, 0x06, 0x07, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x80, 0x80, 0x05, 0x80, 0x80, 0x80, 0x06, 0x07, 0x08, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x80, 0x80, 0x06, 0x80, 0x80, 0x80, 0x07, 0x08, 0x09, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x80, 0x04, 0x80, 0x80, 0x80, 0x05, 0x06, 0x07, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x80, 0x05, 0x80, 0x80, 0x80, 0x06, 0x07, 0x08, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x80, 0x06, 0x80, 0x80, 0x80, 0x07, 0x08, 0x09, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x80, 0x07, 0x80, 0x80, 0x80, 0x08, 0x09, 0x0a, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x04, 0x05, 0x80, 0x80, 0x80, 0x06, 0x07, 0x08, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x05, 0x06, 0x80, 0x80, 0x80, 0x07, 0x08, 0x09, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x06, 0x07, 0x80, 0x80, 0x80, 0x08, 0x09, 0x0a, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x80, 0x80, 0x80, 0x09, 0x0a, 0x0b, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x80, 0x80, 0x80, 0x02, 0x03, 0x80, 0x80, 0x04, 0x05, 0x06, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x80, 0x80, 0x80, 0x03, 0x04, 0x80, 0x80, 0x05, 0x06, 0x07, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x80, 0x80, 0x80, 0x04, 0x05, 0x80, 0x80, 0x06, 0x07, 0x08, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x80, 0x80, 0x80, 0x05, 0x06, 0x80, 0x80, 0x07, 0x08, 0x09, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x80, 0x80, 0x03, 0x04, 0x80, 0x80, 0x05, 0x06, 0x07, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x80, 0x80, 0x04, 0x05, 0x80, 0x80, 0x06, 0x07, 0x08, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x80, 0x80, 0x05, 0x06, 0x80, 0x80, 0x07, 0x08, 0x09, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x80, 0x80, 0x06, 0x07, 0x80, 0x80, 0x08, 0x09, 0x0a, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x80, 0x04, 0x05, 0x80, 0x80, 0x06, 0x07, 0x08, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x80, 0x05, 0x06, 0x80, 0x80, 0x07, 0x08, 0x09, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x80, 0x06, 0x07, 0x80, 0x80, 0x08, 0x09, 0x0a, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x80, 0x07, 0x08, 0x80, 0x80, 0x09, 0x0a, 0x0b, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x80, 0x80, 0x07, 0x08, 0x09, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x80, 0x80, 0x08, 0x09, 0x0a, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x80, 0x80, 0x09, 0x0a, 0x0b, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x80, 0x80, 0x0a, 0x0b, 0x0c, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x80, 0x80, 0x80, 0x02, 0x03, 0x04, 0x80, 0x05, 0x06, 0x07, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x80, 0x80, 0x80, 0x03, 0x04, 0x05, 0x80, 0x06, 0x07, 0x08, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x80, 0x80, 0x80, 0x04, 0x05, 0x06, 0x80, 0x07, 0x08, 0x09, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x80, 0x80, 0x80, 0x05, 0x06, 0x07, 0x80, 0x08, 0x09, 0x0a, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x80, 0x80, 0x03, 0x04, 0x05, 0x80, 0x06, 0x07, 0x08, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x80, 0x80, 0x04, 0x05, 0x06, 0x80, 0x07, 0x08, 0x09, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x80, 0x80, 0x05, 0x06, 0x07, 0x80, 0x08, 0x09, 0x0a, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x80, 0x80, 0x06, 0x07, 0x08, 0x80, 0x09, 0x0a, 0x0b, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x80, 0x04, 0x05, 0x06, 0x80, 0x07, 0x08, 0x09, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x80, 0x05, 0x06, 0x07, 0x80, 0x08, 0x09, 0x0a, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x80, 0x06, 0x07, 0x08, 0x80, 0x09, 0x0a, 0x0b, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x80, 0x07, 0x08, 0x09, 0x80, 0x0a, 0x0b, 0x0c, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x80, 0x08, 0x09, 0x0a, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x80, 0x09, 0x0a, 0x0b, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x80, 0x0a, 0x0b, 0x0c, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x80, 0x0b, 0x0c, 0x0d, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x80, 0x80, 0x80, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x80, 0x80, 0x80, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x80, 0x80, 0x80, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x80, 0x80, 0x80, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x80, 0x80, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x80, 0x80, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x80, 0x80, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x80, 0x80, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x80, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x80, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x80, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x80, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x80],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x80],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x80],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x80, 0x80, 0x80, 0x02, 0x80, 0x80, 0x80, 0x03, 0x04, 0x05, 0x06],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x80, 0x80, 0x80, 0x03, 0x80, 0x80, 0x80, 0x04, 0x05, 0x06, 0x07],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x80, 0x80, 0x80, 0x04, 0x80, 0x80, 0x80, 0x05, 0x06, 0x07, 0x08],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x80, 0x80, 0x80, 0x05, 0x80, 0x80, 0x80, 0x06, 0x07, 0x08, 0x09],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x80, 0x80, 0x03, 0x80, 0x80, 0x80, 0x04, 0x05, 0x06, 0x07],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x80, 0x80, 0x04, 0x80, 0x80, 0x80, 0x05, 0x06, 0x07, 0x08],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x80, 0x80, 0x05, 0x80, 0x80, 0x80, 0x06, 0x07, 0x08, 0x09],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x80, 0x80, 0x06, 0x80, 0x80, 0x80, 0x07, 0x08, 0x09, 0x0a],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x80, 0x04, 0x80, 0x80, 0x80, 0x05, 0x06, 0x07, 0x08],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x80, 0x05, 0x80, 0x80, 0x80, 0x06, 0x07, 0x08, 0x09],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x80, 0x06, 0x80, 0x80, 0x80, 0x07, 0x08, 0x09, 0x0a],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x80, 0x07, 0x80, 0x80, 0x80, 0x08, 0x09, 0x0a, 0x0b],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x04, 0x05, 0x80, 0x80, 0x80, 0x06, 0x07, 0x08, 0x09],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x05, 0x06, 0x80, 0x80, 0x80, 0x07, 0x08, 0x09, 0x0a],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x06, 0x07, 0x80, 0x80, 0x80, 0x08, 0x09, 0x0a, 0x0b],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x80, 0x80, 0x80, 0x09, 0x0a, 0x0b, 0x0c],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x80, 0x80, 0x80, 0x02, 0x03, 0x80, 0x80, 0x04, 0x05, 0x06, 0x07],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x80, 0x80, 0x80, 0x03, 0x04, 0x80, 0x80, 0x05, 0x06, 0x07, 0x08],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x80, 0x80, 0x80, 0x04, 0x05, 0x80, 0x80, 0x06, 0x07, 0x08, 0x09],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x80, 0x80, 0x80, 0x05, 0x06, 0x80, 0x80, 0x07, 0x08, 0x09, 0x0a],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x80, 0x80, 0x03, 0x04, 0x80, 0x80, 0x05, 0x06, 0x07, 0x08],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x80, 0x80, 0x04, 0x05, 0x80, 0x80, 0x06, 0x07, 0x08, 0x09],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x80, 0x80, 0x05, 0x06, 0x80, 0x80, 0x07, 0x08, 0x09, 0x0a],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x80, 0x80, 0x06, 0x07, 0x80, 0x80, 0x08, 0x09, 0x0a, 0x0b],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x80, 0x04, 0x05, 0x80, 0x80, 0x06, 0x07, 0x08, 0x09],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x80, 0x05, 0x06, 0x80, 0x80, 0x07, 0x08, 0x09, 0x0a],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x80, 0x06, 0x07, 0x80, 0x80, 0x08, 0x09, 0x0a, 0x0b],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x80, 0x07, 0x08, 0x80, 0x80, 0x09, 0x0a, 0x0b, 0x0c],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x80, 0x80, 0x07, 0x08, 0x09, 0x0a],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x80, 0x80, 0x08, 0x09, 0x0a, 0x0b],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x80, 0x80, 0x09, 0x0a, 0x0b, 0x0c],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x80, 0x80, 0x0a, 0x0b, 0x0c, 0x0d],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x80, 0x80, 0x80, 0x02, 0x03, 0x04, 0x80, 0x05, 0x06, 0x07, 0x08],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x80, 0x80, 0x80, 0x03, 0x04, 0x05, 0x80, 0x06, 0x07, 0x08, 0x09],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x80, 0x80, 0x80, 0x04, 0x05, 0x06, 0x80, 0x07, 0x08, 0x09, 0x0a],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x80, 0x80, 0x80, 0x05, 0x06, 0x07, 0x80, 0x08, 0x09, 0x0a, 0x0b],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x80, 0x80, 0x03, 0x04, 0x05, 0x80, 0x06, 0x07, 0x08, 0x09],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x80, 0x80, 0x04, 0x05, 0x06, 0x80, 0x07, 0x08, 0x09, 0x0a],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x80, 0x80, 0x05, 0x06, 0x07, 0x80, 0x08, 0x09, 0x0a, 0x0b],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x80, 0x80, 0x06, 0x07, 0x08, 0x80, 0x09, 0x0a, 0x0b, 0x0c],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x80, 0x04, 0x05, 0x06, 0x80, 0x07, 0x08, 0x09, 0x0a],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x80, 0x05, 0x06, 0x07, 0x80, 0x08, 0x09, 0x0a, 0x0b],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x80, 0x06, 0x07, 0x08, 0x80, 0x09, 0x0a, 0x0b, 0x0c],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x80, 0x07, 0x08, 0x09, 0x80, 0x0a, 0x0b, 0x0c, 0x0d],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x80, 0x08, 0x09, 0x0a, 0x0b],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x80, 0x09, 0x0a, 0x0b, 0x0c],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x80, 0x0a, 0x0b, 0x0c, 0x0d],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x80, 0x0b, 0x0c, 0x0d, 0x0e],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x80, 0x80, 0x80, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x80, 0x80, 0x80, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x80, 0x80, 0x80, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x80, 0x80, 0x80, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x80, 0x80, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x80, 0x80, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x80, 0x80, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x80, 0x80, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x80, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x80, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x80, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x80, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e],
+    [0x00, 0x80, 0x80, 0x80, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c],
+    [0x00, 0x01, 0x80, 0x80, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d],
+    [0x00, 0x01, 0x02, 0x80, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f],
+];
+
+// The shuffle which packs the bytes of four u32 lanes into the lengths given
+// by a control byte. Bytes marked 0x80 are unused.
+static STREAMVBYTE_ENCODE: [[u8; 16]; 256] = [
+    [0x00, 0x04, 0x08, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x08, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x08, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x08, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x08, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x08, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x08, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x08, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x08, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x08, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x08, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x08, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x07, 0x08, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x07, 0x08, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x07, 0x08, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x08, 0x09, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x08, 0x09, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x08, 0x09, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x08, 0x09, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x08, 0x09, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x08, 0x09, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x08, 0x09, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x08, 0x09, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x08, 0x09, 0x0a, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x08, 0x09, 0x0a, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x08, 0x09, 0x0a, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x08, 0x09, 0x0a, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0c, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x08, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x08, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x08, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x08, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x08, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x08, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x08, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x08, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x08, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x08, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x08, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x08, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x07, 0x08, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x07, 0x08, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x07, 0x08, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x08, 0x09, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x08, 0x09, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x08, 0x09, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x08, 0x09, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x08, 0x09, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x08, 0x09, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x08, 0x09, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x08, 0x09, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x80, 0x80],
+    [0x00, 0x04, 0x08, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x08, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x08, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x08, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x08, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x08, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x08, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x08, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x08, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x08, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x08, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x08, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x07, 0x08, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x07, 0x08, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x07, 0x08, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x80, 0x80],
+    [0x00, 0x04, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x80],
+    [0x00, 0x04, 0x08, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x08, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x08, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x08, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x08, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x08, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x08, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x08, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x08, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x08, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x08, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x08, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x07, 0x08, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x07, 0x08, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x07, 0x08, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80],
+    [0x00, 0x04, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0c, 0x0d, 0x0e, 0x0f, 0x80],
+    [0x00, 0x04, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x80],
+    [0x00, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80, 0x80],
+    [0x00, 0x01, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x80, 0x80],
+    [0x00, 0x01, 0x02, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x80],
+    [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f],
+];
+
+// Return the number of data bytes of the four values described by a
+// stream-vbyte control byte.
+#[inline(always)]
+fn group_len(control: u8) -> usize {
+    4 + (control & 3) as usize + (control >> 2 & 3) as usize
+        + (control >> 4 & 3) as usize + (control >> 6) as usize
+}
+
+// Return one fewer than the number of bytes needed to hold `x`.
+#[inline(always)]
+fn vbyte_code(x: u32) -> u8 {
+    (x > 0xFF) as u8 + (x > 0xFFFF) as u8 + (x > 0xFF_FFFF) as u8
+}
+
+/// Return the most bytes [`streamvbyte_encode`] may write for `len` values.
+///
+/// [`streamvbyte_encode`]: fn.streamvbyte_encode.html
+pub fn streamvbyte_max_len(len: usize) -> usize {
+    (len + 3) / 4 + 4 * len
+}
+
+/// Write the stream-vbyte encoding of `input` to `output`, and return the
+/// number of bytes written.
+///
+/// Each value is written as its 1 to 4 low bytes in little-endian order.
+/// The lengths are stored as 2-bit codes, four to a control byte starting
+/// from the least significant bits, and all of the control bytes come
+/// before the values. Codes past the last value are zero. This is the layout
+/// of Lemire and Kurz's reference implementation.
+///
+/// # Panics
+///
+/// This function will panic if the encoding doesn't fit in `output`. An
+/// `output` of [`streamvbyte_max_len`] bytes is always sufficient.
+///
+/// ```
+/// extern crate faster;
+/// use faster::codec::{streamvbyte_encode, streamvbyte_decode, streamvbyte_max_len};
+///
+/// # fn main() {
+/// let mut encoded = [0u8; 22];
+/// assert_eq!(streamvbyte_max_len(5), 22);
+/// let len = streamvbyte_encode(&[1, 300, 70000, 1 << 30, 2], &mut encoded);
+/// assert_eq!(&encoded[..len], &[0xE4, 0x00, 1, 0x2C, 0x01, 0x70, 0x11, 0x01,
+///                               0x00, 0x00, 0x00, 0x40, 2]);
+///
+/// let mut values = [0u32; 5];
+/// assert_eq!(streamvbyte_decode(&encoded[..len], &mut values), Ok(len));
+/// assert_eq!(values, [1, 300, 70000, 1 << 30, 2]);
+/// # }
+/// ```
+///
+/// [`streamvbyte_max_len`]: fn.streamvbyte_max_len.html
+pub fn streamvbyte_encode(input: &[u32], output: &mut [u8]) -> usize {
+    let width = u32s::WIDTH;
+    let mut out = (input.len() + 3) / 4;
+    let mut i = 0;
+
+    if input.len() >= width {
+        let mut indices = [0u8; MAX_WIDTH];
+        let mut lanes = [0u8; MAX_WIDTH];
+        let mut controls = [0u8; MAX_WIDTH / 16];
+        while i + width <= input.len() {
+            let v = u32s::load(input, i);
+            let one = u32s(1);
+            let codes = Cmp::min(&(v >> 8), one) + Cmp::min(&(v >> 16), one)
+                + Cmp::min(&(v >> 24), one);
+            for (g, control) in controls[..width / 4].iter_mut().enumerate() {
+                *control = (0..4).fold(0, |c, k| c | (codes.extract(4 * g + k) as u8) << (2 * k));
+                indices[g * 16..g * 16 + 16]
+                    .copy_from_slice(&STREAMVBYTE_ENCODE[*control as usize]);
+            }
+            v.be_u8s().shuffle_bytes(u8s::load(&indices, 0)).store(&mut lanes, 0);
+
+            for (g, &control) in controls[..width / 4].iter().enumerate() {
+                let len = group_len(control);
+                output[i / 4 + g] = control;
+                output[out..out + len].copy_from_slice(&lanes[g * 16..g * 16 + len]);
+                out += len;
+            }
+            i += width;
+        }
+    }
+
+    for (n, &x) in input.iter().enumerate().skip(i) {
+        let code = vbyte_code(x);
+        if n % 4 == 0 {
+            output[n / 4] = 0;
+        }
+        output[n / 4] |= code << (2 * (n % 4));
+        let len = code as usize + 1;
+        output[out..out + len].copy_from_slice(&x.to_le_bytes()[..len]);
+        out += len;
+    }
+    out
+}
+
+/// Decode stream-vbyte encoded values from `input` until `output` is full,
+/// and return the number of bytes read. This is the inverse of
+/// [`streamvbyte_encode`]. The number of values isn't part of the encoding,
+/// so it must be stored separately.
+///
+/// # Errors
+///
+/// Returns `DecodeError::InvalidLength` if `input` ends before
+/// `output.len()` values.
+///
+/// [`streamvbyte_encode`]: fn.streamvbyte_encode.html
+pub fn streamvbyte_decode(input: &[u8], output: &mut [u32]) -> Result<usize, DecodeError> {
+    let width = u32s::WIDTH;
+    let mut pos = (output.len() + 3) / 4;
+    let mut i = 0;
+    if input.len() < pos {
+        return Err(DecodeError::InvalidLength);
+    }
+
+    if output.len() >= width {
+        let mut indices = [0u8; MAX_WIDTH];
+        let mut lanes = [0u8; MAX_WIDTH];
+        'vectors: while i + width <= output.len() {
+            // Every group reads 16 bytes, which may run past its values
+            let mut next = pos;
+            for g in 0..width / 4 {
+                if next + 16 > input.len() {
+                    break 'vectors;
+                }
+                let control = input[i / 4 + g];
+                lanes[g * 16..g * 16 + 16].copy_from_slice(&input[next..next + 16]);
+                indices[g * 16..g * 16 + 16]
+                    .copy_from_slice(&STREAMVBYTE_DECODE[control as usize]);
+                next += group_len(control);
+            }
+            let shuffle = u8s::load(&indices, 0);
+            let bytes = u8s::load(&lanes, 0).shuffle_bytes(shuffle) & in_range(shuffle, 0, 15);
+            bytes.be_u32s().store(output, i);
+            pos = next;
+            i += width;
+        }
+    }
+
+    for (n, out) in output.iter_mut().enumerate().skip(i) {
+        let len = (input[n / 4] >> (2 * (n % 4)) & 3) as usize + 1;
+        if pos + len > input.len() {
+            return Err(DecodeError::InvalidLength);
+        }
+        let mut bytes = [0u8; 4];
+        bytes[..len].copy_from_slice(&input[pos..pos + len]);
+        *out = u32::from_le_bytes(bytes);
+        pos += len;
+    }
+    Ok(pos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::rng;
+
+    const CONFIGS: [Base64Config; 4] = [
+        Base64Config::STANDARD,
+        Base64Config::URL_SAFE,
+        Base64Config::URL_SAFE_NO_PAD,
+        Base64Config { alphabet: Alphabet::Standard, padding: Padding::Optional },
+    ];
+
+    fn base64_scalar(input: &[u8], config: Base64Config) -> Vec<u8> {
+        let mut out = vec![0u8; base64_encoded_len(input.len(), config)];
+        let len = encode_scalar(input, &mut out, config);
+        assert_eq!(len, out.len());
+        out
+    }
+
+    #[test]
+    fn base64_rfc4648() {
+        let cases: &[(&[u8], &[u8])] = &[
+            (b"", b""), (b"f", b"Zg=="), (b"fo", b"Zm8="), (b"foo", b"Zm9v"),
+            (b"foob", b"Zm9vYg=="), (b"fooba", b"Zm9vYmE="), (b"foobar", b"Zm9vYmFy"),
+        ];
+        let mut buf = [0u8; 16];
+        for &(plain, encoded) in cases {
+            let len = base64_encode(plain, &mut buf, Base64Config::STANDARD);
+            assert_eq!(&buf[..len], encoded);
+            let len = base64_decode(encoded, &mut buf, Base64Config::STANDARD).unwrap();
+            assert_eq!(&buf[..len], plain);
+        }
+    }
+
+    #[test]
+    fn base64_round_trip() {
+        let mut seed = 0x9E3779B97F4A7C15;
+        for len in 0..300 {
+            let input = (0..len).map(|_| rng(&mut seed) as u8).collect::<Vec<_>>();
+            for &config in CONFIGS.iter() {
+                let expected = base64_scalar(&input, config);
+                let mut encoded = vec![0u8; expected.len()];
+                assert_eq!(base64_encode(&input, &mut encoded, config), expected.len());
+                assert_eq!(encoded, expected);
+
+                let mut decoded = vec![0u8; base64_decoded_max_len(encoded.len())];
+                let len = base64_decode(&encoded, &mut decoded, config).unwrap();
+                assert_eq!(&decoded[..len], &input[..]);
+            }
+        }
+    }
+
+    #[test]
+    fn base64_alphabets() {
+        let input = [0xFBu8, 0xFF, 0xBF].repeat(20);
+        let mut out = [0u8; 80];
+        base64_encode(&input, &mut out, Base64Config::STANDARD);
+        assert!(out.iter().all(|&b| b == b'+' || b == b'/' || b == b'7'));
+        base64_encode(&input, &mut out, Base64Config::URL_SAFE);
+        assert!(out.iter().all(|&b| b == b'-' || b == b'_' || b == b'7'));
+        assert_eq!(base64_decode(&out, &mut [0u8; 60], Base64Config::STANDARD),
+                   Err(DecodeError::InvalidByte(0, b'-')));
+    }
+
+    #[test]
+    fn base64_padding() {
+        let mut out = [0u8; 8];
+        let optional = CONFIGS[3];
+        assert_eq!(base64_decode(b"Zm8=", &mut out, Base64Config::URL_SAFE_NO_PAD),
+                   Err(DecodeError::InvalidByte(3, b'=')));
+        assert_eq!(base64_decode(b"Zm8", &mut out, Base64Config::STANDARD),
+                   Err(DecodeError::InvalidLength));
+        assert_eq!(base64_decode(b"Zm8", &mut out, optional), Ok(2));
+        assert_eq!(base64_decode(b"Zm8=", &mut out, optional), Ok(2));
+        assert_eq!(base64_decode(b"Zm8==", &mut out, optional), Err(DecodeError::InvalidLength));
+        assert_eq!(base64_decode(b"Z===", &mut out, optional),
+                   Err(DecodeError::InvalidByte(1, b'=')));
+        assert_eq!(base64_decode(b"Zm=v", &mut out, optional),
+                   Err(DecodeError::InvalidByte(2, b'=')));
+        assert_eq!(base64_decode(b"Zg", &mut out, Base64Config::URL_SAFE_NO_PAD), Ok(1));
+        assert_eq!(base64_decode(b"Zh", &mut out, Base64Config::URL_SAFE_NO_PAD),
+                   Err(DecodeError::InvalidLastSymbol(1, b'h')));
+        assert_eq!(base64_decode(b"Z", &mut out, Base64Config::URL_SAFE_NO_PAD),
+                   Err(DecodeError::InvalidLength));
+    }
+
+    #[test]
+    fn base64_error_positions() {
+        let mut seed = 0x2545F4914F6CDD1D;
+        let input = (0..150).map(|_| rng(&mut seed) as u8).collect::<Vec<_>>();
+        let mut encoded = vec![0u8; base64_encoded_len(input.len(), Base64Config::STANDARD)];
+        base64_encode(&input, &mut encoded, Base64Config::STANDARD);
+        let mut out = vec![0u8; input.len()];
+        for i in 0..encoded.len() - 4 {
+            let mut corrupt = encoded.clone();
+            corrupt[i] = b'*';
+            assert_eq!(base64_decode(&corrupt, &mut out, Base64Config::STANDARD),
+                       Err(DecodeError::InvalidByte(i, b'*')));
+            corrupt[i] = b'=';
+            assert_eq!(base64_decode(&corrupt, &mut out, Base64Config::STANDARD),
+                       Err(DecodeError::InvalidByte(i, b'=')));
+        }
+    }
+
+    #[test]
+    fn hex_round_trip() {
+        let mut seed = 0xDA942042E4DD58B5;
+        for len in 0..200 {
+            let input = (0..len).map(|_| rng(&mut seed) as u8).collect::<Vec<_>>();
+            let expected = input.iter().map(|b| format!("{:02x}", b)).collect::<String>();
+            let mut encoded = vec![0u8; len * 2];
+            assert_eq!(hex_encode(&input, &mut encoded), len * 2);
+            assert_eq!(encoded, expected.as_bytes());
+
+            let mut decoded = vec![0u8; len];
+            assert_eq!(hex_decode(&encoded, &mut decoded), Ok(len));
+            assert_eq!(decoded, input);
+            assert_eq!(hex_decode(expected.to_uppercase().as_bytes(), &mut decoded), Ok(len));
+            assert_eq!(decoded, input);
+        }
+    }
+
+    #[test]
+    fn hex_errors() {
+        let mut out = [0u8; 64];
+        assert_eq!(hex_decode(b"abc", &mut out), Err(DecodeError::InvalidLength));
+        for i in 0..128 {
+            for &bad in &[b'g', b'G', b'/', b':', b'@', b'`', 0xFF] {
+                let mut input = [b'7'; 128];
+                input[i] = bad;
+                assert_eq!(hex_decode(&input, &mut out), Err(DecodeError::InvalidByte(i, bad)));
+            }
+        }
+    }
+
+    // Pack `input` one bit at a time
+    fn bitpack_reference(input: &[u32], bits: u32) -> Vec<u8> {
+        let mut out = vec![0u8; bitpacked_len(input.len(), bits)];
+        for (i, &x) in input.iter().enumerate() {
+            for b in 0..bits as usize {
+                if x >> b & 1 == 1 {
+                    let p = i * bits as usize + b;
+                    out[p / 8] |= 1 << (p % 8);
+                }
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn bitpack_matches_reference() {
+        let mut seed = 0x2545F4914F6CDD1D;
+        for bits in 1..=32 {
+            for len in 0..70 {
+                // The high bits of every value are ignored
+                let input = (0..len).map(|_| rng(&mut seed) as u32).collect::<Vec<_>>();
+                let masked = input.iter().map(|&x| x & low_mask(bits)).collect::<Vec<_>>();
+                let expected = bitpack_reference(&masked, bits);
+
+                let mut packed = vec![0xAA; expected.len() + 3];
+                assert_eq!(bitpack(&input, bits, &mut packed), expected.len());
+                assert_eq!(&packed[..expected.len()], &expected[..], "{} bits", bits);
+                assert_eq!(&packed[expected.len()..], &[0xAA; 3]);
+
+                let mut unpacked = vec![0; len];
+                assert_eq!(bitunpack(&packed[..expected.len()], bits, &mut unpacked), expected.len());
+                assert_eq!(unpacked, masked);
+                assert_eq!(bitunpack(&packed, bits, &mut unpacked), expected.len());
+                assert_eq!(unpacked, masked);
+                if len > 0 {
+                    assert!(bits_needed(&masked) <= bits);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn bitunpack_checks_length() {
+        bitunpack(&[0; 4], 5, &mut [0; 7]);
+    }
+
+    #[test]
+    fn deltas_round_trip() {
+        let mut seed = 0x9E3779B97F4A7C15;
+        for len in 0..70 {
+            let input = (0..len).map(|_| rng(&mut seed) as u32).collect::<Vec<_>>();
+            let mut deltas = vec![0; len];
+            delta_encode(&input, &mut deltas);
+            for i in 0..len {
+                assert_eq!(deltas[i], input[i].wrapping_sub(if i == 0 { 0 } else { input[i - 1] }));
+            }
+            let mut decoded = vec![0; len];
+            delta_decode(&deltas, &mut decoded);
+            assert_eq!(decoded, input);
+
+            // Small steps in both directions, and some which overflow
+            let signed = (0..len).scan(0i32, |x, _| {
+                *x = x.wrapping_add(match rng(&mut seed) % 8 {
+                    0 => i32::max_value(),
+                    n => n as i32 - 4,
+                });
+                Some(*x)
+            }).collect::<Vec<_>>();
+            zigzag_delta_encode(&signed, &mut deltas);
+            for i in 0..len {
+                let d = signed[i].wrapping_sub(if i == 0 { 0 } else { signed[i - 1] });
+                assert_eq!(deltas[i], zigzag(d));
+                assert_eq!(unzigzag(deltas[i]), d);
+            }
+            let mut decoded = vec![0; len];
+            zigzag_delta_decode(&deltas, &mut decoded);
+            assert_eq!(decoded, signed);
+        }
+    }
+
+    // Encode `input` one value at a time
+    fn streamvbyte_reference(input: &[u32]) -> Vec<u8> {
+        let mut controls = vec![0u8; (input.len() + 3) / 4];
+        let mut data = Vec::new();
+        for (i, &x) in input.iter().enumerate() {
+            let bytes = x.to_le_bytes();
+            let len = 4 - bytes.iter().rev().take(3).take_while(|&&b| b == 0).count();
+            controls[i / 4] |= (len as u8 - 1) << (2 * (i % 4));
+            data.extend_from_slice(&bytes[..len]);
+        }
+        controls.extend(data);
+        controls
+    }
+
+    #[test]
+    fn streamvbyte_matches_reference() {
+        let mut seed = 0xDA942042E4DD58B5;
+        for len in 0..100 {
+            let input = (0..len).map(|_| (rng(&mut seed) as u32) >> (8 * (rng(&mut seed) % 4)))
+                .collect::<Vec<_>>();
+            let expected = streamvbyte_reference(&input);
+
+            let mut encoded = vec![0xAA; streamvbyte_max_len(len)];
+            let n = streamvbyte_encode(&input, &mut encoded);
+            assert_eq!(&encoded[..n], &expected[..]);
+
+            let mut decoded = vec![0; len];
+            assert_eq!(streamvbyte_decode(&encoded[..n], &mut decoded), Ok(n));
+            assert_eq!(decoded, input);
+            assert_eq!(streamvbyte_decode(&encoded, &mut decoded), Ok(n));
+            assert_eq!(decoded, input);
+            if len > 0 {
+                assert_eq!(streamvbyte_decode(&encoded[..n - 1], &mut decoded),
+                           Err(DecodeError::InvalidLength));
+            }
+        }
+    }
+}
@@ -0,0 +1,348 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Convolution and FIR filtering of `f32`, `f64` and `i16` signals.
+//!
+//! A vector of outputs is computed at once by broadcasting each coefficient
+//! of the kernel and multiplying it with a vector of the input, shifted by
+//! the coefficient's index. Outputs near the ends of the input, whose sums
+//! would need samples from outside of it, are computed one at a time.
+
+use crate::vecs::*;
+use crate::intrin::wrapping::WrappingLanes;
+use crate::core::ops::{Add, Mul};
+
+/// A type of sample which can be convolved.
+pub trait Sample : Packable + Add<Output = Self> + Mul<Output = Self> {
+    const ZERO: Self;
+
+    /// Return the sum of `self` and `other`, which wraps around on overflow
+    /// for integers.
+    fn wrapping_add(self, other: Self) -> Self;
+
+    /// Return the product of `self` and `other`, which wraps around on
+    /// overflow for integers.
+    fn wrapping_mul(self, other: Self) -> Self;
+
+    #[doc(hidden)]
+    fn wrapping_add_vectors(a: Self::Vector, b: Self::Vector) -> Self::Vector;
+
+    #[doc(hidden)]
+    fn wrapping_mul_vectors(a: Self::Vector, b: Self::Vector) -> Self::Vector;
+}
+
+macro_rules! impl_sample {
+    (float $($el:tt),*) => (
+        $(
+            impl Sample for $el {
+                const ZERO: Self = 0.0;
+
+                #[inline(always)]
+                fn wrapping_add(self, other: Self) -> Self { self + other }
+                #[inline(always)]
+                fn wrapping_mul(self, other: Self) -> Self { self * other }
+                #[inline(always)]
+                fn wrapping_add_vectors(a: Self::Vector, b: Self::Vector) -> Self::Vector { a + b }
+                #[inline(always)]
+                fn wrapping_mul_vectors(a: Self::Vector, b: Self::Vector) -> Self::Vector { a * b }
+            }
+        )*
+    );
+    (int $($el:tt),*) => (
+        $(
+            impl Sample for $el {
+                const ZERO: Self = 0;
+
+                #[inline(always)]
+                fn wrapping_add(self, other: Self) -> Self { $el::wrapping_add(self, other) }
+                #[inline(always)]
+                fn wrapping_mul(self, other: Self) -> Self { $el::wrapping_mul(self, other) }
+                #[inline(always)]
+                fn wrapping_add_vectors(a: Self::Vector, b: Self::Vector) -> Self::Vector {
+                    a.wrapping_add_lanes(b)
+                }
+                #[inline(always)]
+                fn wrapping_mul_vectors(a: Self::Vector, b: Self::Vector) -> Self::Vector {
+                    a.wrapping_mul_lanes(b)
+                }
+            }
+        )*
+    );
+}
+
+impl_sample!(float f32, f64);
+impl_sample!(int i16);
+
+/// Which outputs of a convolution [`convolve`] computes.
+///
+/// [`convolve`]: fn.convolve.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConvolveMode {
+    /// Every output which overlaps the input, as if the input were padded
+    /// with zeroes on both sides.
+    Full,
+    /// As many outputs as there are inputs, centered on the full convolution.
+    Same,
+    /// Only the outputs which don't depend on any padding.
+    Valid,
+}
+
+/// Return the number of outputs [`convolve`] writes for an input of
+/// `input_len` samples and a kernel of `kernel_len` coefficients.
+///
+/// [`convolve`]: fn.convolve.html
+pub fn convolved_len(input_len: usize, kernel_len: usize, mode: ConvolveMode) -> usize {
+    if input_len == 0 || kernel_len == 0 {
+        return 0;
+    }
+    match mode {
+        ConvolveMode::Full => input_len + kernel_len - 1,
+        ConvolveMode::Same => input_len,
+        ConvolveMode::Valid => (input_len + 1).saturating_sub(kernel_len),
+    }
+}
+
+/// Write the convolution of `input` with `kernel` to `output`, and return
+/// the number of samples written. Output i of the full convolution is the sum
+/// of `input[i - j] * kernel[j]` over every j for which both are in range.
+///
+/// # Panics
+///
+/// This function will panic if `output` is shorter than
+/// [`convolved_len`]`(input.len(), kernel.len(), mode)`.
+///
+/// ```
+/// extern crate faster;
+/// use faster::dsp::{convolve, ConvolveMode};
+///
+/// # fn main() {
+/// let mut out = [0i16; 6];
+/// let len = convolve(&[1, 2, 3, 4], &[1, 0, -1], &mut out, ConvolveMode::Full);
+/// assert_eq!(&out[..len], &[1, 2, 2, 2, -3, -4]);
+/// let len = convolve(&[1, 2, 3, 4], &[1, 0, -1], &mut out, ConvolveMode::Same);
+/// assert_eq!(&out[..len], &[2, 2, 2, -3]);
+/// let len = convolve(&[1, 2, 3, 4], &[1, 0, -1], &mut out, ConvolveMode::Valid);
+/// assert_eq!(&out[..len], &[2, 2]);
+/// # }
+/// ```
+///
+/// [`convolved_len`]: fn.convolved_len.html
+pub fn convolve<T>(input: &[T], kernel: &[T], output: &mut [T], mode: ConvolveMode) -> usize
+    where T : Sample {
+    let len = convolved_len(input.len(), kernel.len(), mode);
+    assert!(output.len() >= len, "the output of a convolution doesn't fit");
+    let start = match mode {
+        ConvolveMode::Full => 0,
+        ConvolveMode::Same => (kernel.len().max(1) - 1) / 2,
+        ConvolveMode::Valid => kernel.len().max(1) - 1,
+    };
+    convolve_range(input, kernel, &mut output[..len], start);
+    len
+}
+
+// Write outputs `start..start + output.len()` of the full convolution of
+// `input` and `kernel` to `output`.
+#[inline(always)]
+fn convolve_range<T>(input: &[T], kernel: &[T], output: &mut [T], start: usize)
+    where T : Sample {
+    let width = T::Vector::WIDTH;
+    let (n, m) = (input.len(), kernel.len());
+    let end = start + output.len();
+    if n == 0 || m == 0 {
+        return;
+    }
+
+    // Outputs from m - 1 up to n only read samples within the input
+    let interior = (m - 1).max(start).min(end);
+    for k in start..interior {
+        output[k - start] = convolve_at(input, kernel, k);
+    }
+    let mut k = interior;
+    while k + width <= end.min(n) {
+        let mut acc = T::Vector::splat(T::ZERO);
+        for (j, &c) in kernel.iter().enumerate() {
+            let product = T::wrapping_mul_vectors(T::Vector::splat(c), T::Vector::load(input, k - j));
+            acc = T::wrapping_add_vectors(acc, product);
+        }
+        acc.store(output, k - start);
+        k += width;
+    }
+    for k in k..end {
+        output[k - start] = convolve_at(input, kernel, k);
+    }
+}
+
+// Return output `k` of the full convolution of `input` and `kernel`.
+#[inline(always)]
+fn convolve_at<T>(input: &[T], kernel: &[T], k: usize) -> T where T : Sample {
+    let first = (k + 1).saturating_sub(input.len());
+    let last = k.min(kernel.len() - 1);
+    (first..=last).fold(T::ZERO, |acc, j| acc.wrapping_add(input[k - j].wrapping_mul(kernel[j])))
+}
+
+/// A finite impulse response filter, which convolves a stream of blocks of
+/// samples with its taps as if they were one signal.
+///
+/// ```
+/// extern crate faster;
+/// use faster::dsp::FirFilter;
+///
+/// # #[cfg(not(feature = "std"))]
+/// # fn main() { }
+///
+/// # #[cfg(feature = "std")]
+/// # fn main() {
+/// // A moving sum of three samples
+/// let mut filter = FirFilter::new(&[1i16, 1, 1]);
+/// let mut out = [0; 4];
+/// filter.process(&[1, 2, 3, 4], &mut out);
+/// assert_eq!(out, [1, 3, 6, 9]);
+/// filter.process(&[5, 6, 7, 8], &mut out);
+/// assert_eq!(out, [12, 15, 18, 21]);
+/// # }
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct FirFilter<T> where T : Sample {
+    taps: Vec<T>,
+    // The last `taps.len() - 1` samples, followed by the block being filtered
+    buf: Vec<T>,
+}
+
+#[cfg(feature = "std")]
+impl<T> FirFilter<T> where T : Sample {
+    /// Return a filter with the given taps, whose history is all zeroes.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `taps` is empty.
+    pub fn new(taps: &[T]) -> Self {
+        assert!(!taps.is_empty(), "a filter needs at least one tap");
+        FirFilter {
+            taps: taps.to_vec(),
+            buf: vec![T::ZERO; taps.len() - 1],
+        }
+    }
+
+    /// Return the taps of this filter.
+    pub fn taps(&self) -> &[T] {
+        &self.taps
+    }
+
+    /// Filter `input`, which follows the samples of all previous calls, and
+    /// write one output per sample to `output`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `input` and `output` have different
+    /// lengths.
+    pub fn process(&mut self, input: &[T], output: &mut [T]) {
+        assert_eq!(input.len(), output.len(), "a filter writes one output per sample");
+        let history = self.taps.len() - 1;
+        self.buf.extend_from_slice(input);
+        convolve_range(&self.buf, &self.taps, output, history);
+
+        let len = self.buf.len();
+        self.buf.drain(..len - history);
+    }
+
+    /// Set the history of this filter to zeroes, as if it were new.
+    pub fn reset(&mut self) {
+        for s in self.buf.iter_mut() {
+            *s = T::ZERO;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::rng;
+
+    // Return the full convolution of `input` and `kernel` in a wider type
+    fn full(input: &[i16], kernel: &[i16]) -> Vec<i64> {
+        let mut ret = vec![0; (input.len() + kernel.len()).saturating_sub(1)];
+        for (i, &x) in input.iter().enumerate() {
+            for (j, &c) in kernel.iter().enumerate() {
+                ret[i + j] += x as i64 * c as i64;
+            }
+        }
+        ret
+    }
+
+    #[test]
+    fn convolve_matches_scalar() {
+        let mut seed = 0x9E3779B97F4A7C15;
+        for n in 0..50 {
+            for m in 1..12 {
+                let input = (0..n).map(|_| (rng(&mut seed) % 61) as i16 - 30).collect::<Vec<_>>();
+                let kernel = (0..m).map(|_| (rng(&mut seed) % 21) as i16 - 10).collect::<Vec<_>>();
+                let expected = full(&input, &kernel);
+                let (inputf, kernelf) = (input.iter().map(|&x| x as f32).collect::<Vec<_>>(),
+                                         kernel.iter().map(|&x| x as f32).collect::<Vec<_>>());
+                let (inputd, kerneld) = (input.iter().map(|&x| x as f64).collect::<Vec<_>>(),
+                                         kernel.iter().map(|&x| x as f64).collect::<Vec<_>>());
+
+                for &(mode, start) in &[(ConvolveMode::Full, 0), (ConvolveMode::Same, (m - 1) / 2),
+                                        (ConvolveMode::Valid, m - 1)] {
+                    let len = convolved_len(n, m, mode);
+                    let expected = if n == 0 { &[][..] } else { &expected[start..start + len] };
+
+                    let mut out = vec![0i16; len + 3];
+                    assert_eq!(convolve(&input, &kernel, &mut out, mode), len);
+                    assert_eq!(out[..len].iter().map(|&x| x as i64).collect::<Vec<_>>(), expected);
+                    assert_eq!(&out[len..], &[0, 0, 0]);
+
+                    let mut out = vec![0f32; len];
+                    convolve(&inputf, &kernelf, &mut out, mode);
+                    assert_eq!(out.iter().map(|&x| x as i64).collect::<Vec<_>>(), expected);
+
+                    let mut out = vec![0f64; len];
+                    convolve(&inputd, &kerneld, &mut out, mode);
+                    assert_eq!(out.iter().map(|&x| x as i64).collect::<Vec<_>>(), expected);
+                }
+
+                // Products and sums which overflow an i16 wrap around, in
+                // the vectors and at the ends alike
+                let input = input.iter().map(|&x| x.wrapping_mul(1000)).collect::<Vec<_>>();
+                let kernel = kernel.iter().map(|&x| x.wrapping_mul(300)).collect::<Vec<_>>();
+                let expected = full(&input, &kernel);
+                let mut out = vec![0i16; expected.len()];
+                convolve(&input, &kernel, &mut out, ConvolveMode::Full);
+                assert_eq!(out, expected.iter().map(|&x| x as i16).collect::<Vec<_>>());
+            }
+        }
+    }
+
+    #[test]
+    fn fir_filter_matches_convolve() {
+        let mut seed = 0xDA942042E4DD58B5;
+        for m in 1..20 {
+            let taps = (0..m).map(|_| (rng(&mut seed) % 21) as i16 - 10).collect::<Vec<_>>();
+            let signal = (0..300).map(|_| (rng(&mut seed) % 61) as i16 - 30).collect::<Vec<_>>();
+            let mut expected = vec![0; signal.len() + m - 1];
+            convolve(&signal, &taps, &mut expected, ConvolveMode::Full);
+
+            // Blocks of every size, including empty ones
+            let mut filter = FirFilter::new(&taps);
+            let mut out = vec![0; signal.len()];
+            let (mut pos, mut block) = (0, 0);
+            while pos < signal.len() {
+                let end = (pos + block).min(signal.len());
+                filter.process(&signal[pos..end], &mut out[pos..end]);
+                pos = end;
+                block = (block + 7) % 40;
+            }
+            assert_eq!(&out[..], &expected[..signal.len()]);
+
+            filter.reset();
+            let mut first = [0; 1];
+            filter.process(&signal[..1], &mut first);
+            assert_eq!(first[0], signal[0] * taps[0]);
+        }
+    }
+}
@@ -0,0 +1,165 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Hashing many keys at once.
+//!
+//! Each element of a vector is hashed on its own with [`HashLanes`], so the
+//! hash of a key doesn't depend on the vector width or on its position in
+//! the input. Partitions built from these hashes are the same on every
+//! machine.
+//!
+//! [`HashLanes`]: ../intrin/hash_lanes/trait.HashLanes.html
+
+use crate::iters::*;
+use crate::vecs::*;
+use crate::intrin::hash_lanes::HashLanes;
+
+/// A hash function for [`SIMDHashIterator::simd_hash`], and its parameters.
+///
+/// [`SIMDHashIterator::simd_hash`]: trait.SIMDHashIterator.html#method.simd_hash
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LaneHasher<T> {
+    /// See [`HashLanes::hash_multiply_shift`].
+    ///
+    /// [`HashLanes::hash_multiply_shift`]: ../intrin/hash_lanes/trait.HashLanes.html#tymethod.hash_multiply_shift
+    MultiplyShift { multiplier: T, bits: u32 },
+    /// See [`HashLanes::hash_murmur3`].
+    ///
+    /// [`HashLanes::hash_murmur3`]: ../intrin/hash_lanes/trait.HashLanes.html#tymethod.hash_murmur3
+    Murmur3,
+    /// See [`HashLanes::hash_xxh`].
+    ///
+    /// [`HashLanes::hash_xxh`]: ../intrin/hash_lanes/trait.HashLanes.html#tymethod.hash_xxh
+    XxHash { seed: T },
+}
+
+impl<T> LaneHasher<T> where T : Packable {
+    /// Return a vector of the hashes of the elements of `v`.
+    #[inline(always)]
+    pub fn hash<V>(&self, v: V) -> V where V : HashLanes<Scalar = T> {
+        match *self {
+            LaneHasher::MultiplyShift { multiplier, bits } => v.hash_multiply_shift(multiplier, bits),
+            LaneHasher::Murmur3 => v.hash_murmur3(),
+            LaneHasher::XxHash { seed } => v.hash_xxh(seed),
+        }
+    }
+}
+
+/// An iterator which hashes the vectors of another, returned by
+/// [`SIMDHashIterator::simd_hash`].
+///
+/// [`SIMDHashIterator::simd_hash`]: trait.SIMDHashIterator.html#method.simd_hash
+#[derive(Clone, Debug)]
+pub struct SIMDHash<I> where I : SIMDIterable {
+    pub iter: I,
+    pub hasher: LaneHasher<I::Scalar>,
+}
+
+/// An iterator of vectors which can be hashed.
+pub trait SIMDHashIterator : SIMDIterator where Self::Vector : HashLanes {
+    /// Return an iterator which hashes each element of this iterator with
+    /// `hasher`.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// let keys = (0..100u32).collect::<Vec<_>>();
+    /// let buckets = keys.simd_iter(u32s(0))
+    ///     .simd_hash(LaneHasher::MultiplyShift { multiplier: 0x9E37_79B1, bits: 4 })
+    ///     .scalar_collect();
+    /// assert_eq!(buckets[3], 0x9E37_79B1u32.wrapping_mul(3) >> 28);
+    /// # }
+    /// ```
+    #[inline(always)]
+    fn simd_hash(self, hasher: LaneHasher<Self::Scalar>) -> SIMDHash<Self> {
+        SIMDHash {
+            iter: self,
+            hasher,
+        }
+    }
+}
+
+impl<I> SIMDHashIterator for I where I : SIMDIterator, I::Vector : HashLanes {}
+
+impl<I> Iterator for SIMDHash<I> where I : SIMDIterable, I::Vector : HashLanes {
+    type Item = I::Vector;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        let hasher = &self.hasher;
+        self.iter.next().map(|v| hasher.hash(v))
+    }
+}
+
+impl<I> ExactSizeIterator for SIMDHash<I> where I : SIMDIterable, I::Vector : HashLanes {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+impl<I> SIMDObject for SIMDHash<I> where I : SIMDIterable, I::Vector : HashLanes {
+    type Vector = I::Vector;
+    type Scalar = I::Scalar;
+}
+
+impl<I> SIMDSized for SIMDHash<I> where I : SIMDIterable, I::Vector : HashLanes {
+    #[inline(always)]
+    fn scalar_len(&self) -> usize {
+        self.iter.scalar_len()
+    }
+}
+
+impl<I> SIMDIterable for SIMDHash<I> where I : SIMDIterable, I::Vector : HashLanes {
+    #[inline(always)]
+    fn scalar_pos(&self) -> usize {
+        self.iter.scalar_pos()
+    }
+
+    #[inline(always)]
+    fn advance(&mut self, amount: usize) {
+        self.iter.advance(amount);
+    }
+
+    #[inline(always)]
+    fn default(&self) -> Self::Vector {
+        self.hasher.hash(self.iter.default())
+    }
+}
+
+impl<I> SIMDIterator for SIMDHash<I> where I : SIMDIterator, I::Vector : HashLanes {
+    #[inline(always)]
+    fn end(&mut self) -> Option<(Self::Vector, usize)> {
+        let (v, n) = self.iter.end()?;
+        Some((self.hasher.hash(v), n))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn simd_hash_matches_lanes() {
+        let hashers = [LaneHasher::MultiplyShift { multiplier: 0x2545_F491_4F6C_DD1D, bits: 10 },
+                       LaneHasher::Murmur3,
+                       LaneHasher::XxHash { seed: 42 }];
+        for len in 0..40u64 {
+            let keys = (0..len).map(|x| x * 0x9E37_79B9).collect::<Vec<_>>();
+            for hasher in hashers.iter() {
+                let hashed = keys.simd_iter(u64s(0)).simd_hash(*hasher).scalar_collect();
+                assert_eq!(hashed.len(), keys.len());
+                for (&key, &hash) in keys.iter().zip(hashed.iter()) {
+                    // Each key hashes the same in a vector of any width
+                    assert_eq!(hash, hasher.hash(u64s(key)).extract(0));
+                }
+            }
+        }
+    }
+}
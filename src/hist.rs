@@ -0,0 +1,176 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Counting values into histograms.
+//!
+//! Both functions add to counts in a caller-provided array, so histograms
+//! can be built up over many slices without allocating.
+
+use crate::arch::current::vecs::*;
+use crate::vecs::*;
+use crate::intrin::cmp::*;
+use crate::intrin::eq::*;
+
+// The most bytes we count before flushing the sub-histograms, so that none of
+// their counters overflow
+const FLUSH: usize = 1 << 30;
+
+/// Add the number of occurrences of each byte value in `data` to `counts`.
+///
+/// Consecutive bytes are counted in four separate sub-histograms, so a run
+/// of the same byte doesn't make each increment wait for the one before it.
+///
+/// ```
+/// extern crate faster;
+/// use faster::hist::histogram_u8;
+///
+/// # fn main() {
+/// let mut counts = [0; 256];
+/// histogram_u8(b"hello", &mut counts);
+/// histogram_u8(b"world", &mut counts);
+/// assert_eq!(counts[b'l' as usize], 3);
+/// assert_eq!(counts[b'o' as usize], 2);
+/// assert_eq!(counts.iter().sum::<u64>(), 10);
+/// # }
+/// ```
+pub fn histogram_u8(data: &[u8], counts: &mut [u64; 256]) {
+    let width = u8s::WIDTH;
+    for chunk in data.chunks(FLUSH) {
+        let mut sub = [[0u32; 256]; 4];
+        let vectors = chunk.len() / width;
+        for k in 0..vectors {
+            let v = u8s::load(chunk, k * width);
+            for i in (0..width).step_by(4) {
+                sub[0][v.extract(i) as usize] += 1;
+                sub[1][v.extract(i + 1) as usize] += 1;
+                sub[2][v.extract(i + 2) as usize] += 1;
+                sub[3][v.extract(i + 3) as usize] += 1;
+            }
+        }
+        for &b in &chunk[vectors * width..] {
+            sub[0][b as usize] += 1;
+        }
+
+        for (i, count) in counts.iter_mut().enumerate() {
+            *count += (sub[0][i] + sub[1][i] + sub[2][i] + sub[3][i]) as u64;
+        }
+    }
+}
+
+// The masks returned when comparing vectors of f32s
+type Mask = <f32s as Eq>::Out;
+
+/// Add the number of elements of `data` which fall in each bin to `counts`.
+/// Bin i holds the values from `edges[i]`, inclusive, to `edges[i + 1]`,
+/// exclusive, except for the last bin, which includes its upper edge too.
+/// Values outside of the edges and NaNs aren't counted.
+///
+/// The bin of a whole vector is found at once, by counting the edges which
+/// are at most each element.
+///
+/// # Panics
+///
+/// This function will panic if `edges` doesn't have exactly one more
+/// element than `counts`, if there are fewer than two edges, or if the edges
+/// aren't sorted.
+///
+/// ```
+/// extern crate faster;
+/// use faster::hist::histogram_bins;
+///
+/// # fn main() {
+/// let mut counts = [0; 3];
+/// histogram_bins(&[0.5, 1.0, 1.5, 2.5, 3.0, 3.5, -1.0], &[0.0, 1.0, 2.0, 3.0], &mut counts);
+/// assert_eq!(counts, [1, 2, 2]);
+/// # }
+/// ```
+pub fn histogram_bins(data: &[f32], edges: &[f32], counts: &mut [u64]) {
+    assert!(edges.len() >= 2, "a histogram needs at least two edges");
+    assert_eq!(edges.len(), counts.len() + 1, "a histogram needs one more edge than bins");
+    assert!(edges.windows(2).all(|e| e[0] <= e[1]), "the edges of a histogram must be sorted");
+
+    let width = f32s::WIDTH;
+    let last = edges[edges.len() - 1];
+    let mut count = |x: f32, idx: usize| {
+        match idx {
+            0 => {},
+            n if n == edges.len() => if x == last { counts[n - 2] += 1 },
+            i => counts[i - 1] += 1,
+        }
+    };
+
+    let one = <Mask as Packed>::splat(1);
+    let mut indices = [0u32; 16];
+    let vectors = data.len() / width;
+    for k in 0..vectors {
+        let v = f32s::load(data, k * width);
+        // The number of edges at most each element, which is one more than
+        // the bin it falls in. NaNs are never equal to anything, so they
+        // compare below every edge.
+        let mut idx = <Mask as Packed>::splat(0);
+        for &edge in edges {
+            idx = idx + (Cmp::max(&v, f32s(edge)).eq_mask(v) & one);
+        }
+        idx.store(&mut indices, 0);
+        for (i, &idx) in indices[..width].iter().enumerate() {
+            count(v.extract(i), idx as usize);
+        }
+    }
+    for &x in &data[vectors * width..] {
+        count(x, edges.iter().filter(|&&e| x >= e).count());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::rng;
+
+    #[test]
+    fn histogram_u8_matches_scalar() {
+        let mut seed = 0x9E3779B97F4A7C15;
+        for len in (0..100).chain(vec![1000, 4099]) {
+            // Long runs of a few values, and some noise
+            let data = (0..len).map(|i| match rng(&mut seed) % 4 {
+                0 => rng(&mut seed) as u8,
+                _ => (i / 37) as u8,
+            }).collect::<Vec<_>>();
+            let mut expected = [0u64; 256];
+            for &b in &data {
+                expected[b as usize] += 1;
+            }
+            let mut counts = [0u64; 256];
+            histogram_u8(&data, &mut counts);
+            assert_eq!(&counts[..], &expected[..]);
+        }
+    }
+
+    #[test]
+    fn histogram_bins_matches_scalar() {
+        let mut seed = 0xDA942042E4DD58B5;
+        let edges = [-10.0, -2.5, 0.0, 0.0, 1.0, 7.25, 100.0];
+        for len in 0..100 {
+            let data = (0..len).map(|_| match rng(&mut seed) % 8 {
+                0 => edges[rng(&mut seed) as usize % edges.len()],
+                1 => ::std::f32::NAN,
+                _ => (rng(&mut seed) % 24000) as f32 / 100.0 - 20.0,
+            }).collect::<Vec<_>>();
+
+            let mut expected = [0u64; 6];
+            for &x in &data {
+                if x == 100.0 {
+                    expected[5] += 1;
+                } else if let Some(bin) = (0..6).find(|&i| edges[i] <= x && x < edges[i + 1]) {
+                    expected[bin] += 1;
+                }
+            }
+            let mut counts = [0u64; 6];
+            histogram_bins(&data, &edges, &mut counts);
+            assert_eq!(counts, expected, "{:?}", data);
+        }
+    }
+}
@@ -0,0 +1,721 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Processing of images made of bytes.
+//!
+//! Images are borrowed as [`Plane`]s, whose rows may be further apart than
+//! their width, so the functions here can work on part of a larger image.
+//! The width of a plane counts bytes, so a row of an RGBA image with `n`
+//! pixels is `4 * n` wide.
+//!
+//! Packed pixels are split into their channels with [`Stride`], and put back
+//! together with [`IntoInterleaved`]. Sums of bytes are computed in 16-bit
+//! lanes, and the weighted sums of RGBA pixels use [`MaddBytes`].
+//!
+//! [`Plane`]: struct.Plane.html
+//! [`Stride`]: ../stride/trait.Stride.html
+//! [`IntoInterleaved`]: ../zip/trait.IntoInterleaved.html
+//! [`MaddBytes`]: ../intrin/madd_bytes/trait.MaddBytes.html
+
+use crate::arch::current::vecs::*;
+use crate::vecs::*;
+use crate::iters::*;
+use crate::stride::*;
+use crate::zip::*;
+use crate::intrin::cmp::*;
+use crate::intrin::downcast::*;
+use crate::intrin::madd_bytes::*;
+use crate::intrin::saturating_add::*;
+use crate::intrin::transmute::*;
+use crate::intrin::upcast::*;
+#[cfg(feature = "std")]
+use crate::intrin::wrapping::WrappingLanes;
+
+// The weights of the red, green and blue channels in a gray pixel, out of 128
+const RED: u8 = 38;
+const GREEN: u8 = 75;
+const BLUE: u8 = 15;
+
+/// An image of bytes borrowed from a slice. Row y of the image holds the
+/// `width` bytes from index `y * stride` of the slice.
+#[derive(Clone, Copy, Debug)]
+pub struct Plane<'a> {
+    data: &'a [u8],
+    width: usize,
+    height: usize,
+    stride: usize,
+}
+
+/// A mutable image of bytes borrowed from a slice, laid out as in [`Plane`].
+///
+/// [`Plane`]: struct.Plane.html
+#[derive(Debug)]
+pub struct PlaneMut<'a> {
+    data: &'a mut [u8],
+    width: usize,
+    height: usize,
+    stride: usize,
+}
+
+// Panic if rows of this width overlap, or if the last row of an image with
+// these dimensions is past the end of a slice of length `len`.
+fn check_bounds(len: usize, width: usize, height: usize, stride: usize) {
+    assert!(stride >= width, "rows of {} bytes don't fit in a stride of {}", width, stride);
+    if width > 0 && height > 0 {
+        assert!((height - 1) * stride + width <= len,
+                "a {}x{} image with stride {} doesn't fit in {} bytes", width, height, stride, len);
+    }
+}
+
+impl<'a> Plane<'a> {
+    /// Return a `width` by `height` image stored in `data`, with rows
+    /// starting every `stride` bytes.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `stride` is less than `width`, or if the
+    /// image doesn't fit in `data`.
+    pub fn new(data: &'a [u8], width: usize, height: usize, stride: usize) -> Self {
+        check_bounds(data.len(), width, height, stride);
+        Plane { data, width, height, stride }
+    }
+
+    /// Return the width of this image, in bytes.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Return the number of rows of this image.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Return the distance between the starts of consecutive rows.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Return row `y` of this image.
+    #[inline(always)]
+    pub fn row(&self, y: usize) -> &'a [u8] {
+        assert!(y < self.height);
+        &self.data[y * self.stride..y * self.stride + self.width]
+    }
+}
+
+impl<'a> PlaneMut<'a> {
+    /// Return a `width` by `height` image stored in `data`, with rows
+    /// starting every `stride` bytes.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `stride` is less than `width`, or if the
+    /// image doesn't fit in `data`.
+    pub fn new(data: &'a mut [u8], width: usize, height: usize, stride: usize) -> Self {
+        check_bounds(data.len(), width, height, stride);
+        PlaneMut { data, width, height, stride }
+    }
+
+    /// Return the width of this image, in bytes.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Return the number of rows of this image.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Return the distance between the starts of consecutive rows.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Return an immutable view of this image.
+    pub fn as_plane(&self) -> Plane {
+        Plane {
+            data: &*self.data,
+            width: self.width,
+            height: self.height,
+            stride: self.stride,
+        }
+    }
+
+    /// Return row `y` of this image.
+    #[inline(always)]
+    pub fn row(&self, y: usize) -> &[u8] {
+        assert!(y < self.height);
+        &self.data[y * self.stride..y * self.stride + self.width]
+    }
+
+    /// Return row `y` of this image, which may be modified.
+    #[inline(always)]
+    pub fn row_mut(&mut self, y: usize) -> &mut [u8] {
+        assert!(y < self.height);
+        &mut self.data[y * self.stride..y * self.stride + self.width]
+    }
+}
+
+// Panic if `dst` doesn't have the given dimensions.
+fn check_size(dst: &PlaneMut, width: usize, height: usize) {
+    assert!(dst.width() == width && dst.height() == height,
+            "expected a {}x{} destination, but it's {}x{}", width, height, dst.width(), dst.height());
+}
+
+// Return `i`, clamped to the indices of something of length `len`.
+#[inline(always)]
+fn clamp(i: isize, len: usize) -> usize {
+    i.max(0).min(len as isize - 1) as usize
+}
+
+// Return `x / 255`, rounded to the nearest integer.
+#[inline(always)]
+fn div255(x: u32) -> u8 {
+    let t = x + 128;
+    ((t + (t >> 8)) >> 8) as u8
+}
+
+// Return `x / 255` for every element of `x`, rounded to the nearest
+// integer. The elements of `x` must be at most 255 * 255.
+#[inline(always)]
+fn div255_vec(x: u16s) -> u16s {
+    let t = x + u16s(128);
+    (t + (t >> 8)) >> 8
+}
+
+// Return `a * b / 255` for every element, rounded to the nearest integer.
+#[inline(always)]
+fn mul_div255(a: u8s, b: u8s) -> u8s {
+    let (a0, a1): (u16s, u16s) = a.upcast();
+    let (b0, b1): (u16s, u16s) = b.upcast();
+    div255_vec(a0 * b0).saturating_downcast(div255_vec(a1 * b1))
+}
+
+// Return the gray value of a pixel.
+#[inline(always)]
+fn gray(r: u8, g: u8, b: u8) -> u8 {
+    ((u32::from(r) * u32::from(RED) + u32::from(g) * u32::from(GREEN)
+      + u32::from(b) * u32::from(BLUE) + 64) >> 7) as u8
+}
+
+/// Write the luma of each pixel of an RGB image to `dst`, weighting the
+/// channels as in BT.601, to within 1/128.
+///
+/// # Panics
+///
+/// This function will panic if `src` isn't three times as wide as `dst`, or
+/// if they have different heights.
+///
+/// ```
+/// extern crate faster;
+/// use faster::image::{rgb_to_gray, Plane, PlaneMut};
+///
+/// # fn main() {
+/// let rgb = [255, 0, 0, 0, 255, 0, 0, 0, 255, 200, 200, 200];
+/// let mut gray = [0; 4];
+/// rgb_to_gray(Plane::new(&rgb, 12, 1, 12), &mut PlaneMut::new(&mut gray, 4, 1, 4));
+/// assert_eq!(gray, [76, 149, 30, 200]);
+/// # }
+/// ```
+pub fn rgb_to_gray(src: Plane, dst: &mut PlaneMut) {
+    assert!(src.width() % 3 == 0, "an RGB image must be a multiple of 3 bytes wide");
+    check_size(dst, src.width() / 3, src.height());
+    let weights = (u16s(u16::from(RED)), u16s(u16::from(GREEN)), u16s(u16::from(BLUE)));
+    for y in 0..src.height() {
+        src.row(y).stride_three(tuplify!(3, u8s(0))).zip()
+            .simd_map(|(r, g, b)| {
+                let (r0, r1): (u16s, u16s) = r.upcast();
+                let (g0, g1): (u16s, u16s) = g.upcast();
+                let (b0, b1): (u16s, u16s) = b.upcast();
+                let lo = (r0 * weights.0 + g0 * weights.1 + b0 * weights.2 + u16s(64)) >> 7;
+                let hi = (r1 * weights.0 + g1 * weights.1 + b1 * weights.2 + u16s(64)) >> 7;
+                let gray: u8s = lo.saturating_downcast(hi);
+                gray
+            })
+            .scalar_fill(dst.row_mut(y));
+    }
+}
+
+/// Write the luma of each pixel of an RGBA image to `dst`, ignoring alpha,
+/// with the same weights as [`rgb_to_gray`].
+///
+/// The weighted sums of each vector of pixels are computed in place with
+/// [`MaddBytes`], without separating the channels.
+///
+/// # Panics
+///
+/// This function will panic if `src` isn't four times as wide as `dst`, or
+/// if they have different heights.
+///
+/// ```
+/// extern crate faster;
+/// use faster::image::{rgba_to_gray, Plane, PlaneMut};
+///
+/// # fn main() {
+/// let rgba = [255, 0, 0, 255, 0, 255, 0, 0, 0, 0, 255, 255, 200, 200, 200, 9];
+/// let mut gray = [0; 4];
+/// rgba_to_gray(Plane::new(&rgba, 16, 1, 16), &mut PlaneMut::new(&mut gray, 4, 1, 4));
+/// assert_eq!(gray, [76, 149, 30, 200]);
+/// # }
+/// ```
+///
+/// [`rgb_to_gray`]: fn.rgb_to_gray.html
+/// [`MaddBytes`]: ../intrin/madd_bytes/trait.MaddBytes.html
+pub fn rgba_to_gray(src: Plane, dst: &mut PlaneMut) {
+    assert!(src.width() % 4 == 0, "an RGBA image must be a multiple of 4 bytes wide");
+    check_size(dst, src.width() / 4, src.height());
+    let width = u8s::WIDTH;
+    let mut weights = i8s(0);
+    for i in (0..width).step_by(4) {
+        weights = weights.replace(i, RED as i8).replace(i + 1, GREEN as i8).replace(i + 2, BLUE as i8);
+    }
+
+    for y in 0..src.height() {
+        let row = src.row(y);
+        let out = dst.row_mut(y);
+        let mut x = 0;
+        while x + width <= out.len() {
+            // Each pair of bytes is summed into an i16, and each pixel into a
+            // u32, so four vectors of pixels give one vector of bytes
+            let mut sums = [u32s(0); 4];
+            for (i, sum) in sums.iter_mut().enumerate() {
+                let pairs = u8s::load(row, 4 * x + i * width).madd_bytes(weights).be_u32s();
+                *sum = ((pairs & u32s(0xFFFF)) + (pairs >> 16) + u32s(64)) >> 7;
+            }
+            let lo: u16s = sums[0].saturating_downcast(sums[1]);
+            let hi: u16s = sums[2].saturating_downcast(sums[3]);
+            let gray: u8s = lo.saturating_downcast(hi);
+            gray.store(out, x);
+            x += width;
+        }
+        for (x, p) in out.iter_mut().enumerate().skip(x) {
+            *p = gray(row[4 * x], row[4 * x + 1], row[4 * x + 2]);
+        }
+    }
+}
+
+/// Write each pixel of an RGBA image to `dst`, with its color channels
+/// multiplied by its alpha. Channels are rounded to the nearest integer.
+///
+/// # Panics
+///
+/// This function will panic if `src` and `dst` have different dimensions, or
+/// if they aren't a multiple of 4 bytes wide.
+///
+/// ```
+/// extern crate faster;
+/// use faster::image::{premultiply_alpha, Plane, PlaneMut};
+///
+/// # fn main() {
+/// let rgba = [200, 100, 0, 128, 10, 20, 30, 255];
+/// let mut out = [0; 8];
+/// premultiply_alpha(Plane::new(&rgba, 8, 1, 8), &mut PlaneMut::new(&mut out, 8, 1, 8));
+/// assert_eq!(out, [100, 50, 0, 128, 10, 20, 30, 255]);
+/// # }
+/// ```
+pub fn premultiply_alpha(src: Plane, dst: &mut PlaneMut) {
+    assert!(src.width() % 4 == 0, "an RGBA image must be a multiple of 4 bytes wide");
+    check_size(dst, src.width(), src.height());
+    for y in 0..src.height() {
+        src.row(y).stride_four(tuplify!(4, u8s(0))).zip()
+            .simd_map_tuple(|(r, g, b, a)| (mul_div255(r, a), mul_div255(g, a), mul_div255(b, a), a))
+            .interleave_fill(dst.row_mut(y));
+    }
+}
+
+/// Write the composite of the premultiplied RGBA image `fg` over the
+/// premultiplied RGBA image `bg` to `dst`. Each channel of the result is the
+/// channel of `fg`, plus the channel of `bg` scaled by the transparency of
+/// `fg`.
+///
+/// # Panics
+///
+/// This function will panic if the images have different dimensions, or if
+/// they aren't a multiple of 4 bytes wide.
+///
+/// ```
+/// extern crate faster;
+/// use faster::image::{blend_over, Plane, PlaneMut};
+///
+/// # fn main() {
+/// let fg = [100, 0, 0, 128, 0, 0, 0, 0];
+/// let bg = [0, 0, 200, 255, 0, 0, 200, 255];
+/// let mut out = [0; 8];
+/// blend_over(Plane::new(&fg, 8, 1, 8), Plane::new(&bg, 8, 1, 8),
+///            &mut PlaneMut::new(&mut out, 8, 1, 8));
+/// assert_eq!(out, [100, 0, 100, 255, 0, 0, 200, 255]);
+/// # }
+/// ```
+pub fn blend_over(fg: Plane, bg: Plane, dst: &mut PlaneMut) {
+    assert!(fg.width() % 4 == 0, "an RGBA image must be a multiple of 4 bytes wide");
+    assert!(fg.width() == bg.width() && fg.height() == bg.height(),
+            "can't blend a {}x{} image over a {}x{} one", fg.width(), fg.height(), bg.width(), bg.height());
+    check_size(dst, fg.width(), fg.height());
+    let over = |f: u8s, b: u8s, t: u8s| f.saturating_add(mul_div255(b, t));
+    for y in 0..fg.height() {
+        let (front, back) = (fg.row(y), bg.row(y));
+        let (r0, g0, b0, a0) = front.stride_four(tuplify!(4, u8s(0)));
+        let (r1, g1, b1, a1) = back.stride_four(tuplify!(4, u8s(0)));
+        (r0, g0, b0, a0, r1, g1, b1, a1).zip()
+            .simd_map_tuple(|(r0, g0, b0, a0, r1, g1, b1, a1)| {
+                let t = u8s(255) - a0;
+                (over(r0, r1, t), over(g0, g1, t), over(b0, b1, t), over(a0, a1, t))
+            })
+            .interleave_fill(dst.row_mut(y));
+    }
+}
+
+// Add each byte of `add` to the sum at the same index, and subtract each
+// byte of `sub` from it.
+#[cfg(feature = "std")]
+#[inline(always)]
+fn accumulate(sums: &mut [u16], add: &[u8], sub: &[u8]) {
+    let width = u8s::WIDTH;
+    let half = u16s::WIDTH;
+    let mut x = 0;
+    while x + width <= sums.len() {
+        let (add0, add1): (u16s, u16s) = u8s::load(add, x).upcast();
+        let (sub0, sub1): (u16s, u16s) = u8s::load(sub, x).upcast();
+        // A full sum goes past a u16 before the row leaving it is taken
+        // away, so this wraps like the tail does
+        u16s::load(sums, x).wrapping_add_lanes(add0).wrapping_sub_lanes(sub0).store(sums, x);
+        u16s::load(sums, x + half).wrapping_add_lanes(add1).wrapping_sub_lanes(sub1).store(sums, x + half);
+        x += width;
+    }
+    for ((sum, &a), &s) in sums[x..].iter_mut().zip(&add[x..]).zip(&sub[x..]) {
+        *sum = sum.wrapping_add(u16::from(a)).wrapping_sub(u16::from(s));
+    }
+}
+
+/// Write the average of the square of `2 * radius + 1` pixels around each
+/// pixel of `src` to `dst`, rounded to the nearest integer. Pixels past the
+/// edges of `src` repeat the nearest edge.
+///
+/// Each output is computed from running sums of the columns of the square,
+/// so the cost per pixel doesn't depend on the radius.
+///
+/// # Panics
+///
+/// This function will panic if `src` and `dst` have different dimensions,
+/// or if `radius` is greater than 128.
+///
+/// ```
+/// extern crate faster;
+/// use faster::image::{box_blur, Plane, PlaneMut};
+///
+/// # #[cfg(not(feature = "std"))]
+/// # fn main() { }
+///
+/// # #[cfg(feature = "std")]
+/// # fn main() {
+/// let src = [0, 0, 90, 0, 0];
+/// let mut dst = [0; 5];
+/// box_blur(Plane::new(&src, 5, 1, 5), &mut PlaneMut::new(&mut dst, 5, 1, 5), 1);
+/// assert_eq!(dst, [0, 30, 30, 30, 0]);
+/// # }
+/// ```
+#[cfg(feature = "std")]
+pub fn box_blur(src: Plane, dst: &mut PlaneMut, radius: usize) {
+    assert!(radius <= 128, "the radius of a box blur can be at most 128");
+    check_size(dst, src.width(), src.height());
+    let (width, height) = (src.width(), src.height());
+    if width == 0 || height == 0 {
+        return;
+    }
+
+    let r = radius as isize;
+    let area = ((2 * radius + 1) * (2 * radius + 1)) as u32;
+    let zeroes = vec![0u8; width];
+    // The sums of the column of the square above and below each pixel of
+    // the current row, which fit in a u16 as there are at most 257 rows
+    let mut sums = vec![0u16; width];
+    for i in -r..=r {
+        accumulate(&mut sums, src.row(clamp(i, height)), &zeroes);
+    }
+
+    for y in 0..height {
+        let out = dst.row_mut(y);
+        let mut sum = (-r..=r).map(|i| u32::from(sums[clamp(i, width)])).sum::<u32>();
+        for (x, p) in out.iter_mut().enumerate() {
+            *p = ((sum + area / 2) / area) as u8;
+            let x = x as isize;
+            sum = sum + u32::from(sums[clamp(x + r + 1, width)]) - u32::from(sums[clamp(x - r, width)]);
+        }
+        let y = y as isize;
+        accumulate(&mut sums, src.row(clamp(y + r + 1, height)), src.row(clamp(y - r, height)));
+    }
+}
+
+/// Write the sum of the 3x3 pixels around each pixel of `src`, weighted by
+/// `kernel` and shifted right by `shift` with rounding, to `dst`. Results
+/// are clamped to the range of a byte, and pixels past the edges of `src`
+/// repeat the nearest edge.
+///
+/// The kernel is in row-major order and isn't flipped, so `kernel[0]` weighs
+/// the pixel above and to the left. The sums are computed in 16 bits, so the
+/// absolute values of the weights must add up to at most 128.
+///
+/// # Panics
+///
+/// This function will panic if `src` and `dst` have different dimensions,
+/// if the weights are too large, or if `shift` is greater than 7.
+///
+/// ```
+/// extern crate faster;
+/// use faster::image::{convolve_3x3, Plane, PlaneMut};
+///
+/// # fn main() {
+/// let src = [10, 10, 10, 50, 50, 50];
+/// let mut dst = [0; 6];
+/// // A horizontal Sobel filter
+/// let sobel = [-1, 0, 1, -2, 0, 2, -1, 0, 1];
+/// convolve_3x3(Plane::new(&src, 6, 1, 6), &mut PlaneMut::new(&mut dst, 6, 1, 6), &sobel, 2);
+/// assert_eq!(dst, [0, 0, 40, 40, 0, 0]);
+/// # }
+/// ```
+pub fn convolve_3x3(src: Plane, dst: &mut PlaneMut, kernel: &[i16; 9], shift: u32) {
+    assert!(kernel.iter().map(|&k| i32::from(k).abs()).sum::<i32>() <= 128,
+            "the weights of a 3x3 kernel can add up to at most 128");
+    assert!(shift < 8, "a 3x3 convolution can be shifted by at most 7");
+    check_size(dst, src.width(), src.height());
+    let (width, height) = (src.width(), src.height());
+    if width == 0 {
+        return;
+    }
+
+    let round = (1i16 << shift) >> 1;
+    let vwidth = u8s::WIDTH;
+    for y in 0..height {
+        let y = y as isize;
+        let rows = [src.row(clamp(y - 1, height)), src.row(clamp(y, height)), src.row(clamp(y + 1, height))];
+        let at = |x: usize| {
+            let mut sum = i32::from(round);
+            for (dy, row) in rows.iter().enumerate() {
+                for dx in 0..3 {
+                    let p = row[clamp(x as isize + dx as isize - 1, width)];
+                    sum += i32::from(kernel[dy * 3 + dx]) * i32::from(p);
+                }
+            }
+            (sum >> shift).max(0).min(255) as u8
+        };
+
+        let out = dst.row_mut(y as usize);
+        out[0] = at(0);
+        // Vectors of outputs whose neighbors are all within the row
+        let mut x = 1;
+        while x + vwidth < width {
+            let (mut lo, mut hi) = (i16s(round), i16s(round));
+            for (dy, row) in rows.iter().enumerate() {
+                for dx in 0..3 {
+                    let k = i16s(kernel[dy * 3 + dx]);
+                    let (p0, p1): (u16s, u16s) = u8s::load(row, x + dx - 1).upcast();
+                    lo = lo + k * p0.be_i16s();
+                    hi = hi + k * p1.be_i16s();
+                }
+            }
+            let lo = Cmp::max(&(lo >> shift), i16s(0)).be_u16s();
+            let hi = Cmp::max(&(hi >> shift), i16s(0)).be_u16s();
+            let bytes: u8s = lo.saturating_downcast(hi);
+            bytes.store(out, x);
+            x += vwidth;
+        }
+        for (x, p) in out.iter_mut().enumerate().skip(x) {
+            *p = at(x);
+        }
+    }
+}
+
+/// Write the average of each 2x2 square of pixels of `src`, rounded to the
+/// nearest integer, to `dst`. If `src` has an odd width or height, its last
+/// column or row is ignored.
+///
+/// # Panics
+///
+/// This function will panic if `dst` isn't half as wide and as high as
+/// `src`, rounded down.
+///
+/// ```
+/// extern crate faster;
+/// use faster::image::{downscale_2x, Plane, PlaneMut};
+///
+/// # fn main() {
+/// let src = [0, 2, 10, 20, 99,
+///            4, 2, 30, 40, 99];
+/// let mut dst = [0; 2];
+/// downscale_2x(Plane::new(&src, 5, 2, 5), &mut PlaneMut::new(&mut dst, 2, 1, 2));
+/// assert_eq!(dst, [2, 25]);
+/// # }
+/// ```
+pub fn downscale_2x(src: Plane, dst: &mut PlaneMut) {
+    check_size(dst, src.width() / 2, src.height() / 2);
+    let width = u8s::WIDTH;
+    let ones = i8s(1);
+    for y in 0..dst.height() {
+        let (top, bottom) = (src.row(2 * y), src.row(2 * y + 1));
+        let out = dst.row_mut(y);
+        let mut x = 0;
+        while x + width <= out.len() {
+            // Pairs of horizontal neighbors are summed into 16-bit lanes
+            let lo = (u8s::load(top, 2 * x).madd_bytes(ones)
+                      + u8s::load(bottom, 2 * x).madd_bytes(ones) + i16s(2)) >> 2;
+            let hi = (u8s::load(top, 2 * x + width).madd_bytes(ones)
+                      + u8s::load(bottom, 2 * x + width).madd_bytes(ones) + i16s(2)) >> 2;
+            let bytes: u8s = lo.be_u16s().saturating_downcast(hi.be_u16s());
+            bytes.store(out, x);
+            x += width;
+        }
+        for (x, p) in out.iter_mut().enumerate().skip(x) {
+            let sum = u16::from(top[2 * x]) + u16::from(top[2 * x + 1])
+                + u16::from(bottom[2 * x]) + u16::from(bottom[2 * x + 1]);
+            *p = ((sum + 2) >> 2) as u8;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::rng;
+
+    // Return a buffer holding a `width` by `height` image with a few bytes of
+    // padding after each row, which tests will check aren't written
+    fn image(seed: &mut u64, width: usize, height: usize) -> (Vec<u8>, usize) {
+        let stride = width + 3;
+        ((0..stride * height).map(|_| rng(seed) as u8).collect(), stride)
+    }
+
+    // The sizes of images to test, in pixels
+    fn sizes() -> Vec<(usize, usize)> {
+        vec![(0, 0), (1, 1), (3, 2), (15, 3), (16, 1), (33, 4), (64, 2), (70, 5), (130, 3)]
+    }
+
+    // Run `f` on a destination of the given dimensions, and check that it
+    // wrote `expected` without touching the padding between rows
+    fn check<F>(width: usize, height: usize, expected: &[u8], f: F) where F : FnOnce(&mut PlaneMut) {
+        let stride = width + 5;
+        let mut out = vec![0xAA; stride * height];
+        f(&mut PlaneMut::new(&mut out, width, height, stride));
+        for y in 0..height {
+            assert_eq!(&out[y * stride..y * stride + width], &expected[y * width..(y + 1) * width]);
+            assert!(out[y * stride + width..(y + 1) * stride].iter().all(|&b| b == 0xAA));
+        }
+    }
+
+    #[test]
+    fn gray_matches_scalar() {
+        let mut seed = 0x9E3779B97F4A7C15;
+        for (w, h) in sizes() {
+            let (rgb, stride) = image(&mut seed, w * 3, h);
+            let src = Plane::new(&rgb, w * 3, h, stride);
+            let expected = (0..h).flat_map(|y| src.row(y).chunks(3).map(|p| gray(p[0], p[1], p[2])).collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            check(w, h, &expected, |dst| rgb_to_gray(src, dst));
+
+            let (rgba, stride) = image(&mut seed, w * 4, h);
+            let src = Plane::new(&rgba, w * 4, h, stride);
+            let expected = (0..h).flat_map(|y| src.row(y).chunks(4).map(|p| gray(p[0], p[1], p[2])).collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            check(w, h, &expected, |dst| rgba_to_gray(src, dst));
+        }
+        assert_eq!(gray(255, 255, 255), 255);
+    }
+
+    #[test]
+    fn alpha_matches_scalar() {
+        let mut seed = 0xDA942042E4DD58B5;
+        for (w, h) in sizes() {
+            let (rgba, stride) = image(&mut seed, w * 4, h);
+            let src = Plane::new(&rgba, w * 4, h, stride);
+            let expected = (0..h).flat_map(|y| src.row(y).chunks(4).flat_map(|p| {
+                let a = u32::from(p[3]);
+                vec![div255(u32::from(p[0]) * a), div255(u32::from(p[1]) * a), div255(u32::from(p[2]) * a), p[3]]
+            }).collect::<Vec<_>>()).collect::<Vec<_>>();
+            check(w * 4, h, &expected, |dst| premultiply_alpha(src, dst));
+
+            let (bg, stride) = image(&mut seed, w * 4, h);
+            let back = Plane::new(&bg, w * 4, h, stride);
+            let expected = (0..h).flat_map(|y| {
+                src.row(y).chunks(4).zip(back.row(y).chunks(4)).flat_map(|(f, b)| {
+                    let t = 255 - u32::from(f[3]);
+                    (0..4).map(|c| f[c].saturating_add(div255(u32::from(b[c]) * t))).collect::<Vec<_>>()
+                }).collect::<Vec<_>>()
+            }).collect::<Vec<_>>();
+            check(w * 4, h, &expected, |dst| blend_over(src, back, dst));
+        }
+        for x in 0..=255 * 255 {
+            assert_eq!(u32::from(div255(x)), (x + 127) / 255);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn box_blur_matches_scalar() {
+        let mut seed = 0x2545F4914F6CDD1D;
+        for (w, h) in sizes() {
+            let (data, stride) = image(&mut seed, w, h);
+            let src = Plane::new(&data, w, h, stride);
+            for &radius in &[0, 1, 2, 7, 128] {
+                let r = radius as isize;
+                let area = ((2 * radius + 1) * (2 * radius + 1)) as u32;
+                let expected = (0..h * w).map(|i| {
+                    let (y, x) = ((i / w) as isize, (i % w) as isize);
+                    let sum = (-r..=r).flat_map(|dy| (-r..=r).map(move |dx| (dy, dx)))
+                        .map(|(dy, dx)| u32::from(src.row(clamp(y + dy, h))[clamp(x + dx, w)]))
+                        .sum::<u32>();
+                    ((sum + area / 2) / area) as u8
+                }).collect::<Vec<_>>();
+                check(w, h, &expected, |dst| box_blur(src, dst, radius));
+            }
+
+            // Each column sum of a white image is 257 * 255, the largest
+            // u16, so adding the next row overflows before the last one is
+            // taken away
+            let white = vec![255; w * h];
+            check(w, h, &white, |dst| box_blur(Plane::new(&white, w, h, w), dst, 128));
+        }
+    }
+
+    #[test]
+    fn convolve_3x3_matches_scalar() {
+        let mut seed = 0x853C49E6748FEA9B;
+        let kernels = [([1, 2, 1, 2, 4, 2, 1, 2, 1], 4),
+                       ([-1, 0, 1, -2, 0, 2, -1, 0, 1], 0),
+                       ([0, -1, 0, -1, 5, -1, 0, -1, 0], 0),
+                       ([-16, -16, -16, -16, 0, -16, -16, -16, 0], 7)];
+        for (w, h) in sizes() {
+            let (data, stride) = image(&mut seed, w, h);
+            let src = Plane::new(&data, w, h, stride);
+            for &(kernel, shift) in &kernels {
+                let round = (1i32 << shift) >> 1;
+                let expected = (0..h * w).map(|i| {
+                    let (y, x) = ((i / w) as isize, (i % w) as isize);
+                    let sum = (0..9).map(|k| {
+                        let (dy, dx) = (k as isize / 3 - 1, k as isize % 3 - 1);
+                        i32::from(kernel[k]) * i32::from(src.row(clamp(y + dy, h))[clamp(x + dx, w)])
+                    }).sum::<i32>();
+                    ((sum + round) >> shift).max(0).min(255) as u8
+                }).collect::<Vec<_>>();
+                check(w, h, &expected, |dst| convolve_3x3(src, dst, &kernel, shift));
+            }
+        }
+    }
+
+    #[test]
+    fn downscale_2x_matches_scalar() {
+        let mut seed = 0x9E3779B97F4A7C15;
+        for (w, h) in sizes() {
+            let (data, stride) = image(&mut seed, w, h);
+            let src = Plane::new(&data, w, h, stride);
+            let expected = (0..h / 2).flat_map(|y| (0..w / 2).map(move |x| (y, x))).map(|(y, x)| {
+                let sum = u32::from(src.row(2 * y)[2 * x]) + u32::from(src.row(2 * y)[2 * x + 1])
+                    + u32::from(src.row(2 * y + 1)[2 * x]) + u32::from(src.row(2 * y + 1)[2 * x + 1]);
+                ((sum + 2) / 4) as u8
+            }).collect::<Vec<_>>();
+            check(w / 2, h / 2, &expected, |dst| downscale_2x(src, dst));
+        }
+    }
+}
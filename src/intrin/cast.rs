@@ -75,15 +75,19 @@ pub trait Asu64s {
     fn as_u64s(self) -> Self::Cast;
 }
 
-// macro_rules! impl_cast {
-//     ($trait:path, $from:ty, $to:ty, $name:ident, $rsname:ident) => (
-//         impl $trait for $from {
-//             type Cast = $to;
-
-//             #[inline(always)]
-//             fn $name(self) -> Self::Cast {
-//                 self.$rsname()
-//             }
-//         }
-//     );
-// }
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn int_float_casts() {
+        assert_eq!(i32s(-3).as_f32s(), f32s(-3.0));
+        assert_eq!(u32s(0x1_0000).as_f32s(), f32s(65536.0));
+        assert_eq!(f32s(-2.75).as_i32s(), i32s(-2));
+        assert_eq!(f32s(2.75).as_u32s(), u32s(2));
+        assert_eq!(i64s(-5).as_f64s(), f64s(-5.0));
+        assert_eq!(f64s(1e10).as_i64s(), i64s(10_000_000_000));
+        assert_eq!(i8s(-1).as_u8s(), u8s(0xFF));
+        assert_eq!(u16s(0x8000).as_i16s(), i16s(-0x8000));
+    }
+}
@@ -0,0 +1,250 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::vecs::*;
+
+/// Classification and sign manipulation of floating-point vectors.
+///
+/// The classification functions are named after their scalar counterparts,
+/// but return masks in the style of [`Eq::eq_mask`], as the vector types of
+/// some backends already define inherent `is_nan` and `is_finite` functions.
+///
+/// [`Eq::eq_mask`]: ../eq/trait.Eq.html#tymethod.eq_mask
+pub trait FloatOps : Packed {
+    type Out : Pattern;
+
+    /// Return a vector where each element at an index i is filled with 1s if
+    /// the element of `self` at index i is NaN, and filled with zeroes
+    /// otherwise.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// assert_eq!(f32s(::std::f32::NAN).nan_mask(), u32s(0xFFFFFFFF));
+    /// assert_eq!(f32s(1.0).nan_mask(), u32s(0));
+    /// # }
+    /// ```
+    fn nan_mask(&self) -> Self::Out;
+
+    /// Return a vector where each element at an index i is filled with 1s if
+    /// the element of `self` at index i is positive or negative infinity, and
+    /// filled with zeroes otherwise.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// assert_eq!(f32s(::std::f32::NEG_INFINITY).infinite_mask(), u32s(0xFFFFFFFF));
+    /// assert_eq!(f32s(::std::f32::MAX).infinite_mask(), u32s(0));
+    /// # }
+    /// ```
+    fn infinite_mask(&self) -> Self::Out;
+
+    /// Return a vector where each element at an index i is filled with 1s if
+    /// the element of `self` at index i is neither infinite nor NaN, and
+    /// filled with zeroes otherwise.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// assert_eq!(f32s(-2.0).finite_mask(), u32s(0xFFFFFFFF));
+    /// assert_eq!(f32s(::std::f32::NAN).finite_mask(), u32s(0));
+    /// # }
+    /// ```
+    fn finite_mask(&self) -> Self::Out;
+
+    /// Return a vector where each element at an index i is filled with 1s if
+    /// the sign bit of the element of `self` at index i is set, and filled
+    /// with zeroes otherwise. Negative zero and NaNs with their sign bit set
+    /// are considered negative.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// assert_eq!(f32s(-0.0).sign_negative_mask(), u32s(0xFFFFFFFF));
+    /// assert_eq!(f32s(0.0).sign_negative_mask(), u32s(0));
+    /// # }
+    /// ```
+    fn sign_negative_mask(&self) -> Self::Out;
+
+    /// Return a vector containing the magnitudes of the elements of `self`
+    /// and the signs of the elements of `sign`.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// assert_eq!(f32s(3.0).copysign(f32s(-0.0)), f32s(-3.0));
+    /// assert_eq!(f32s(-3.0).copysign(f32s(1.0)), f32s(3.0));
+    /// # }
+    /// ```
+    fn copysign(&self, sign: Self) -> Self;
+
+    /// Return a vector containing 1.0 for every positive element of `self`,
+    /// -1.0 for every negative element of `self`, and NaN for every NaN
+    /// element of `self`. Zeroes are treated as having the sign of their sign
+    /// bit.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// assert_eq!(f32s(-4.5).signum(), f32s(-1.0));
+    /// assert_eq!(f32s(0.0).signum(), f32s(1.0));
+    /// # }
+    /// ```
+    fn signum(&self) -> Self;
+
+    /// Return a vector containing the negated absolute value of the elements
+    /// of `self`.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// assert_eq!(f32s(2.0).neg_abs(), f32s(-2.0));
+    /// assert_eq!(f32s(-2.0).neg_abs(), f32s(-2.0));
+    /// # }
+    /// ```
+    fn neg_abs(&self) -> Self;
+
+    /// Return a vector where each element at an index i is the element of
+    /// `self` at index i restricted to the interval given by the elements of
+    /// `min` and `max` at index i. NaN elements of `self` are preserved.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// assert_eq!(f32s(5.0).clamp(f32s(-1.0), f32s(1.0)), f32s(1.0));
+    /// assert_eq!(f32s(-5.0).clamp(f32s(-1.0), f32s(1.0)), f32s(-1.0));
+    /// assert_eq!(f32s(0.5).clamp(f32s(-1.0), f32s(1.0)), f32s(0.5));
+    /// # }
+    /// ```
+    fn clamp(&self, min: Self, max: Self) -> Self;
+}
+
+macro_rules! impl_packed_float_ops {
+    ($($vec:tt, $uvec:tt, $ivec:tt, $ubits:ident, $ibits:ident, $fbits:ident, $shift:expr, $sign:expr, $exp:expr, $one:expr);*) => (
+        $(
+            impl FloatOps for $vec {
+                type Out = $uvec;
+
+                #[inline(always)]
+                fn nan_mask(&self) -> Self::Out {
+                    // NaN is the only value which does not equal itself
+                    self.ne_mask(*self)
+                }
+
+                #[inline(always)]
+                fn infinite_mask(&self) -> Self::Out {
+                    (self.$ubits() & $uvec::splat(!$sign)).eq_mask($uvec::splat($exp))
+                }
+
+                #[inline(always)]
+                fn finite_mask(&self) -> Self::Out {
+                    (self.$ubits() & $uvec::splat($exp)).ne_mask($uvec::splat($exp))
+                }
+
+                #[inline(always)]
+                fn sign_negative_mask(&self) -> Self::Out {
+                    // Smear the sign bit across the lane
+                    (self.$ibits() >> $ivec::splat($shift)).$ubits()
+                }
+
+                #[inline(always)]
+                fn copysign(&self, sign: Self) -> Self {
+                    let bits = (self.$ubits() & $uvec::splat(!$sign))
+                        | (sign.$ubits() & $uvec::splat($sign));
+                    unsafe { bits.$fbits() }
+                }
+
+                #[inline(always)]
+                fn signum(&self) -> Self {
+                    let nan = self.nan_mask();
+                    let ones = (self.$ubits() & $uvec::splat($sign)) | $uvec::splat($one);
+                    let bits = (ones & (nan ^ $uvec::ones())) | (self.$ubits() & nan);
+                    unsafe { bits.$fbits() }
+                }
+
+                #[inline(always)]
+                fn neg_abs(&self) -> Self {
+                    unsafe { (self.$ubits() | $uvec::splat($sign)).$fbits() }
+                }
+
+                #[inline(always)]
+                fn clamp(&self, min: Self, max: Self) -> Self {
+                    let nan = self.nan_mask();
+                    let clamped = self.max(min).min(max).$ubits();
+                    let bits = (clamped & (nan ^ $uvec::ones())) | (self.$ubits() & nan);
+                    unsafe { bits.$fbits() }
+                }
+            }
+        )*
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    macro_rules! test_float_ops {
+        ($vec:tt, $el:tt, $uvec:tt, $name:ident) => {
+            #[test]
+            fn $name() {
+                use crate::core::$el::*;
+                let vals = [0.0, -0.0, 1.0, -1.5, 1234.5678, -0.001, MAX, MIN,
+                            MIN_POSITIVE, MIN_POSITIVE / 4.0, INFINITY,
+                            NEG_INFINITY, NAN];
+
+                let mask = |b: bool| if b { $uvec::ones() } else { $uvec::zeroes() };
+                let same = |a: $el, b: $el| (a.is_nan() && b.is_nan()) || a.to_bits() == b.to_bits();
+
+                for &a in vals.iter() {
+                    let v = $vec(a);
+                    assert_eq!(v.nan_mask(), mask(a.is_nan()));
+                    assert_eq!(v.infinite_mask(), mask(a.is_infinite()));
+                    assert_eq!(v.finite_mask(), mask(a.is_finite()));
+                    assert_eq!(v.sign_negative_mask(), mask(a.is_sign_negative()));
+                    assert!(same(v.signum().extract(0), a.signum()));
+                    assert!(same(v.neg_abs().extract(0), -a.abs()));
+                    assert!(same(v.clamp($vec(-2.0), $vec(2.0)).extract(0),
+                                 if a.is_nan() { a } else { a.max(-2.0).min(2.0) }));
+
+                    for &b in vals.iter() {
+                        assert!(same(v.copysign($vec(b)).extract(0),
+                                     if b.is_sign_negative() { -a.abs() } else { a.abs() }));
+                    }
+                }
+            }
+        }
+    }
+
+    test_float_ops!(f32s, f32, u32s, float_ops_f32s);
+    test_float_ops!(f64s, f64, u64s, float_ops_f64s);
+
+    #[test]
+    fn float_ops_lanes_independent() {
+        let v = f32s::interleave(crate::core::f32::NAN, -3.0);
+        assert_eq!(v.nan_mask(), u32s::interleave(0xFFFFFFFF, 0));
+        assert_eq!(v.sign_negative_mask(), u32s::interleave(0, 0xFFFFFFFF));
+        assert_eq!(v.neg_abs().extract(1), -3.0);
+        assert!(v.clamp(f32s(-1.0), f32s(1.0)).extract(0).is_nan());
+        assert_eq!(v.clamp(f32s(-1.0), f32s(1.0)).extract(1), -1.0);
+    }
+}
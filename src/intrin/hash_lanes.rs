@@ -0,0 +1,287 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::vecs::*;
+use crate::intrin::wrapping::WrappingLanes;
+
+/// Hash functions which hash each element of a vector on its own. The hash
+/// of an element depends only on its value and the parameters, so the
+/// results are the same for any vector width.
+pub trait HashLanes : Packed {
+    /// Return a vector containing the top `bits` bits of the wrapping
+    /// product of each element of `self` and `multiplier`, which should be
+    /// odd and random. This is a universal hash into `2^bits` buckets.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `bits` is zero or larger than the number
+    /// of bits in an element.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// assert_eq!(u32s(3).hash_multiply_shift(0x9E37_79B1, 8), u32s(0xDA));
+    /// # }
+    /// ```
+    fn hash_multiply_shift(&self, multiplier: Self::Scalar, bits: u32) -> Self;
+
+    /// Return a vector containing the MurmurHash3 finalizer of each element
+    /// of `self`; `fmix32` for 32-bit elements, and `fmix64` for 64-bit.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// assert_eq!(u32s(0).hash_murmur3(), u32s(0));
+    /// assert_eq!(u32s(1).hash_murmur3(), u32s(0x514E_28B7));
+    /// # }
+    /// ```
+    fn hash_murmur3(&self) -> Self;
+
+    /// Return a vector containing the xxHash of each element of `self` with
+    /// the given seed, as if the element were hashed as a little-endian
+    /// byte string; XXH32 for 32-bit elements, and XXH64 for 64-bit.
+    fn hash_xxh(&self, seed: Self::Scalar) -> Self;
+}
+
+// Wrapping arithmetic on a word, or a vector of words of type `T`, so each
+// hash function is only written once
+pub(crate) trait HashWord<T> : WrappingLanes {
+    fn wrapping_mul_word(self, x: T) -> Self;
+    fn wrapping_add_word(self, x: T) -> Self;
+    fn xor_lanes(self, other: Self) -> Self;
+    fn shr_lanes(self, n: u32) -> Self;
+    fn rotl_lanes(self, n: u32) -> Self;
+
+    #[inline(always)]
+    fn xor_shr(self, n: u32) -> Self {
+        self.xor_lanes(self.shr_lanes(n))
+    }
+}
+
+macro_rules! impl_hash_word {
+    (word $($el:tt),*) => (
+        $(
+            impl HashWord<$el> for $el {
+                #[inline(always)]
+                fn wrapping_mul_word(self, x: $el) -> Self { self.wrapping_mul(x) }
+                #[inline(always)]
+                fn wrapping_add_word(self, x: $el) -> Self { self.wrapping_add(x) }
+                #[inline(always)]
+                fn xor_lanes(self, other: Self) -> Self { self ^ other }
+                #[inline(always)]
+                fn shr_lanes(self, n: u32) -> Self { self >> n }
+                #[inline(always)]
+                fn rotl_lanes(self, n: u32) -> Self { self.rotate_left(n) }
+            }
+        )*
+    );
+    (vector $($vec:tt, $el:tt);*) => (
+        $(
+            impl HashWord<$el> for $vec {
+                #[inline(always)]
+                fn wrapping_mul_word(self, x: $el) -> Self { self * $vec::splat(x) }
+                #[inline(always)]
+                fn wrapping_add_word(self, x: $el) -> Self { self + $vec::splat(x) }
+                #[inline(always)]
+                fn xor_lanes(self, other: Self) -> Self { self ^ other }
+                #[inline(always)]
+                fn shr_lanes(self, n: u32) -> Self { self >> $vec::splat(n as $el) }
+                #[inline(always)]
+                fn rotl_lanes(self, n: u32) -> Self {
+                    let bits = (<$el>::min_value().count_zeros()) as $el;
+                    (self << $vec::splat(n as $el)) | (self >> $vec::splat(bits - n as $el))
+                }
+            }
+        )*
+    );
+    (lanes $($vec:tt, $el:tt);*) => (
+        $(
+            // The vectors of this backend panic on overflow, so work on each
+            // lane in turn
+            impl HashWord<$el> for $vec {
+                #[inline(always)]
+                fn wrapping_mul_word(self, x: $el) -> Self { self.map_lanes(|v| v.wrapping_mul(x)) }
+                #[inline(always)]
+                fn wrapping_add_word(self, x: $el) -> Self { self.map_lanes(|v| v.wrapping_add(x)) }
+                #[inline(always)]
+                fn xor_lanes(self, other: Self) -> Self { self ^ other }
+                #[inline(always)]
+                fn shr_lanes(self, n: u32) -> Self { self.map_lanes(|v| v >> n) }
+                #[inline(always)]
+                fn rotl_lanes(self, n: u32) -> Self { self.map_lanes(|v| v.rotate_left(n)) }
+            }
+
+            impl MapLanes<$el> for $vec {
+                #[inline(always)]
+                fn map_lanes<F: Fn($el) -> $el>(self, f: F) -> Self {
+                    let mut ret = self;
+                    for i in 0..Self::WIDTH {
+                        ret = ret.replace(i, f(self.extract(i)));
+                    }
+                    ret
+                }
+            }
+        )*
+    );
+}
+
+pub(crate) trait MapLanes<T> {
+    fn map_lanes<F: Fn(T) -> T>(self, f: F) -> Self;
+}
+
+impl_hash_word!(word u32, u64);
+
+const MURMUR3_32: [u32; 2] = [0x85EB_CA6B, 0xC2B2_AE35];
+const MURMUR3_64: [u64; 2] = [0xFF51_AFD7_ED55_8CCD, 0xC4CE_B9FE_1A85_EC53];
+const XXH_PRIME32: [u32; 5] = [0x9E37_79B1, 0x85EB_CA77, 0xC2B2_AE3D, 0x27D4_EB2F, 0x1656_67B1];
+const XXH_PRIME64: [u64; 5] = [0x9E37_79B1_85EB_CA87, 0xC2B2_AE3D_27D4_EB4F,
+                               0x1656_67B1_9E37_79F9, 0x85EB_CA77_C2B2_AE63,
+                               0x27D4_EB2F_1656_67C5];
+
+#[inline(always)]
+pub(crate) fn fmix32<W: HashWord<u32>>(h: W) -> W {
+    h.xor_shr(16)
+        .wrapping_mul_word(MURMUR3_32[0]).xor_shr(13)
+        .wrapping_mul_word(MURMUR3_32[1]).xor_shr(16)
+}
+
+#[inline(always)]
+pub(crate) fn fmix64<W: HashWord<u64>>(h: W) -> W {
+    h.xor_shr(33)
+        .wrapping_mul_word(MURMUR3_64[0]).xor_shr(33)
+        .wrapping_mul_word(MURMUR3_64[1]).xor_shr(33)
+}
+
+// XXH32 of the four bytes of each word; `start` holds the seed in every lane
+#[inline(always)]
+pub(crate) fn xxh32<W: HashWord<u32>>(x: W, start: W) -> W {
+    let p = XXH_PRIME32;
+    start.wrapping_add_word(p[4].wrapping_add(4))
+        .wrapping_add_lanes(x.wrapping_mul_word(p[2]))
+        .rotl_lanes(17).wrapping_mul_word(p[3])
+        .xor_shr(15).wrapping_mul_word(p[1])
+        .xor_shr(13).wrapping_mul_word(p[2])
+        .xor_shr(16)
+}
+
+// XXH64 of the eight bytes of each word; `start` holds the seed in every lane
+#[inline(always)]
+pub(crate) fn xxh64<W: HashWord<u64>>(x: W, start: W) -> W {
+    let p = XXH_PRIME64;
+    let k = x.wrapping_mul_word(p[1]).rotl_lanes(31).wrapping_mul_word(p[0]);
+    start.wrapping_add_word(p[4].wrapping_add(8))
+        .xor_lanes(k)
+        .rotl_lanes(27).wrapping_mul_word(p[0]).wrapping_add_word(p[3])
+        .xor_shr(33).wrapping_mul_word(p[1])
+        .xor_shr(29).wrapping_mul_word(p[2])
+        .xor_shr(32)
+}
+
+macro_rules! impl_packed_hash_lanes {
+    ($($vec:tt, $el:tt, $fmix:ident, $xxh:ident);*) => (
+        $(
+            impl HashLanes for $vec {
+                #[inline(always)]
+                fn hash_multiply_shift(&self, multiplier: $el, bits: u32) -> Self {
+                    let width = (<$el>::min_value().count_zeros()) as u32;
+                    assert!(bits > 0 && bits <= width, "bits must be between 1 and {}", width);
+                    (*self).wrapping_mul_word(multiplier).shr_lanes(width - bits)
+                }
+
+                #[inline(always)]
+                fn hash_murmur3(&self) -> Self {
+                    $fmix(*self)
+                }
+
+                #[inline(always)]
+                fn hash_xxh(&self, seed: $el) -> Self {
+                    $xxh(*self, $vec::splat(seed))
+                }
+            }
+        )*
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use super::{fmix32, fmix64, xxh32, xxh64};
+    use crate::test_util::rng;
+
+    #[test]
+    fn hash_lanes_u32s() {
+        let mut seed = 0x9E3779B97F4A7C15;
+        for _ in 0..100 {
+            let mut v = u32s(0);
+            for i in 0..u32s::WIDTH {
+                v = v.replace(i, rng(&mut seed) as u32);
+            }
+            let salt = rng(&mut seed) as u32;
+            let (shifted, murmur, xxh) =
+                (v.hash_multiply_shift(salt | 1, 11), v.hash_murmur3(), v.hash_xxh(salt));
+            for i in 0..u32s::WIDTH {
+                let x = v.extract(i);
+                assert_eq!(shifted.extract(i), x.wrapping_mul(salt | 1) >> 21);
+                assert_eq!(murmur.extract(i), fmix32(x));
+                assert_eq!(xxh.extract(i), xxh32(x, salt));
+            }
+        }
+    }
+
+    #[test]
+    fn hash_lanes_u64s() {
+        let mut seed = 0xDA942042E4DD58B5;
+        for _ in 0..100 {
+            let mut v = u64s(0);
+            for i in 0..u64s::WIDTH {
+                v = v.replace(i, rng(&mut seed));
+            }
+            let salt = rng(&mut seed);
+            let (shifted, murmur, xxh) =
+                (v.hash_multiply_shift(salt | 1, 64), v.hash_murmur3(), v.hash_xxh(salt));
+            for i in 0..u64s::WIDTH {
+                let x = v.extract(i);
+                assert_eq!(shifted.extract(i), x.wrapping_mul(salt | 1));
+                assert_eq!(murmur.extract(i), fmix64(x));
+                assert_eq!(xxh.extract(i), xxh64(x, salt));
+            }
+        }
+    }
+
+    #[test]
+    fn hash_known_values() {
+        assert_eq!(fmix32(1u32), 0x514E_28B7);
+        assert_eq!(fmix64(1u64), 0xB456_BCFC_34C2_CB2C);
+    }
+
+    #[test]
+    fn xxh_known_values() {
+        // From the reference implementation, hashing the little-endian bytes
+        let xxh32_cases = [(0, 0, 0x08D6_D969), (0, 1, 0x77F4_98C8), (0, 0x9E37_79B1, 0x4982_E72F),
+                           (1, 0, 0xF3BB_7693), (1, 1, 0xEA95_1E9A), (1, 0x9E37_79B1, 0xD275_60EC),
+                           (0xDEAD_BEEF, 0, 0xE4AA_E6D1), (0xDEAD_BEEF, 0x9E37_79B1, 0x67D1_1667)];
+        for &(x, seed, hash) in xxh32_cases.iter() {
+            assert_eq!(xxh32(x, seed), hash);
+            assert_eq!(u32s(x).hash_xxh(seed), u32s(hash));
+        }
+
+        let xxh64_cases = [(0, 0, 0x34C9_6ACD_CADB_1BBB), (0, 1, 0x22C7_6AFD_15F0_110F),
+                           (0, 0x9E37_79B1_85EB_CA87, 0x6718_BF88_67D1_F7BF),
+                           (1, 0, 0x9F29_CB17_A2A4_9995), (1, 1, 0x8AFB_45D6_A8B3_9709),
+                           (1, 0x9E37_79B1_85EB_CA87, 0xE1E5_CF53_4DC6_509A),
+                           (0x0123_4567_89AB_CDEF, 0, 0xEA3C_5208_1E98_43EC),
+                           (0x0123_4567_89AB_CDEF, 0x9E37_79B1_85EB_CA87, 0x7641_E066_27F2_379C)];
+        for &(x, seed, hash) in xxh64_cases.iter() {
+            assert_eq!(xxh64(x, seed), hash);
+            assert_eq!(u64s(x).hash_xxh(seed), u64s(hash));
+        }
+    }
+}
@@ -0,0 +1,134 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::vecs::*;
+
+pub trait Lookup : Packed {
+    /// Return a vector where each element at an index i is the element of
+    /// `table` indexed by the low four bits of the element of `self` at
+    /// index i. The high four bits of each element of `self` are ignored.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// let hex = b"0123456789abcdef";
+    /// assert_eq!(u8s(0x3C).lookup_nibbles(hex), u8s(b'c'));
+    /// assert_eq!(u8s(0x05).lookup_nibbles(hex), u8s(b'5'));
+    /// # }
+    /// ```
+    fn lookup_nibbles(&self, table: &[u8; 16]) -> Self;
+
+    /// Return a vector where each element at an index i is the element of
+    /// `self` at the index given by the low four bits of the element of
+    /// `indices` at index i, counted from the start of the group of 16
+    /// elements containing index i. Elements never move between groups.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// let v = u8s::interleave(1, 2);
+    /// assert_eq!(v.shuffle_bytes(u8s(1)), u8s(2));
+    /// assert_eq!(v.shuffle_bytes(u8s::interleave(1, 0)), u8s::interleave(2, 1));
+    /// # }
+    /// ```
+    fn shuffle_bytes(&self, indices: Self) -> Self;
+}
+
+macro_rules! impl_packed_lookup {
+    ($($vec:tt, $feat:expr, $mmfn:tt);*) => (
+        $(
+            impl Lookup for $vec {
+                #[inline(always)]
+                #[cfg(target_feature = $feat)]
+                fn lookup_nibbles(&self, table: &[u8; 16]) -> Self {
+                    optimized!();
+                    // The shuffle only indexes within each 128-bit lane, so
+                    // the table is repeated in every lane.
+                    let mut lanes = [0u8; 64];
+                    for chunk in lanes.chunks_mut(16) {
+                        chunk.copy_from_slice(table);
+                    }
+                    let table = $vec::load(&lanes, 0);
+                    unsafe { $mmfn(table.be_i8s(), (*self & $vec::splat(0x0F)).be_i8s()).be_u8s() }
+                }
+
+                #[inline(always)]
+                #[cfg(not(target_feature = $feat))]
+                fn lookup_nibbles(&self, table: &[u8; 16]) -> Self {
+                    fallback!();
+                    let mut ret = *self;
+                    for i in 0..Self::WIDTH {
+                        ret = ret.replace(i, table[(self.extract(i) & 0x0F) as usize]);
+                    }
+                    ret
+                }
+
+                #[inline(always)]
+                #[cfg(target_feature = $feat)]
+                fn shuffle_bytes(&self, indices: Self) -> Self {
+                    optimized!();
+                    unsafe { $mmfn(self.be_i8s(), (indices & $vec::splat(0x0F)).be_i8s()).be_u8s() }
+                }
+
+                #[inline(always)]
+                #[cfg(not(target_feature = $feat))]
+                fn shuffle_bytes(&self, indices: Self) -> Self {
+                    fallback!();
+                    let mut ret = *self;
+                    for i in 0..Self::WIDTH {
+                        let idx = (i & !15) | (indices.extract(i) & 0x0F) as usize;
+                        ret = ret.replace(i, self.extract(idx));
+                    }
+                    ret
+                }
+            }
+        )*
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn lookup_nibbles_u8s() {
+        let mut table = [0u8; 16];
+        for (i, t) in table.iter_mut().enumerate() {
+            *t = 200 - i as u8 * 3;
+        }
+        for b in 0..=255u8 {
+            assert_eq!(u8s(b).lookup_nibbles(&table), u8s(table[(b & 0x0F) as usize]));
+        }
+
+        let mut v = u8s(0);
+        for i in 0..u8s::WIDTH {
+            v = v.replace(i, (i * 7) as u8);
+        }
+        let looked = v.lookup_nibbles(&table);
+        for i in 0..u8s::WIDTH {
+            assert_eq!(looked.extract(i), table[(i * 7) & 0x0F]);
+        }
+    }
+
+    #[test]
+    fn shuffle_bytes_u8s() {
+        let mut v = u8s(0);
+        let mut reversed = u8s(0);
+        for i in 0..u8s::WIDTH {
+            v = v.replace(i, i as u8);
+            reversed = reversed.replace(i, 15 - (i % 16) as u8 + 0xF0);
+        }
+        let shuffled = v.shuffle_bytes(reversed);
+        for i in 0..u8s::WIDTH {
+            assert_eq!(shuffled.extract(i) as usize, (i & !15) + 15 - (i % 16));
+        }
+    }
+}
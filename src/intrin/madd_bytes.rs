@@ -0,0 +1,104 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::vecs::*;
+
+pub trait MaddBytes : Packed {
+    type Weights : Packed;
+    type Out : Packed;
+
+    /// Return a vector where each element is the sum of the products of a
+    /// pair of adjacent elements of `self`, treated as unsigned, and the
+    /// elements of `weights` at the same indices, treated as signed. Sums
+    /// which don't fit in an i16 saturate.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// assert_eq!(u8s::interleave(200, 3).madd_bytes(i8s::interleave(2, -1)), i16s(397));
+    /// assert_eq!(u8s(255).madd_bytes(i8s(127)), i16s(i16::max_value()));
+    /// # }
+    /// ```
+    fn madd_bytes(&self, weights: Self::Weights) -> Self::Out;
+}
+
+macro_rules! impl_packed_madd_bytes {
+    // Each pair of elements lies within one half of the vector, so the
+    // halves can be multiplied on their own
+    (halves $($vec:tt, $weights:tt, $out:tt, $half:tt, $hweights:tt, $hout:tt);*) => (
+        $(
+            impl MaddBytes for $vec {
+                type Weights = $weights;
+                type Out = $out;
+
+                #[inline(always)]
+                fn madd_bytes(&self, weights: Self::Weights) -> Self::Out {
+                    let (v, w): ([$half; 2], [$hweights; 2]) = unsafe { (transmute(*self), transmute(weights)) };
+                    let sums: [$hout; 2] = [v[0].madd_bytes(w[0]), v[1].madd_bytes(w[1])];
+                    unsafe { transmute(sums) }
+                }
+            }
+        )*
+    );
+    ($($vec:tt, $weights:tt, $out:tt, $feat:expr, $mmfn:tt);*) => (
+        $(
+            impl MaddBytes for $vec {
+                type Weights = $weights;
+                type Out = $out;
+
+                #[inline(always)]
+                #[cfg(target_feature = $feat)]
+                fn madd_bytes(&self, weights: Self::Weights) -> Self::Out {
+                    optimized!();
+                    unsafe { $mmfn(self.be_i16s(), weights.be_i16s()) }
+                }
+
+                #[inline(always)]
+                #[cfg(not(target_feature = $feat))]
+                fn madd_bytes(&self, weights: Self::Weights) -> Self::Out {
+                    fallback!();
+                    let mut ret = $out::splat(0);
+                    for i in 0..$out::WIDTH {
+                        let sum = self.extract(i * 2) as i32 * weights.extract(i * 2) as i32
+                            + self.extract(i * 2 + 1) as i32 * weights.extract(i * 2 + 1) as i32;
+                        let sum = sum.max(i16::min_value() as i32).min(i16::max_value() as i32);
+                        ret = ret.replace(i, sum as i16);
+                    }
+                    ret
+                }
+            }
+        )*
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::test_util::rng;
+
+    #[test]
+    fn madd_bytes_u8s() {
+        let mut seed = 0x9E3779B97F4A7C15u64;
+        for _ in 0..100 {
+            let (mut v, mut w) = (u8s(0), i8s(0));
+            for i in 0..u8s::WIDTH {
+                let x = rng(&mut seed);
+                v = v.replace(i, x as u8);
+                w = w.replace(i, (x >> 8) as i8);
+            }
+            let sums = v.madd_bytes(w);
+            for i in 0..i16s::WIDTH {
+                let sum = (i * 2..i * 2 + 2)
+                    .map(|j| v.extract(j) as i32 * w.extract(j) as i32)
+                    .sum::<i32>();
+                assert_eq!(sums.extract(i) as i32, sum.max(-32768).min(32767));
+            }
+        }
+    }
+}
@@ -13,22 +13,33 @@ pub mod cmp;
 pub mod downcast;
 #[macro_use] pub mod endian;
 #[macro_use] pub mod eq;
+#[macro_use] pub mod float_ops;
 pub mod hadd;
+#[macro_use] pub mod hash_lanes;
 pub mod hsub;
+#[macro_use] pub mod lookup;
+#[macro_use] pub mod madd_bytes;
 #[macro_use] pub mod macros;
 #[macro_use] pub mod merge;
+#[macro_use] pub mod movemask;
+#[macro_use] pub mod overflowing_add;
 #[macro_use] pub mod popcnt;
-pub mod recip;
+#[macro_use] pub mod prefix_xor;
+#[macro_use] pub mod recip;
 pub mod round;
-pub mod rsqrt;
+#[macro_use] pub mod rsqrt;
 #[macro_use] pub mod sum;
-pub mod saturating_add;
+#[macro_use] pub mod sum_bytes;
+#[macro_use] pub mod saturating_add;
 pub mod saturating_hadd;
 pub mod saturating_hsub;
-pub mod saturating_sub;
+#[macro_use] pub mod saturating_mul;
+#[macro_use] pub mod saturating_sub;
+pub(crate) mod shuffle;
 pub mod sqrt;
 #[macro_use] pub mod transmute;
 pub mod upcast;
+#[macro_use] pub mod wrapping;
 
 // We use an internal prelude not to clutter the namespace when we import
 // from actual prelude.
@@ -41,17 +52,26 @@ pub(crate) mod prelude {
     pub use super::downcast::*;
     pub use super::endian::*;
     pub use super::eq::*;
+    pub use super::float_ops::*;
     pub use super::hadd::*;
+    pub use super::hash_lanes::HashLanes;
     pub use super::hsub::*;
+    pub use super::lookup::*;
+    pub use super::madd_bytes::*;
     pub use super::merge::*;
+    pub use super::movemask::*;
+    pub use super::overflowing_add::*;
     pub use super::popcnt::*;
+    pub use super::prefix_xor::*;
     pub use super::recip::*;
     pub use super::round::*;
     pub use super::rsqrt::*;
     pub use super::sum::*;
+    pub use super::sum_bytes::*;
     pub use super::saturating_add::*;
     pub use super::saturating_hadd::*;
     pub use super::saturating_hsub::*;
+    pub use super::saturating_mul::*;
     pub use super::saturating_sub::*;
     pub use super::sqrt::*;
     pub use super::transmute::*;
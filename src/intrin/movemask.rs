@@ -0,0 +1,77 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::vecs::*;
+
+pub trait Movemask : Packed {
+    /// Return an integer where bit i is the most significant bit of the
+    /// element of `self` at index i. Typically used to inspect the result of
+    /// [`Eq::eq_mask`].
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// assert_eq!(u8s(0).replace(1, 0x80).movemask(), 0b10);
+    /// assert_eq!(u8s(3).eq_mask(u8s(4)).movemask(), 0);
+    /// # }
+    /// ```
+    ///
+    /// [`Eq::eq_mask`]: ../eq/trait.Eq.html#tymethod.eq_mask
+    fn movemask(&self) -> u32;
+}
+
+macro_rules! impl_packed_movemask {
+    ($($vec:tt, $feat:expr, $mmfn:tt);*) => (
+        $(
+            impl Movemask for $vec {
+                #[inline(always)]
+                #[cfg(target_feature = $feat)]
+                fn movemask(&self) -> u32 {
+                    optimized!();
+                    unsafe { $mmfn(self.be_i8s()) as u32 }
+                }
+
+                #[inline(always)]
+                #[cfg(not(target_feature = $feat))]
+                fn movemask(&self) -> u32 {
+                    fallback!();
+                    let bytes = self.be_u8s();
+                    let mut ret = 0;
+                    for i in 0..Self::WIDTH {
+                        ret |= ((bytes.extract(i) >> 7) as u32) << i;
+                    }
+                    ret
+                }
+            }
+        )*
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn movemask_u8s() {
+        for i in 0..u8s::WIDTH {
+            assert_eq!(u8s(0).replace(i, 0x80).movemask(), 1 << i);
+            assert_eq!(u8s(0x7F).replace(i, 0xFF).movemask(), 1 << i);
+        }
+        assert_eq!(u8s(0).movemask(), 0);
+        assert_eq!(u8s(0xFF).movemask().count_ones() as usize, u8s::WIDTH);
+    }
+
+    #[test]
+    fn movemask_i8s() {
+        for i in 0..i8s::WIDTH {
+            assert_eq!(i8s(0).replace(i, -1).movemask(), 1 << i);
+        }
+        assert_eq!(i8s(-128).movemask().count_ones() as usize, i8s::WIDTH);
+    }
+}
@@ -0,0 +1,105 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::vecs::*;
+
+pub trait OverflowingAdd : Packed {
+    type Out : Pattern;
+
+    /// Return a tuple of a vector containing the wrapping sums of the
+    /// elements of `self` and `other`, and a vector where each element at
+    /// index i is filled with 1s if the sum at index i overflowed, and filled
+    /// with zeroes otherwise. For unsigned vectors, the mask is the carry out
+    /// of each element.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// assert_eq!(u32s(0xFFFFFFFF).overflowing_add(u32s(2)), (u32s(1), u32s(0xFFFFFFFF)));
+    /// assert_eq!(i32s(-1).overflowing_add(i32s(2)), (i32s(1), u32s(0)));
+    /// # }
+    /// ```
+    fn overflowing_add(&self, other: Self) -> (Self, Self::Out);
+}
+
+macro_rules! impl_packed_overflowing_add {
+    (unsigned $($vec:tt, $shift:expr);*) => (
+        $(
+            impl OverflowingAdd for $vec {
+                type Out = Self;
+
+                #[inline(always)]
+                fn overflowing_add(&self, other: Self) -> (Self, Self::Out) {
+                    let (a, b) = (*self, other);
+                    let sum = a + b;
+                    let carry = (a & b) | ((a | b) & !sum);
+                    (sum, $vec::splat(0) - (carry >> $vec::splat($shift)))
+                }
+            }
+        )*
+    );
+    (signed $($vec:tt, $uvec:tt, $ubits:ident, $shift:expr);*) => (
+        $(
+            impl OverflowingAdd for $vec {
+                type Out = $uvec;
+
+                #[inline(always)]
+                fn overflowing_add(&self, other: Self) -> (Self, Self::Out) {
+                    let (a, b) = (*self, other);
+                    let sum = a + b;
+                    let overflow = ((a ^ sum) & (b ^ sum)) >> $vec::splat($shift);
+                    (sum, overflow.$ubits())
+                }
+            }
+        )*
+    );
+    (scalar $($vec:tt, $uvec:tt, [$($n:expr),+]);*) => (
+        $(
+            impl OverflowingAdd for $vec {
+                type Out = $uvec;
+
+                #[inline(always)]
+                fn overflowing_add(&self, other: Self) -> (Self, Self::Out) {
+                    fallback!();
+                    (Self::new($(self.extract($n).wrapping_add(other.extract($n))),*),
+                     $uvec::new($(if self.extract($n).checked_add(other.extract($n)).is_none() { !0 } else { 0 }),*))
+                }
+            }
+        )*
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    macro_rules! test_overflowing_add {
+        ($vec:tt, $el:tt, $uvec:tt, $name:ident) => {
+            #[test]
+            fn $name() {
+                use crate::core::$el::{MAX, MIN};
+                let vals = [0, 1, 2, MAX, MAX - 1, MIN, MIN + 1, MAX / 2, MAX / 2 + 1, MIN / 2];
+                for &a in vals.iter() {
+                    for &b in vals.iter() {
+                        let (sum, overflow) = a.overflowing_add(b);
+                        let mask = if overflow { $uvec::ones() } else { $uvec::zeroes() };
+                        assert_eq!($vec(a).overflowing_add($vec(b)), ($vec(sum), mask));
+                    }
+                }
+            }
+        }
+    }
+
+    test_overflowing_add!(u32s, u32, u32s, overflowing_add_u32s);
+    test_overflowing_add!(i32s, i32, u32s, overflowing_add_i32s);
+    test_overflowing_add!(u64s, u64, u64s, overflowing_add_u64s);
+    test_overflowing_add!(i64s, i64, u64s, overflowing_add_i64s);
+    test_overflowing_add!(u8s, u8, u8s, overflowing_add_u8s);
+    test_overflowing_add!(i16s, i16, u16s, overflowing_add_i16s);
+}
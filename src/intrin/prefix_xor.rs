@@ -0,0 +1,72 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+pub trait PrefixXor {
+    /// Return an integer where bit i is the exclusive or of bits 0 through i
+    /// of `self`. Given a mask of quotes, this sets the bits from each
+    /// opening quote up to, but not including, its closing quote.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// assert_eq!(0b0100_0100u64.prefix_xor(), 0b0011_1100);
+    /// assert_eq!(1u64.prefix_xor(), !0);
+    /// # }
+    /// ```
+    fn prefix_xor(&self) -> Self;
+}
+
+macro_rules! impl_prefix_xor {
+    ($ty:ty, $vec:tt, $feat:expr, $mmfn:tt) => (
+        impl PrefixXor for $ty {
+            #[inline(always)]
+            #[cfg(target_feature = $feat)]
+            fn prefix_xor(&self) -> Self {
+                optimized!();
+                // A carryless multiplication by all ones
+                unsafe { $mmfn($vec::new(*self as i64, 0), $vec::splat(-1), 0).extract(0) as $ty }
+            }
+
+            #[inline(always)]
+            #[cfg(not(target_feature = $feat))]
+            fn prefix_xor(&self) -> Self {
+                fallback!();
+                let mut ret = *self;
+                ret ^= ret << 1;
+                ret ^= ret << 2;
+                ret ^= ret << 4;
+                ret ^= ret << 8;
+                ret ^= ret << 16;
+                ret ^= ret << 32;
+                ret
+            }
+        }
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::test_util::rng;
+
+    #[test]
+    fn prefix_xor_u64() {
+        let mut seed = 0x9E3779B97F4A7C15u64;
+        for _ in 0..1000 {
+            let x = rng(&mut seed);
+            let mut expected = 0u64;
+            let mut bit = 0;
+            for i in 0..64 {
+                bit ^= (x >> i) & 1;
+                expected |= bit << i;
+            }
+            assert_eq!(x.prefix_xor(), expected);
+        }
+    }
+}
@@ -5,6 +5,16 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+/// Reciprocals of floating-point vectors.
+///
+/// Vectors of `f32` may be estimated in hardware, in which case `recip` is
+/// accurate to roughly 12 bits. Subnormal inputs and outputs of `recip` and
+/// `recip_nr` are flushed to zero on every backend, so the reciprocal of a
+/// subnormal is an infinity of the same sign. Zeroes and infinities are
+/// always mapped to infinities and zeroes of the same sign.
+///
+/// Vectors of `f64` have no estimate instruction, so all three functions
+/// return the correctly rounded reciprocal.
 pub trait Recip {
     /// Return a vector containing an estimation of the reciprocal of the
     /// corresponding elements of `self`.
@@ -21,6 +31,122 @@ pub trait Recip {
     /// # }
     /// ```
     fn recip(&self) -> Self;
+
+    /// Return a vector containing an estimation of the reciprocal of the
+    /// corresponding elements of `self`, refined by one Newton-Raphson step.
+    /// The result is within a few ULP of the exact reciprocal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// assert!((f32s(3.0).recip_nr().coalesce() - 1.0 / 3.0).abs() < 1e-6);
+    /// # }
+    /// ```
+    fn recip_nr(&self) -> Self;
+
+    /// Return a vector containing the correctly rounded reciprocal of the
+    /// corresponding elements of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// assert_eq!(f32s(3.0).recip_exact(), f32s(1.0 / 3.0));
+    /// # }
+    /// ```
+    fn recip_exact(&self) -> Self;
+}
+
+/// Flush a subnormal `f32` to a zero of the same sign, mirroring the
+/// behavior of the x86 estimate instructions.
+#[inline(always)]
+pub(crate) fn flush_subnormal(x: f32) -> f32 {
+    if x.to_bits() & 0x7F80_0000 == 0 {
+        f32::from_bits(x.to_bits() & 0x8000_0000)
+    } else {
+        x
+    }
+}
+
+/// Return the reciprocal of `x` with the same handling of special values as
+/// the hardware estimate.
+#[inline(always)]
+pub(crate) fn recip_estimate(x: f32) -> f32 {
+    flush_subnormal(1.0 / flush_subnormal(x))
+}
+
+// Replace the lanes of a refined estimate where the estimate is zero or
+// infinite, as a Newton-Raphson step turns them into NaNs.
+macro_rules! refine_guard {
+    ($est:expr, $refined:expr, $uvec:tt, $ubits:ident, $fbits:ident) => {{
+        let est = $est;
+        let special = est.infinite_mask() | est.eq_mask(Self::splat(0.0));
+        let bits = ($refined.$ubits() & (special ^ $uvec::ones())) | (est.$ubits() & special);
+        unsafe { bits.$fbits() }
+    }}
+}
+
+macro_rules! impl_packed_recip {
+    ($vec:tt, $uvec:tt, $feat:expr, $mmfn:tt, [$($n:expr),+]) => {
+        impl Recip for $vec {
+            #[inline(always)]
+            #[cfg(target_feature = $feat)]
+            fn recip(&self) -> Self {
+                optimized!();
+                unsafe { $mmfn(*self) }
+            }
+
+            #[inline(always)]
+            #[cfg(not(target_feature = $feat))]
+            fn recip(&self) -> Self {
+                fallback!();
+                Self::new($(crate::intrin::recip::recip_estimate(self.extract($n))),*)
+            }
+
+            #[inline(always)]
+            fn recip_nr(&self) -> Self {
+                // x1 = x0 * (2 - a * x0)
+                let est = self.recip();
+                let refined = est * (Self::splat(2.0) - *self * est);
+                refine_guard!(est, refined, $uvec, be_u32s, be_f32s_unchecked)
+            }
+
+            #[inline(always)]
+            fn recip_exact(&self) -> Self {
+                Self::splat(1.0) / *self
+            }
+        }
+    }
+}
+
+macro_rules! impl_packed_recip_exact {
+    ($($vec:tt),*) => {
+        $(
+            impl Recip for $vec {
+                #[inline(always)]
+                fn recip(&self) -> Self {
+                    self.recip_exact()
+                }
+
+                #[inline(always)]
+                fn recip_nr(&self) -> Self {
+                    self.recip_exact()
+                }
+
+                #[inline(always)]
+                fn recip_exact(&self) -> Self {
+                    Self::splat(1.0) / *self
+                }
+            }
+        )*
+    }
 }
 
 #[cfg(test)]
@@ -39,4 +165,54 @@ mod tests {
             i += 1.0
         }
     }
+
+    #[test]
+    fn recip_nr_f32s() {
+        let mut i = -1024.0f32;
+        while i < 1024.0 {
+            let ans = f32s(i).recip_nr().extract(0);
+            let real = 1.0 / i;
+            assert!((real == INFINITY && ans == INFINITY) || ((ans - real) / real).abs() < 1e-6);
+            i += 0.75
+        }
+    }
+
+    #[test]
+    fn recip_exact_f32s() {
+        let mut i = -1024.0f32;
+        while i < 1024.0 {
+            assert_eq!(f32s(i).recip_exact().extract(0), 1.0 / i);
+            i += 0.75
+        }
+    }
+
+    #[test]
+    fn recip_f64s() {
+        let mut i = -1024.0f64;
+        while i < 1024.0 {
+            assert_eq!(f64s(i).recip().extract(0), 1.0 / i);
+            assert_eq!(f64s(i).recip_nr().extract(0), 1.0 / i);
+            assert_eq!(f64s(i).recip_exact().extract(0), 1.0 / i);
+            i += 0.75
+        }
+    }
+
+    #[test]
+    fn recip_special_f32s() {
+        use std::f32::*;
+        let subnormal = MIN_POSITIVE / 4.0;
+
+        let fns: [fn(f32s) -> f32s; 2] = [|v| v.recip(), |v| v.recip_nr()];
+        for f in fns.iter() {
+            assert_eq!(f(f32s(0.0)).extract(0), INFINITY);
+            assert_eq!(f(f32s(-0.0)).extract(0), NEG_INFINITY);
+            assert_eq!(f(f32s(INFINITY)).extract(0).to_bits(), 0.0f32.to_bits());
+            assert_eq!(f(f32s(NEG_INFINITY)).extract(0).to_bits(), (-0.0f32).to_bits());
+            assert_eq!(f(f32s(subnormal)).extract(0), INFINITY);
+            assert_eq!(f(f32s(-subnormal)).extract(0), NEG_INFINITY);
+            assert!(f(f32s(NAN)).extract(0).is_nan());
+        }
+
+        assert_eq!(f32s(subnormal).recip_exact().extract(0), 1.0 / subnormal);
+    }
 }
@@ -5,8 +5,15 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-// TODO: Guards and non-simd
-
+/// Reciprocal square roots of floating-point vectors.
+///
+/// Special values are handled in the same way as [`Recip`]: subnormal inputs
+/// of `f32` vectors are flushed to zero, zeroes are mapped to infinities of
+/// the same sign, positive infinity is mapped to zero, and negative inputs
+/// are mapped to NaN. Vectors of `f64` always return the correctly rounded
+/// result.
+///
+/// [`Recip`]: ../recip/trait.Recip.html
 pub trait Rsqrt {
     /// Return a vector containing an approximation of the reciprocals of the
     /// square-roots of elements in `self`. May contain significant float error
@@ -22,4 +29,126 @@ pub trait Rsqrt {
     /// # }
     /// ```
     fn rsqrt(&self) -> Self;
+
+    /// Return a vector containing an approximation of the reciprocals of the
+    /// square-roots of elements in `self`, refined by two Newton-Raphson
+    /// steps. The result is within a few ULP of the exact value.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// assert!((f32s(9.0).rsqrt_nr().coalesce() - 1.0 / 3.0).abs() < 1e-6);
+    /// # }
+    /// ```
+    fn rsqrt_nr(&self) -> Self;
+}
+
+/// Return the reciprocal square root of `x` with the same handling of special
+/// values as the hardware estimate.
+#[inline(always)]
+pub(crate) fn rsqrt_estimate(x: f32) -> f32 {
+    1.0 / crate::intrin::recip::flush_subnormal(x).sqrt()
+}
+
+macro_rules! impl_packed_rsqrt {
+    ($vec:tt, $uvec:tt, $feat:expr, $mmfn:tt, [$($n:expr),+]) => {
+        impl Rsqrt for $vec {
+            #[inline(always)]
+            #[cfg(target_feature = $feat)]
+            fn rsqrt(&self) -> Self {
+                optimized!();
+                unsafe { $mmfn(*self) }
+            }
+
+            #[inline(always)]
+            #[cfg(not(target_feature = $feat))]
+            fn rsqrt(&self) -> Self {
+                fallback!();
+                Self::new($(crate::intrin::rsqrt::rsqrt_estimate(self.extract($n))),*)
+            }
+
+            #[inline(always)]
+            fn rsqrt_nr(&self) -> Self {
+                // x1 = x0 * (1.5 - 0.5 * a * x0 * x0)
+                let est = self.rsqrt();
+                let half = *self * Self::splat(0.5);
+                let step = |x: Self| x * (Self::splat(1.5) - half * x * x);
+                refine_guard!(est, step(step(est)), $uvec, be_u32s, be_f32s_unchecked)
+            }
+        }
+    }
+}
+
+macro_rules! impl_packed_rsqrt_exact {
+    ($($vec:tt),*) => {
+        $(
+            impl Rsqrt for $vec {
+                #[inline(always)]
+                fn rsqrt(&self) -> Self {
+                    Self::splat(1.0) / Sqrt::sqrt(self)
+                }
+
+                #[inline(always)]
+                fn rsqrt_nr(&self) -> Self {
+                    self.rsqrt()
+                }
+            }
+        )*
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn rsqrt_f32s() {
+        let mut i = 0.25f32;
+        while i < 1024.0 {
+            let ans = f32s(i).rsqrt().extract(0);
+            let real = 1.0 / i.sqrt();
+            assert!((ans - real).abs() < 0.0005);
+            i += 0.25
+        }
+    }
+
+    #[test]
+    fn rsqrt_nr_f32s() {
+        let mut i = 0.25f32;
+        while i < 1024.0 {
+            let ans = f32s(i).rsqrt_nr().extract(0);
+            let real = 1.0 / i.sqrt();
+            assert!(((ans - real) / real).abs() < 1e-6);
+            i += 0.25
+        }
+    }
+
+    #[test]
+    fn rsqrt_f64s() {
+        let mut i = 0.25f64;
+        while i < 1024.0 {
+            assert_eq!(f64s(i).rsqrt().extract(0), 1.0 / i.sqrt());
+            assert_eq!(f64s(i).rsqrt_nr().extract(0), 1.0 / i.sqrt());
+            i += 0.25
+        }
+    }
+
+    #[test]
+    fn rsqrt_special_f32s() {
+        use std::f32::*;
+        let subnormal = MIN_POSITIVE / 4.0;
+
+        let fns: [fn(f32s) -> f32s; 2] = [|v| v.rsqrt(), |v| v.rsqrt_nr()];
+        for f in fns.iter() {
+            assert_eq!(f(f32s(0.0)).extract(0), INFINITY);
+            assert_eq!(f(f32s(-0.0)).extract(0), NEG_INFINITY);
+            assert_eq!(f(f32s(INFINITY)).extract(0).to_bits(), 0.0f32.to_bits());
+            assert_eq!(f(f32s(subnormal)).extract(0), INFINITY);
+            assert!(f(f32s(-1.0)).extract(0).is_nan());
+            assert!(f(f32s(NEG_INFINITY)).extract(0).is_nan());
+            assert!(f(f32s(NAN)).extract(0).is_nan());
+        }
+    }
 }
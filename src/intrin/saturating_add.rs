@@ -6,5 +6,85 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 pub trait SaturatingAdd {
+    /// Return a vector containing the sums of the elements of `self` and
+    /// `other`, clamped to the maximum and minimum value of the element type.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// assert_eq!(u32s(0xFFFFFFF0).saturating_add(u32s(0x20)), u32s(0xFFFFFFFF));
+    /// assert_eq!(i64s(-5).saturating_add(i64s(::std::i64::MIN)), i64s(::std::i64::MIN));
+    /// # }
+    /// ```
     fn saturating_add(&self, other: Self) -> Self;
 }
+
+// Lanes without a saturating instruction are computed with a wrapping add,
+// after which the lanes which overflowed are replaced with a bound.
+macro_rules! impl_packed_saturating_add {
+    (unsigned $($vec:tt, $shift:expr);*) => (
+        $(
+            impl SaturatingAdd for $vec {
+                #[inline(always)]
+                fn saturating_add(&self, other: Self) -> Self {
+                    let (a, b) = (*self, other);
+                    let sum = a + b;
+                    // The top bit of the carry out of each lane
+                    let carry = (a & b) | ((a | b) & !sum);
+                    sum | ($vec::splat(0) - (carry >> $vec::splat($shift)))
+                }
+            }
+        )*
+    );
+    (signed $($vec:tt, $shift:expr, $max:expr);*) => (
+        $(
+            impl SaturatingAdd for $vec {
+                #[inline(always)]
+                fn saturating_add(&self, other: Self) -> Self {
+                    let (a, b) = (*self, other);
+                    let sum = a + b;
+                    // Overflow occurs when both operands differ in sign from
+                    // the sum, in which case we saturate towards a's sign.
+                    let overflow = ((a ^ sum) & (b ^ sum)) >> $vec::splat($shift);
+                    let bound = (a >> $vec::splat($shift)) ^ $vec::splat($max);
+                    (sum & !overflow) | (bound & overflow)
+                }
+            }
+        )*
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    macro_rules! test_saturating_add {
+        ($vec:tt, $el:tt, $name:ident) => {
+            #[test]
+            fn $name() {
+                use crate::core::$el::{MAX, MIN};
+                let vals = [0, 1, 2, MAX, MAX - 1, MIN, MIN + 1, MAX / 2, MAX / 2 + 1, MIN / 2];
+                for &a in vals.iter() {
+                    for &b in vals.iter() {
+                        assert_eq!($vec(a).saturating_add($vec(b)), $vec(a.saturating_add(b)));
+                    }
+                }
+            }
+        }
+    }
+
+    test_saturating_add!(u32s, u32, saturating_add_u32s);
+    test_saturating_add!(i32s, i32, saturating_add_i32s);
+    test_saturating_add!(u64s, u64, saturating_add_u64s);
+    test_saturating_add!(i64s, i64, saturating_add_i64s);
+
+    #[test]
+    fn saturating_add_lanes_independent() {
+        assert_eq!(u32s::interleave(0xFFFFFFFF, 1).saturating_add(u32s(1)),
+                   u32s::interleave(0xFFFFFFFF, 2));
+        assert_eq!(i32s::interleave(i32::max_value(), i32::min_value()).saturating_add(i32s::interleave(1, -1)),
+                   i32s::interleave(i32::max_value(), i32::min_value()));
+    }
+}
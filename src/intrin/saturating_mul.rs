@@ -0,0 +1,228 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+pub trait SaturatingMul {
+    /// Return a vector containing the products of the elements of `self` and
+    /// `other`, clamped to the maximum and minimum value of the element type.
+    ///
+    /// On x86, 32-bit lanes are multiplied in pairs with `pmuludq` or
+    /// `pmuldq`. There is no widening multiply of 64-bit lanes, so those are
+    /// split into 32-bit halves whose products are combined, and the lanes
+    /// which overflow are found from the high halves and the carries.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// assert_eq!(u32s(0x10000).saturating_mul(u32s(0x10000)), u32s(0xFFFFFFFF));
+    /// assert_eq!(i32s(-3).saturating_mul(i32s(7)), i32s(-21));
+    /// # }
+    /// ```
+    fn saturating_mul(&self, other: Self) -> Self;
+}
+
+macro_rules! impl_packed_saturating_mul {
+    // The products of the even lanes, and of the odd lanes moved down into
+    // them, are computed in 64-bit lanes. The lanes whose high half isn't the
+    // extension of their low half are then replaced with a bound.
+    (widening unsigned $($vec:tt, $wide:tt, $feat:expr, $mul:ident, [$($n:expr),+]);*) => (
+        $(
+            impl SaturatingMul for $vec {
+                #[inline(always)]
+                #[cfg(target_feature = $feat)]
+                fn saturating_mul(&self, other: Self) -> Self {
+                    optimized!();
+                    let (even, odd) = unsafe {
+                        ($mul(*self, other).be_u64s(),
+                         $mul((self.be_u64s() >> $wide::splat(32)).be_u32s(),
+                              (other.be_u64s() >> $wide::splat(32)).be_u32s()).be_u64s())
+                    };
+                    let lo = ((even & $wide::splat(0xFFFF_FFFF)) | (odd << $wide::splat(32))).be_u32s();
+                    let hi = ((even >> $wide::splat(32)) | (odd & $wide::splat(0xFFFF_FFFF_0000_0000))).be_u32s();
+                    lo | hi.ne_mask($vec::splat(0))
+                }
+
+                #[inline(always)]
+                #[cfg(not(target_feature = $feat))]
+                fn saturating_mul(&self, other: Self) -> Self {
+                    fallback!();
+                    Self::new($(self.extract($n).saturating_mul(other.extract($n))),*)
+                }
+            }
+        )*
+    );
+    (widening signed $($vec:tt, $wide:tt, $feat:expr, $mul:ident, [$($n:expr),+]);*) => (
+        $(
+            impl SaturatingMul for $vec {
+                #[inline(always)]
+                #[cfg(target_feature = $feat)]
+                fn saturating_mul(&self, other: Self) -> Self {
+                    optimized!();
+                    let (even, odd) = unsafe {
+                        ($mul(*self, other).be_u64s(),
+                         $mul((self.be_u64s() >> $wide::splat(32)).be_i32s(),
+                              (other.be_u64s() >> $wide::splat(32)).be_i32s()).be_u64s())
+                    };
+                    let lo = ((even & $wide::splat(0xFFFF_FFFF)) | (odd << $wide::splat(32))).be_i32s();
+                    let hi = ((even >> $wide::splat(32)) | (odd & $wide::splat(0xFFFF_FFFF_0000_0000))).be_i32s();
+                    let overflow = hi.ne_mask(lo >> $vec::splat(31)).be_i32s();
+                    // Overflowing products saturate towards the sign they
+                    // would have had
+                    let bound = ((*self ^ other) >> $vec::splat(31)) ^ $vec::splat(0x7FFF_FFFF);
+                    (lo & !overflow) | (bound & overflow)
+                }
+
+                #[inline(always)]
+                #[cfg(not(target_feature = $feat))]
+                fn saturating_mul(&self, other: Self) -> Self {
+                    fallback!();
+                    Self::new($(self.extract($n).saturating_mul(other.extract($n))),*)
+                }
+            }
+        )*
+    );
+    // The full product of two 32-bit lanes fits in a 64-bit lane, so we
+    // multiply in the wider type and saturate on the way back down.
+    (upcast $($vec:tt, $wide:tt);*) => (
+        $(
+            impl SaturatingMul for $vec {
+                #[inline(always)]
+                fn saturating_mul(&self, other: Self) -> Self {
+                    let (a0, a1): ($wide, $wide) = self.upcast();
+                    let (b0, b1): ($wide, $wide) = other.upcast();
+                    (a0 * b0).saturating_downcast(a1 * b1)
+                }
+            }
+        )*
+    );
+    // A product of 64-bit lanes overflows if both high halves are set, or
+    // if the cross products of the halves reach past the top of the lane.
+    // The products of the halves fit in 64-bit lanes, and lower to
+    // `pmuludq` on x86.
+    (halves unsigned $($vec:tt),*) => (
+        $(
+            impl SaturatingMul for $vec {
+                #[inline(always)]
+                fn saturating_mul(&self, other: Self) -> Self {
+                    let (zero, low) = ($vec::splat(0), $vec::splat(0xFFFF_FFFF));
+                    let (al, ah) = (*self & low, *self >> $vec::splat(32));
+                    let (bl, bh) = (other & low, other >> $vec::splat(32));
+                    let ll = al * bl;
+                    // This only wraps when both high halves are set
+                    let cross = (al * bh).wrapping_add_lanes(ah * bl);
+                    let carry = ((ll >> $vec::splat(32)) + (cross & low)) >> $vec::splat(32);
+                    let lo = ll.wrapping_add_lanes(cross << $vec::splat(32));
+                    let overflow = ((cross >> $vec::splat(32)) | carry).ne_mask(zero)
+                        | (ah.ne_mask(zero) & bh.ne_mask(zero));
+                    lo | overflow
+                }
+            }
+        )*
+    );
+    // Multiply the magnitudes, and negate the product if the signs differ.
+    // A magnitude past the bound of its sign flips the sign of the result.
+    (halves signed $($vec:tt, $uvec:tt);*) => (
+        $(
+            impl SaturatingMul for $vec {
+                #[inline(always)]
+                fn saturating_mul(&self, other: Self) -> Self {
+                    let abs = |x: Self| {
+                        let sign = (x >> $vec::splat(63)).be_u64s();
+                        (x.be_u64s() ^ sign).wrapping_sub_lanes(sign)
+                    };
+                    let sign = ((*self ^ other) >> $vec::splat(63)).be_u64s();
+                    let product = abs(*self).saturating_mul(abs(other));
+                    let ret = (product ^ sign).wrapping_sub_lanes(sign);
+                    let overflow = (((ret ^ sign) & product).be_i64s() >> $vec::splat(63)).be_u64s();
+                    let bound = sign ^ $uvec::splat(0x7FFF_FFFF_FFFF_FFFF);
+                    (ret ^ ((ret ^ bound) & overflow)).be_i64s()
+                }
+            }
+        )*
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    macro_rules! test_saturating_mul {
+        ($vec:tt, $el:tt, $name:ident) => {
+            #[test]
+            fn $name() {
+                use crate::core::$el::{MAX, MIN};
+                let vals = [0, 1, 2, 3, MAX, MAX - 1, MIN, MIN + 1, MAX / 2, MAX / 2 + 1, MIN / 2,
+                            1 << 15, 1 << 16, (1 << 16) - 1];
+                for &a in vals.iter() {
+                    for &b in vals.iter() {
+                        assert_eq!($vec(a).saturating_mul($vec(b)), $vec(a.saturating_mul(b)));
+                    }
+                }
+            }
+        }
+    }
+
+    test_saturating_mul!(u32s, u32, saturating_mul_u32s);
+    test_saturating_mul!(i32s, i32, saturating_mul_i32s);
+    test_saturating_mul!(u64s, u64, saturating_mul_u64s);
+    test_saturating_mul!(i64s, i64, saturating_mul_i64s);
+
+    macro_rules! test_saturating_mul_lanes {
+        ($vec:tt, $el:tt, $name:ident) => {
+            #[test]
+            fn $name() {
+                use crate::core::$el::{MAX, MIN};
+                let vals = [0, 1, 3, MAX, MAX - 1, MIN, MIN + 1, MAX / 2 + 1, MIN / 2,
+                            1 << 15, 1 << 16, (1 << 16) - 1, 46341, 65537];
+                // Every lane of a vector holds a different pair, so the even
+                // and odd lanes can't be mixed up
+                for offset in 0..vals.len() {
+                    let (mut a, mut b) = ($vec(0), $vec(0));
+                    for i in 0..$vec::WIDTH {
+                        a = a.replace(i, vals[(offset + i) % vals.len()]);
+                        b = b.replace(i, vals[(offset * 3 + i * 5) % vals.len()]);
+                    }
+                    let product = a.saturating_mul(b);
+                    for i in 0..$vec::WIDTH {
+                        assert_eq!(product.extract(i), a.extract(i).saturating_mul(b.extract(i)));
+                    }
+                }
+            }
+        }
+    }
+
+    test_saturating_mul_lanes!(u32s, u32, saturating_mul_lanes_u32s);
+    test_saturating_mul_lanes!(i32s, i32, saturating_mul_lanes_i32s);
+    test_saturating_mul_lanes!(u64s, u64, saturating_mul_lanes_u64s);
+    test_saturating_mul_lanes!(i64s, i64, saturating_mul_lanes_i64s);
+
+    #[test]
+    fn saturating_mul_64_halves() {
+        // Products whose cross terms or carries reach past the top of a lane
+        let vals = [1u64 << 32, (1 << 32) - 1, (1 << 32) + 1, 1 << 31, 1 << 33, 0xFFFF_FFFF_0000_0000,
+                    0x1_8000_0000, 3 << 62, 1 << 63, (1 << 63) - 1, 0x9E37_79B9_7F4A_7C15];
+        for &a in vals.iter() {
+            for &b in vals.iter() {
+                assert_eq!(u64s(a).saturating_mul(u64s(b)), u64s(a.saturating_mul(b)));
+                let (x, y) = (a as i64, b as i64);
+                for &(x, y) in [(x, y), (x.wrapping_neg(), y), (x >> 1, (y >> 1).wrapping_neg()),
+                                (x.wrapping_neg() >> 2, y.wrapping_neg())].iter() {
+                    assert_eq!(i64s(x).saturating_mul(i64s(y)), i64s(x.saturating_mul(y)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn saturating_mul_lanes_independent() {
+        assert_eq!(i32s::interleave(0x10000, -0x10000).saturating_mul(i32s(0x10000)),
+                   i32s::interleave(i32::max_value(), i32::min_value()));
+        assert_eq!(u32s::interleave(2, 0x80000000).saturating_mul(u32s(3)),
+                   u32s::interleave(6, u32::max_value()));
+    }
+}
@@ -6,5 +6,88 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 pub trait SaturatingSub {
+    /// Return a vector containing the differences of the elements of `self`
+    /// and `other`, clamped to the maximum and minimum value of the element
+    /// type.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// assert_eq!(u32s(5).saturating_sub(u32s(6)), u32s(0));
+    /// assert_eq!(i64s(5).saturating_sub(i64s(::std::i64::MIN)), i64s(::std::i64::MAX));
+    /// # }
+    /// ```
     fn saturating_sub(&self, other: Self) -> Self;
 }
+
+// Lanes without a saturating instruction are computed with a wrapping
+// subtraction, after which the lanes which overflowed are replaced with a
+// bound.
+macro_rules! impl_packed_saturating_sub {
+    (unsigned $($vec:tt, $shift:expr);*) => (
+        $(
+            impl SaturatingSub for $vec {
+                #[inline(always)]
+                fn saturating_sub(&self, other: Self) -> Self {
+                    let (a, b) = (*self, other);
+                    let diff = a - b;
+                    // The top bit of the borrow out of each lane
+                    let borrow = (!a & b) | (!(a ^ b) & diff);
+                    diff & !($vec::splat(0) - (borrow >> $vec::splat($shift)))
+                }
+            }
+        )*
+    );
+    (signed $($vec:tt, $shift:expr, $max:expr);*) => (
+        $(
+            impl SaturatingSub for $vec {
+                #[inline(always)]
+                fn saturating_sub(&self, other: Self) -> Self {
+                    let (a, b) = (*self, other);
+                    let diff = a - b;
+                    // Overflow occurs when the operands differ in sign and the
+                    // difference differs in sign from a, in which case we
+                    // saturate towards a's sign.
+                    let overflow = ((a ^ b) & (a ^ diff)) >> $vec::splat($shift);
+                    let bound = (a >> $vec::splat($shift)) ^ $vec::splat($max);
+                    (diff & !overflow) | (bound & overflow)
+                }
+            }
+        )*
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    macro_rules! test_saturating_sub {
+        ($vec:tt, $el:tt, $name:ident) => {
+            #[test]
+            fn $name() {
+                use crate::core::$el::{MAX, MIN};
+                let vals = [0, 1, 2, MAX, MAX - 1, MIN, MIN + 1, MAX / 2, MAX / 2 + 1, MIN / 2];
+                for &a in vals.iter() {
+                    for &b in vals.iter() {
+                        assert_eq!($vec(a).saturating_sub($vec(b)), $vec(a.saturating_sub(b)));
+                    }
+                }
+            }
+        }
+    }
+
+    test_saturating_sub!(u32s, u32, saturating_sub_u32s);
+    test_saturating_sub!(i32s, i32, saturating_sub_i32s);
+    test_saturating_sub!(u64s, u64, saturating_sub_u64s);
+    test_saturating_sub!(i64s, i64, saturating_sub_i64s);
+
+    #[test]
+    fn saturating_sub_lanes_independent() {
+        assert_eq!(u32s::interleave(0, 7).saturating_sub(u32s(1)),
+                   u32s::interleave(0, 6));
+        assert_eq!(i32s::interleave(i32::min_value(), i32::max_value()).saturating_sub(i32s::interleave(1, -1)),
+                   i32s::interleave(i32::min_value(), i32::max_value()));
+    }
+}
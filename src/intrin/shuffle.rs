@@ -0,0 +1,75 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+// Lane permutations, and moves of bytes between slices and the groups of 16
+// elements in which byte shuffles work
+
+use crate::arch::current::vecs::*;
+use crate::vecs::*;
+
+// The largest vector we may need to gather into or scatter from
+pub(crate) const MAX_WIDTH: usize = 64;
+
+// Return a vector whose element at index i is the element of `v` at index
+// `f(i)`. This moves one element at a time rather than using a shuffle
+// instruction.
+#[inline(always)]
+pub(crate) fn permute<V, F>(v: V, f: F) -> V where V : Packed, F : Fn(usize) -> usize {
+    let mut ret = v;
+    for i in 0..V::WIDTH {
+        ret = ret.replace(i, v.extract(f(i)));
+    }
+    ret
+}
+
+// Return a vector whose element at index i is taken from `a` if `f(i)` is
+// true, and from `b` otherwise. Like `permute`, this moves one element at a
+// time.
+#[inline(always)]
+pub(crate) fn blend<V, F>(a: V, b: V, f: F) -> V where V : Packed, F : Fn(usize) -> bool {
+    let mut ret = b;
+    for i in 0..V::WIDTH {
+        if f(i) {
+            ret = ret.replace(i, a.extract(i));
+        }
+    }
+    ret
+}
+
+// Return a vector with `pattern` repeated in every group of 16 elements.
+#[inline(always)]
+pub(crate) fn repeat_groups(pattern: &[u8; 16]) -> u8s {
+    let mut lanes = [0u8; MAX_WIDTH];
+    for (i, lane) in lanes.iter_mut().enumerate() {
+        *lane = pattern[i % 16];
+    }
+    u8s::load(&lanes, 0)
+}
+
+// Load `chunk` bytes from `input` at `pos` into the start of each group of
+// 16 elements of a vector.
+#[inline(always)]
+pub(crate) fn gather(input: &[u8], pos: usize, chunk: usize) -> u8s {
+    let mut lanes = [0u8; MAX_WIDTH];
+    for g in 0..u8s::WIDTH / 16 {
+        lanes[g * 16..g * 16 + chunk]
+            .copy_from_slice(&input[pos + g * chunk..pos + (g + 1) * chunk]);
+    }
+    u8s::load(&lanes, 0)
+}
+
+// Store the first `chunk` elements of each group of 16 elements of `v` into
+// `output` at `pos`.
+#[inline(always)]
+pub(crate) fn scatter(v: u8s, output: &mut [u8], pos: usize, chunk: usize) {
+    let mut lanes = [0u8; MAX_WIDTH];
+    v.store(&mut lanes, 0);
+    for g in 0..u8s::WIDTH / 16 {
+        output[pos + g * chunk..pos + (g + 1) * chunk]
+            .copy_from_slice(&lanes[g * 16..g * 16 + chunk]);
+    }
+}
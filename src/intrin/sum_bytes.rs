@@ -0,0 +1,91 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::vecs::*;
+
+pub trait SumBytes : Packed {
+    type Out : Packed;
+
+    /// Return a vector where each element is the sum of a group of eight
+    /// consecutive elements of `self`, in order.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// assert_eq!(u8s(255).sum_bytes(), u64s(8 * 255));
+    /// # }
+    /// ```
+    fn sum_bytes(&self) -> Self::Out;
+}
+
+macro_rules! impl_packed_sum_bytes {
+    // Each group of eight elements lies within one half of the vector, so the
+    // halves can be summed on their own
+    (halves $($vec:tt, $out:tt, $half:tt, $hout:tt);*) => (
+        $(
+            impl SumBytes for $vec {
+                type Out = $out;
+
+                #[inline(always)]
+                fn sum_bytes(&self) -> Self::Out {
+                    let v: [$half; 2] = unsafe { transmute(*self) };
+                    let sums: [$hout; 2] = [v[0].sum_bytes(), v[1].sum_bytes()];
+                    unsafe { transmute(sums) }
+                }
+            }
+        )*
+    );
+    ($($vec:tt, $out:tt, $feat:expr, $mmfn:tt);*) => (
+        $(
+            impl SumBytes for $vec {
+                type Out = $out;
+
+                #[inline(always)]
+                #[cfg(target_feature = $feat)]
+                fn sum_bytes(&self) -> Self::Out {
+                    optimized!();
+                    unsafe { $mmfn(*self, $vec::splat(0)).be_u64s() }
+                }
+
+                #[inline(always)]
+                #[cfg(not(target_feature = $feat))]
+                fn sum_bytes(&self) -> Self::Out {
+                    fallback!();
+                    let mut ret = $out::splat(0);
+                    for i in 0..$out::WIDTH {
+                        let mut sum = 0u64;
+                        for j in 0..8 {
+                            sum += self.extract(i * 8 + j) as u64;
+                        }
+                        ret = ret.replace(i, sum);
+                    }
+                    ret
+                }
+            }
+        )*
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn sum_bytes_u8s() {
+        let mut v = u8s(0);
+        for i in 0..u8s::WIDTH {
+            v = v.replace(i, (i * 37) as u8);
+        }
+        let sums = v.sum_bytes();
+        for i in 0..u64s::WIDTH {
+            let expected = (i * 8..i * 8 + 8).map(|j| (j * 37) as u8 as u64).sum::<u64>();
+            assert_eq!(sums.extract(i), expected);
+        }
+    }
+}
@@ -0,0 +1,98 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+// Wrapping arithmetic on each lane of an integer vector, or on an integer,
+// which never panics on overflow
+pub(crate) trait WrappingLanes : Copy {
+    fn wrapping_add_lanes(self, other: Self) -> Self;
+    fn wrapping_sub_lanes(self, other: Self) -> Self;
+    fn wrapping_mul_lanes(self, other: Self) -> Self;
+}
+
+macro_rules! impl_wrapping_lanes {
+    (word $($el:tt),*) => (
+        $(
+            impl WrappingLanes for $el {
+                #[inline(always)]
+                fn wrapping_add_lanes(self, other: Self) -> Self { self.wrapping_add(other) }
+                #[inline(always)]
+                fn wrapping_sub_lanes(self, other: Self) -> Self { self.wrapping_sub(other) }
+                #[inline(always)]
+                fn wrapping_mul_lanes(self, other: Self) -> Self { self.wrapping_mul(other) }
+            }
+        )*
+    );
+    // The integer operators of these vectors already wrap
+    (vector $($vec:tt),*) => (
+        $(
+            impl WrappingLanes for $vec {
+                #[inline(always)]
+                fn wrapping_add_lanes(self, other: Self) -> Self { self + other }
+                #[inline(always)]
+                fn wrapping_sub_lanes(self, other: Self) -> Self { self - other }
+                #[inline(always)]
+                fn wrapping_mul_lanes(self, other: Self) -> Self { self * other }
+            }
+        )*
+    );
+    // The vectors of this backend panic on overflow, so work on each lane in
+    // turn
+    (lanes $($vec:tt),*) => (
+        $(
+            impl WrappingLanes for $vec {
+                #[inline(always)]
+                fn wrapping_add_lanes(self, other: Self) -> Self {
+                    let mut ret = self;
+                    for i in 0..Self::WIDTH {
+                        ret = ret.replace(i, self.extract(i).wrapping_add(other.extract(i)));
+                    }
+                    ret
+                }
+                #[inline(always)]
+                fn wrapping_sub_lanes(self, other: Self) -> Self {
+                    let mut ret = self;
+                    for i in 0..Self::WIDTH {
+                        ret = ret.replace(i, self.extract(i).wrapping_sub(other.extract(i)));
+                    }
+                    ret
+                }
+                #[inline(always)]
+                fn wrapping_mul_lanes(self, other: Self) -> Self {
+                    let mut ret = self;
+                    for i in 0..Self::WIDTH {
+                        ret = ret.replace(i, self.extract(i).wrapping_mul(other.extract(i)));
+                    }
+                    ret
+                }
+            }
+        )*
+    );
+}
+
+impl_wrapping_lanes!(word u8, i8, u16, i16, u32, i32, u64, i64);
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use super::*;
+
+    #[test]
+    fn wrapping_lanes_match_scalar() {
+        assert_eq!(u8s(250).wrapping_add_lanes(u8s(10)), u8s(4));
+        assert_eq!(i16s(crate::core::i16::MAX).wrapping_add_lanes(i16s(1)), i16s(crate::core::i16::MIN));
+        assert_eq!(u16s(3).wrapping_sub_lanes(u16s(5)), u16s(0xFFFE));
+        assert_eq!(i16s(300).wrapping_mul_lanes(i16s(300)), i16s(300i16.wrapping_mul(300)));
+        assert_eq!(u32s(0).wrapping_sub_lanes(u32s(1)), u32s(crate::core::u32::MAX));
+        assert_eq!(u32s(0x8000_0001).wrapping_mul_lanes(u32s(2)), u32s(2));
+        assert_eq!(i64s(crate::core::i64::MIN).wrapping_sub_lanes(i64s(1)), i64s(crate::core::i64::MAX));
+
+        // Each lane wraps on its own
+        let (a, b) = (u32s::interleave(7, crate::core::u32::MAX), u32s::interleave(crate::core::u32::MAX, 3));
+        assert_eq!(a.wrapping_add_lanes(b), u32s::interleave(6, 2));
+        assert_eq!(a.wrapping_sub_lanes(b), u32s::interleave(8, crate::core::u32::MAX - 3));
+    }
+}
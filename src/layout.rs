@@ -0,0 +1,563 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Rearranging matrices and records between layouts.
+//!
+//! [`transpose`] moves square tiles of `u32`, `i32` and `f32` through registers,
+//! using `unpacklo`/`unpackhi` shuffles on 4x4 tiles with SSE and 8x8 tiles
+//! with AVX. [`aos_to_soa`] and [`soa_to_aos`] convert between interleaved
+//! records and one slice per field. Records of 2 and 4 fields of the types
+//! with a [`Destride`] move a vector of each field at a time; other records
+//! move one element at a time, with the field counts 3 and 8 passed as a
+//! constant so the compiler can unroll the loop over each record.
+//!
+//! [`transpose`]: fn.transpose.html
+//! [`aos_to_soa`]: fn.aos_to_soa.html
+//! [`soa_to_aos`]: fn.soa_to_aos.html
+//! [`Destride`]: ../intrin/destride/trait.Destride.html
+
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse"))]
+use crate::arch::current::vecs::*;
+use crate::vecs::*;
+use crate::intrin::destride::*;
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse"))]
+use crate::core::slice::{from_raw_parts, from_raw_parts_mut};
+
+// The side of the square tiles which are transposed at once
+const TILE: usize = 8;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// An element type which [`transpose`], [`aos_to_soa`] and [`soa_to_aos`]
+/// accept.
+///
+/// This trait is sealed; it's implemented for the primitive integer and
+/// floating-point types.
+///
+/// [`transpose`]: fn.transpose.html
+/// [`aos_to_soa`]: fn.aos_to_soa.html
+/// [`soa_to_aos`]: fn.soa_to_aos.html
+pub trait Transpose : Copy + sealed::Sealed {
+    #[doc(hidden)]
+    #[inline(always)]
+    fn transpose_tiles(input: &[Self], output: &mut [Self], rows: usize, cols: usize) {
+        transpose_tiled(input, output, rows, cols, transpose_tile);
+    }
+
+    #[doc(hidden)]
+    #[inline(always)]
+    fn split_records(input: &[Self], outputs: &mut [&mut [Self]]) {
+        aos_to_soa_from(input, outputs, 0);
+    }
+
+    #[doc(hidden)]
+    #[inline(always)]
+    fn join_records(inputs: &[&[Self]], output: &mut [Self]) {
+        soa_to_aos_from(inputs, output, 0);
+    }
+}
+
+// Move records of 2 and 4 fields through vectors. There are no destrides of
+// 512-bit vectors, so those move an element at a time.
+macro_rules! destride_records {
+    () => (
+        #[inline(always)]
+        #[cfg(not(target_feature = "avx512"))]
+        fn split_records(input: &[Self], outputs: &mut [&mut [Self]]) {
+            aos_to_soa_vectors(input, outputs);
+        }
+
+        #[inline(always)]
+        #[cfg(not(target_feature = "avx512"))]
+        fn join_records(inputs: &[&[Self]], output: &mut [Self]) {
+            soa_to_aos_vectors(inputs, output);
+        }
+    )
+}
+
+macro_rules! impl_transpose {
+    (scalar $($el:ty),*) => {
+        $(
+            impl sealed::Sealed for $el {}
+            impl Transpose for $el {}
+        )*
+    };
+    (destride $($el:ty),*) => {
+        $(
+            impl sealed::Sealed for $el {}
+            impl Transpose for $el {
+                destride_records!();
+            }
+        )*
+    };
+}
+
+macro_rules! impl_transpose_f32 {
+    ($($el:ty),*) => {
+        $(
+            impl sealed::Sealed for $el {}
+
+            #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse"))]
+            impl Transpose for $el {
+                #[inline(always)]
+                fn transpose_tiles(input: &[Self], output: &mut [Self], rows: usize, cols: usize) {
+                    // This type has the size and alignment of an f32 and no
+                    // padding, and the shuffles move the bits of each
+                    // element unchanged
+                    let input = unsafe { from_raw_parts(input.as_ptr() as *const f32, input.len()) };
+                    let output = unsafe { from_raw_parts_mut(output.as_mut_ptr() as *mut f32, output.len()) };
+                    transpose_tiled(input, output, rows, cols, transpose_tile_f32);
+                }
+
+                destride_records!();
+            }
+
+            #[cfg(not(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse")))]
+            impl Transpose for $el {
+                destride_records!();
+            }
+        )*
+    }
+}
+
+impl_transpose!(destride u8, i8, u16, i16);
+impl_transpose!(scalar u64, i64, f64, usize, isize);
+impl_transpose_f32!(u32, i32, f32);
+
+/// Write the transpose of `input`, a `rows` by `cols` matrix in row-major
+/// order, to `output`, as a `cols` by `rows` matrix in row-major order.
+///
+/// Matrices of `u32`, `i32` and `f32` are transposed in tiles of vectors.
+/// Other types are transposed in tiles a scalar at a time, which keeps the
+/// accesses to `output` within a few cache lines.
+///
+/// # Panics
+///
+/// This function will panic if `input` or `output` don't have exactly
+/// `rows * cols` elements.
+///
+/// ```
+/// extern crate faster;
+/// use faster::layout::transpose;
+///
+/// # fn main() {
+/// let mut out = [0u32; 6];
+/// transpose(&[1, 2, 3,
+///             4, 5, 6], &mut out, 2, 3);
+/// assert_eq!(out, [1, 4,
+///                  2, 5,
+///                  3, 6]);
+/// # }
+/// ```
+pub fn transpose<T>(input: &[T], output: &mut [T], rows: usize, cols: usize) where T : Transpose {
+    assert_eq!(input.len(), rows * cols, "the input doesn't hold a {}x{} matrix", rows, cols);
+    assert_eq!(output.len(), rows * cols, "the output doesn't hold a {}x{} matrix", cols, rows);
+    T::transpose_tiles(input, output, rows, cols);
+}
+
+// Transpose each whole tile of `input` with `tile`, and the elements along
+// the bottom and right edges one at a time.
+#[inline(always)]
+fn transpose_tiled<T, F>(input: &[T], output: &mut [T], rows: usize, cols: usize, tile: F)
+    where T : Copy, F : Fn(&[T], usize, &mut [T], usize) {
+    let (tiled_rows, tiled_cols) = (rows - rows % TILE, cols - cols % TILE);
+    for i in (0..tiled_rows).step_by(TILE) {
+        for j in (0..tiled_cols).step_by(TILE) {
+            tile(&input[i * cols + j..], cols, &mut output[j * rows + i..], rows);
+        }
+    }
+    for i in 0..rows {
+        let start = if i < tiled_rows { tiled_cols } else { 0 };
+        for j in start..cols {
+            output[j * rows + i] = input[i * cols + j];
+        }
+    }
+}
+
+// Transpose the tile at the start of `input`, whose rows are `in_stride`
+// elements apart, into the start of `output`, whose rows are `out_stride`
+// elements apart.
+#[inline(always)]
+fn transpose_tile<T>(input: &[T], in_stride: usize, output: &mut [T], out_stride: usize) where T : Copy {
+    for i in 0..TILE {
+        for j in 0..TILE {
+            output[j * out_stride + i] = input[i * in_stride + j];
+        }
+    }
+}
+
+#[inline(always)]
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "avx"))]
+fn transpose_tile_f32(input: &[f32], in_stride: usize, output: &mut [f32], out_stride: usize) {
+    use crate::vektor::x86::{_mm256_unpacklo_ps, _mm256_unpackhi_ps, _mm256_shuffle_ps, _mm256_permute2f128_ps};
+    optimized!();
+    let r = |i: usize| f32x8::load(input, i * in_stride);
+    unsafe {
+        // Interleave pairs of rows, then pairs of pairs, leaving the first and
+        // last four elements of each column in opposite halves
+        let t0 = _mm256_unpacklo_ps(r(0), r(1));
+        let t1 = _mm256_unpackhi_ps(r(0), r(1));
+        let t2 = _mm256_unpacklo_ps(r(2), r(3));
+        let t3 = _mm256_unpackhi_ps(r(2), r(3));
+        let t4 = _mm256_unpacklo_ps(r(4), r(5));
+        let t5 = _mm256_unpackhi_ps(r(4), r(5));
+        let t6 = _mm256_unpacklo_ps(r(6), r(7));
+        let t7 = _mm256_unpackhi_ps(r(6), r(7));
+        let u0 = _mm256_shuffle_ps(t0, t2, 0x44);
+        let u1 = _mm256_shuffle_ps(t0, t2, 0xEE);
+        let u2 = _mm256_shuffle_ps(t1, t3, 0x44);
+        let u3 = _mm256_shuffle_ps(t1, t3, 0xEE);
+        let u4 = _mm256_shuffle_ps(t4, t6, 0x44);
+        let u5 = _mm256_shuffle_ps(t4, t6, 0xEE);
+        let u6 = _mm256_shuffle_ps(t5, t7, 0x44);
+        let u7 = _mm256_shuffle_ps(t5, t7, 0xEE);
+        let columns = [
+            _mm256_permute2f128_ps(u0, u4, 0x20),
+            _mm256_permute2f128_ps(u1, u5, 0x20),
+            _mm256_permute2f128_ps(u2, u6, 0x20),
+            _mm256_permute2f128_ps(u3, u7, 0x20),
+            _mm256_permute2f128_ps(u0, u4, 0x31),
+            _mm256_permute2f128_ps(u1, u5, 0x31),
+            _mm256_permute2f128_ps(u2, u6, 0x31),
+            _mm256_permute2f128_ps(u3, u7, 0x31),
+        ];
+        for (j, column) in columns.iter().enumerate() {
+            column.store(output, j * out_stride);
+        }
+    }
+}
+
+#[inline(always)]
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse", not(target_feature = "avx")))]
+fn transpose_tile_f32(input: &[f32], in_stride: usize, output: &mut [f32], out_stride: usize) {
+    use crate::vektor::x86::{_mm_unpacklo_ps, _mm_unpackhi_ps, _mm_movelh_ps, _mm_movehl_ps};
+    optimized!();
+    for i in (0..TILE).step_by(4) {
+        for j in (0..TILE).step_by(4) {
+            let r = |n: usize| f32x4::load(input, (i + n) * in_stride + j);
+            unsafe {
+                let t0 = _mm_unpacklo_ps(r(0), r(1));
+                let t1 = _mm_unpacklo_ps(r(2), r(3));
+                let t2 = _mm_unpackhi_ps(r(0), r(1));
+                let t3 = _mm_unpackhi_ps(r(2), r(3));
+                let columns = [_mm_movelh_ps(t0, t1), _mm_movehl_ps(t1, t0),
+                               _mm_movelh_ps(t2, t3), _mm_movehl_ps(t3, t2)];
+                for (n, column) in columns.iter().enumerate() {
+                    column.store(output, (j + n) * out_stride + i);
+                }
+            }
+        }
+    }
+}
+
+/// Split `input`, a slice of records of `outputs.len()` fields each, into
+/// one slice per field: field f of record i is written to `outputs[f][i]`.
+///
+/// # Panics
+///
+/// This function will panic if there are no outputs, if the length of
+/// `input` isn't a multiple of the number of fields, or if an output doesn't
+/// have exactly one element per record.
+///
+/// ```
+/// extern crate faster;
+/// use faster::layout::aos_to_soa;
+///
+/// # fn main() {
+/// let rgb = [1u8, 2, 3, 4, 5, 6];
+/// let (mut r, mut g, mut b) = ([0; 2], [0; 2], [0; 2]);
+/// aos_to_soa(&rgb, &mut [&mut r[..], &mut g[..], &mut b[..]]);
+/// assert_eq!((r, g, b), ([1, 4], [2, 5], [3, 6]));
+/// # }
+/// ```
+pub fn aos_to_soa<T>(input: &[T], outputs: &mut [&mut [T]]) where T : Transpose {
+    let fields = outputs.len();
+    assert!(fields > 0, "a record needs at least one field");
+    assert_eq!(input.len() % fields, 0, "the input doesn't hold a whole number of records");
+    let records = input.len() / fields;
+    assert!(outputs.iter().all(|o| o.len() == records), "each output needs one element per record");
+    T::split_records(input, outputs);
+}
+
+// Split the records of 2 or 4 fields a vector of each field at a time, and
+// the remaining records one element at a time.
+#[inline(always)]
+fn aos_to_soa_vectors<T>(input: &[T], outputs: &mut [&mut [T]])
+    where T : Packable, T::Vector : Destride {
+    let (fields, width) = (outputs.len(), T::Vector::WIDTH);
+    let records = input.len() / fields;
+    let load = |i: usize| T::Vector::load(input, i);
+    let mut i = 0;
+    match fields {
+        2 => while i + width <= records {
+            let (a, b) = load(2 * i).destride_two(load(2 * i + width));
+            a.store(outputs[0], i);
+            b.store(outputs[1], i);
+            i += width;
+        },
+        4 => while i + width <= records {
+            let (a, b, c, d) = load(4 * i).destride_four(load(4 * i + width),
+                                                         load(4 * i + 2 * width),
+                                                         load(4 * i + 3 * width));
+            a.store(outputs[0], i);
+            b.store(outputs[1], i);
+            c.store(outputs[2], i);
+            d.store(outputs[3], i);
+            i += width;
+        },
+        _ => {}
+    }
+    aos_to_soa_from(input, outputs, i);
+}
+
+// Split the records from `start` on one element at a time.
+#[inline(always)]
+fn aos_to_soa_from<T>(input: &[T], outputs: &mut [&mut [T]], start: usize) where T : Copy {
+    // A constant field count lets the compiler unroll the loop over each
+    // record
+    match outputs.len() {
+        2 => aos_to_soa_fields(input, outputs, 2, start),
+        3 => aos_to_soa_fields(input, outputs, 3, start),
+        4 => aos_to_soa_fields(input, outputs, 4, start),
+        8 => aos_to_soa_fields(input, outputs, 8, start),
+        n => aos_to_soa_fields(input, outputs, n, start),
+    }
+}
+
+#[inline(always)]
+fn aos_to_soa_fields<T>(input: &[T], outputs: &mut [&mut [T]], fields: usize, start: usize) where T : Copy {
+    for (f, output) in outputs.iter_mut().enumerate() {
+        for (out, record) in output[start..].iter_mut().zip(input[start * fields..].chunks_exact(fields)) {
+            *out = record[f];
+        }
+    }
+}
+
+/// Interleave `inputs`, one slice per field, into `output` as a slice of
+/// records: `inputs[f][i]` is written as field f of record i. This is the
+/// inverse of [`aos_to_soa`].
+///
+/// # Panics
+///
+/// This function will panic if there are no inputs, if the inputs have
+/// different lengths, or if `output` doesn't have exactly one element per
+/// field of each record.
+///
+/// ```
+/// extern crate faster;
+/// use faster::layout::soa_to_aos;
+///
+/// # fn main() {
+/// let mut rgb = [0u8; 6];
+/// soa_to_aos(&[&[1, 4], &[2, 5], &[3, 6]], &mut rgb);
+/// assert_eq!(rgb, [1, 2, 3, 4, 5, 6]);
+/// # }
+/// ```
+///
+/// [`aos_to_soa`]: fn.aos_to_soa.html
+pub fn soa_to_aos<T>(inputs: &[&[T]], output: &mut [T]) where T : Transpose {
+    let fields = inputs.len();
+    assert!(fields > 0, "a record needs at least one field");
+    let records = inputs[0].len();
+    assert!(inputs.iter().all(|i| i.len() == records), "each input needs one element per record");
+    assert_eq!(output.len(), records * fields, "the output needs one element per field of each record");
+    T::join_records(inputs, output);
+}
+
+// Join the records of 2 or 4 fields a vector of each field at a time, and
+// the remaining records one element at a time.
+//
+// A destride moves the element at index i of the concatenated vectors to
+// index i / n + (i % n) * width, which rotates the bits of the index of a
+// power-of-two number of elements. Rotating them until they come back
+// around, less one destride, undoes it.
+#[inline(always)]
+fn soa_to_aos_vectors<T>(inputs: &[&[T]], output: &mut [T])
+    where T : Packable, T::Vector : Destride {
+    let (fields, width) = (inputs.len(), T::Vector::WIDTH);
+    let records = inputs[0].len();
+    let mut i = 0;
+    match fields {
+        2 => {
+            // Each destride rotates the index bits by one
+            let rounds = (2 * width).trailing_zeros() - 1;
+            while i + width <= records {
+                let (mut a, mut b) = (T::Vector::load(inputs[0], i), T::Vector::load(inputs[1], i));
+                for _ in 0..rounds {
+                    let (x, y) = a.destride_two(b);
+                    a = x;
+                    b = y;
+                }
+                a.store(output, 2 * i);
+                b.store(output, 2 * i + width);
+                i += width;
+            }
+        },
+        4 => {
+            // Each destride rotates the index bits by two, so an odd number
+            // of bits takes two turns to come back around
+            let bits = (4 * width).trailing_zeros();
+            let rounds = if bits % 2 == 0 { bits / 2 - 1 } else { bits - 1 };
+            while i + width <= records {
+                let load = |f: usize| T::Vector::load(inputs[f], i);
+                let (mut a, mut b, mut c, mut d) = (load(0), load(1), load(2), load(3));
+                for _ in 0..rounds {
+                    let (w, x, y, z) = a.destride_four(b, c, d);
+                    a = w;
+                    b = x;
+                    c = y;
+                    d = z;
+                }
+                a.store(output, 4 * i);
+                b.store(output, 4 * i + width);
+                c.store(output, 4 * i + 2 * width);
+                d.store(output, 4 * i + 3 * width);
+                i += width;
+            }
+        },
+        _ => {}
+    }
+    soa_to_aos_from(inputs, output, i);
+}
+
+// Join the records from `start` on one element at a time.
+#[inline(always)]
+fn soa_to_aos_from<T>(inputs: &[&[T]], output: &mut [T], start: usize) where T : Copy {
+    match inputs.len() {
+        2 => soa_to_aos_fields(inputs, output, 2, start),
+        3 => soa_to_aos_fields(inputs, output, 3, start),
+        4 => soa_to_aos_fields(inputs, output, 4, start),
+        8 => soa_to_aos_fields(inputs, output, 8, start),
+        n => soa_to_aos_fields(inputs, output, n, start),
+    }
+}
+
+#[inline(always)]
+fn soa_to_aos_fields<T>(inputs: &[&[T]], output: &mut [T], fields: usize, start: usize) where T : Copy {
+    for (i, record) in output.chunks_exact_mut(fields).enumerate().skip(start) {
+        for (field, input) in record.iter_mut().zip(inputs.iter()) {
+            *field = input[i];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transpose_matches_scalar() {
+        for &(rows, cols) in &[(0, 5), (1, 1), (3, 7), (8, 8), (9, 17), (16, 24), (33, 10)] {
+            let input = (0..(rows * cols) as u32).collect::<Vec<_>>();
+            let mut output = vec![0; rows * cols];
+            transpose(&input, &mut output, rows, cols);
+            for i in 0..rows {
+                for j in 0..cols {
+                    assert_eq!(output[j * rows + i], input[i * cols + j]);
+                }
+            }
+
+            // Transposing twice gives back the input, for other sizes of
+            // element too
+            let input = input.iter().map(|&x| x as f32 + 0.5).collect::<Vec<_>>();
+            let mut output = vec![0.0; rows * cols];
+            let mut back = vec![0.0; rows * cols];
+            transpose(&input, &mut output, rows, cols);
+            transpose(&output, &mut back, cols, rows);
+            assert_eq!(back, input);
+
+            let input = input.iter().map(|&x| x as u8).collect::<Vec<_>>();
+            let mut output = vec![0; rows * cols];
+            let mut back = vec![0; rows * cols];
+            transpose(&input, &mut output, rows, cols);
+            transpose(&output, &mut back, cols, rows);
+            assert_eq!(back, input);
+
+            let input = input.iter().map(|&x| (x as u64) << 40).collect::<Vec<_>>();
+            let mut output = vec![0; rows * cols];
+            let mut back = vec![0; rows * cols];
+            transpose(&input, &mut output, rows, cols);
+            transpose(&output, &mut back, cols, rows);
+            assert_eq!(back, input);
+        }
+    }
+
+    #[test]
+    fn transpose_preserves_bits() {
+        // Signaling NaNs must come out unchanged
+        let input = (0..64u32).map(|x| f32::from_bits(0x7F80_0001 + x)).collect::<Vec<_>>();
+        let mut output = vec![0.0; 64];
+        transpose(&input, &mut output, 8, 8);
+        for i in 0..8 {
+            for j in 0..8 {
+                assert_eq!(output[j * 8 + i].to_bits(), input[i * 8 + j].to_bits());
+            }
+        }
+    }
+
+    #[test]
+    fn aos_soa_round_trip() {
+        for fields in 1..10 {
+            for records in 0..40 {
+                let aos = (0..fields * records).map(|x| x as u16).collect::<Vec<_>>();
+                let mut soa = vec![vec![0; records]; fields];
+                {
+                    let mut outputs = soa.iter_mut().map(|o| &mut o[..]).collect::<Vec<_>>();
+                    aos_to_soa(&aos, &mut outputs);
+                }
+                for (f, field) in soa.iter().enumerate() {
+                    for (i, &x) in field.iter().enumerate() {
+                        assert_eq!(x, aos[i * fields + f]);
+                    }
+                }
+
+                let inputs = soa.iter().map(|i| &i[..]).collect::<Vec<_>>();
+                let mut back = vec![0; fields * records];
+                soa_to_aos(&inputs, &mut back);
+                assert_eq!(back, aos);
+            }
+        }
+    }
+
+    macro_rules! test_aos_soa_vectors {
+        ($($el:ty, $name:ident);*) => (
+            $(
+                #[test]
+                fn $name() {
+                    // Enough records for a few vectors of the widest type,
+                    // and a partial one
+                    for &fields in &[2, 4] {
+                        for &records in &[0, 1, 15, 16, 33, 64, 100] {
+                            let aos = (0..fields * records).map(|x| x as $el).collect::<Vec<_>>();
+                            let mut soa = vec![vec![0 as $el; records]; fields];
+                            {
+                                let mut outputs = soa.iter_mut().map(|o| &mut o[..]).collect::<Vec<_>>();
+                                aos_to_soa(&aos, &mut outputs);
+                            }
+                            for (f, field) in soa.iter().enumerate() {
+                                assert!(field.iter().enumerate().all(|(i, &x)| x == aos[i * fields + f]));
+                            }
+
+                            let inputs = soa.iter().map(|i| &i[..]).collect::<Vec<_>>();
+                            let mut back = vec![0 as $el; fields * records];
+                            soa_to_aos(&inputs, &mut back);
+                            assert_eq!(back, aos);
+                        }
+                    }
+                }
+            )*
+        )
+    }
+
+    test_aos_soa_vectors!(u8, aos_soa_vectors_u8;
+                          i16, aos_soa_vectors_i16;
+                          u32, aos_soa_vectors_u32;
+                          f32, aos_soa_vectors_f32;
+                          u64, aos_soa_vectors_u64);
+}
@@ -222,5 +222,33 @@ pub mod into_iters;
 pub mod prelude;
 pub mod stride_zip;
 pub mod stride;
+pub mod windows;
+pub mod sort;
+pub mod set;
+pub mod bytes;
+pub mod text;
+pub mod codec;
+pub mod scan;
+pub mod checksum;
+pub mod hash;
+pub mod hist;
+pub mod linalg;
+pub mod layout;
+pub mod dsp;
+pub mod image;
+pub mod audio;
+pub mod stats;
+pub mod bitmap;
+
+#[cfg(test)]
+pub(crate) mod test_util {
+    // A small xorshift generator, so the tests are reproducible
+    pub(crate) fn rng(seed: &mut u64) -> u64 {
+        *seed ^= *seed << 13;
+        *seed ^= *seed >> 7;
+        *seed ^= *seed << 17;
+        *seed
+    }
+}
 
 pub use crate::prelude::*;
@@ -0,0 +1,636 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Dense linear algebra on slices of `f32` and `f64`.
+//!
+//! The vector kernels keep several independent accumulators, so consecutive
+//! additions don't wait on each other. [`gemm`] computes a block of four rows
+//! and two vectors of columns of its output at a time, keeping the whole
+//! block in registers while it walks the shared dimension.
+//!
+//! Matrices are borrowed slices with a row stride and a column stride, so
+//! row-major and column-major storage, submatrices and transposes can all be
+//! passed without copying. The fast paths need unit column strides; other
+//! layouts are computed one element at a time.
+//!
+//! [`gemm`]: fn.gemm.html
+
+use crate::vecs::*;
+use crate::iters::{SIMDIter, SIMDIterable, SIMDIterator};
+use crate::intrin::abs::*;
+use crate::intrin::cmp::*;
+use crate::intrin::sqrt::*;
+use crate::intrin::sum::*;
+use crate::core::ops::{Add, Sub, Mul, Div};
+
+const UNROLL: usize = 4;
+// The number of rows and vectors of columns in a block of `gemm`
+const BLOCK_ROWS: usize = 4;
+const BLOCK_VECTORS: usize = 2;
+
+/// A floating-point type which the functions in this module accept.
+pub trait Real : Packable + PartialOrd
+    + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+    const INFINITY: Self;
+}
+
+impl Real for f32 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+    const INFINITY: Self = crate::core::f32::INFINITY;
+}
+
+impl Real for f64 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+    const INFINITY: Self = crate::core::f64::INFINITY;
+}
+
+/// The operations the functions in this module need on vectors of a
+/// [`Real`].
+///
+/// [`Real`]: trait.Real.html
+pub trait RealVector : Packed + Cmp + Sqrt + Sum + Abs<Out = Self>
+    + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self> {}
+
+impl<V> RealVector for V where V : Packed + Cmp + Sqrt + Sum + Abs<Out = V>
+    + Add<Output = V> + Sub<Output = V> + Mul<Output = V> + Div<Output = V> {}
+
+// Return an iterator over the vectors of `data`, which pads partial vectors
+// with zeroes.
+#[inline(always)]
+fn zero_padded<T>(data: &[T]) -> SIMDIter<&[T]> where T : Real {
+    SIMDIter {
+        position: 0,
+        data: data,
+        default: T::Vector::splat(T::ZERO),
+    }
+}
+
+/// A matrix borrowed from a slice. The element at row i and column j is at
+/// index `i * row_stride + j * col_stride` of the slice.
+#[derive(Clone, Copy, Debug)]
+pub struct Matrix<'a, T : 'a> {
+    data: &'a [T],
+    rows: usize,
+    cols: usize,
+    row_stride: usize,
+    col_stride: usize,
+}
+
+/// A mutable matrix borrowed from a slice, laid out as in [`Matrix`].
+///
+/// [`Matrix`]: struct.Matrix.html
+#[derive(Debug)]
+pub struct MatrixMut<'a, T : 'a> {
+    data: &'a mut [T],
+    rows: usize,
+    cols: usize,
+    row_stride: usize,
+    col_stride: usize,
+}
+
+// Panic if the last element of a matrix with these dimensions is past the
+// end of a slice of length `len`.
+fn check_bounds(len: usize, rows: usize, cols: usize, row_stride: usize, col_stride: usize) {
+    if rows > 0 && cols > 0 {
+        let last = (rows - 1) * row_stride + (cols - 1) * col_stride;
+        assert!(last < len, "a {}x{} matrix with strides ({}, {}) doesn't fit in {} elements",
+                rows, cols, row_stride, col_stride, len);
+    }
+}
+
+impl<'a, T> Matrix<'a, T> {
+    /// Return a `rows` by `cols` matrix stored in `data` in row-major order.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the matrix doesn't fit in `data`.
+    pub fn new(data: &'a [T], rows: usize, cols: usize) -> Self {
+        Self::with_strides(data, rows, cols, cols, 1)
+    }
+
+    /// Return a `rows` by `cols` matrix stored in `data` with the given
+    /// strides.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the matrix doesn't fit in `data`.
+    pub fn with_strides(data: &'a [T], rows: usize, cols: usize, row_stride: usize, col_stride: usize) -> Self {
+        check_bounds(data.len(), rows, cols, row_stride, col_stride);
+        Matrix { data, rows, cols, row_stride, col_stride }
+    }
+
+    /// Return the transpose of this matrix, which borrows the same slice.
+    pub fn transpose(&self) -> Self {
+        Matrix {
+            data: self.data,
+            rows: self.cols,
+            cols: self.rows,
+            row_stride: self.col_stride,
+            col_stride: self.row_stride,
+        }
+    }
+
+    /// Return the number of rows of this matrix.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Return the number of columns of this matrix.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    #[inline(always)]
+    fn index(&self, i: usize, j: usize) -> usize {
+        i * self.row_stride + j * self.col_stride
+    }
+}
+
+impl<'a, T> Matrix<'a, T> where T : Copy {
+    /// Return the element at row `i` and column `j` of this matrix.
+    #[inline(always)]
+    pub fn get(&self, i: usize, j: usize) -> T {
+        debug_assert!(i < self.rows && j < self.cols);
+        self.data[self.index(i, j)]
+    }
+}
+
+impl<'a, T> MatrixMut<'a, T> {
+    /// Return a `rows` by `cols` matrix stored in `data` in row-major order.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the matrix doesn't fit in `data`.
+    pub fn new(data: &'a mut [T], rows: usize, cols: usize) -> Self {
+        Self::with_strides(data, rows, cols, cols, 1)
+    }
+
+    /// Return a `rows` by `cols` matrix stored in `data` with the given
+    /// strides. Distinct elements must not share an index, which would
+    /// happen if a stride were zero.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the matrix doesn't fit in `data`.
+    pub fn with_strides(data: &'a mut [T], rows: usize, cols: usize, row_stride: usize, col_stride: usize) -> Self {
+        check_bounds(data.len(), rows, cols, row_stride, col_stride);
+        MatrixMut { data, rows, cols, row_stride, col_stride }
+    }
+
+    /// Return the number of rows of this matrix.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Return the number of columns of this matrix.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Return an immutable view of this matrix.
+    pub fn as_matrix(&self) -> Matrix<T> {
+        Matrix {
+            data: &*self.data,
+            rows: self.rows,
+            cols: self.cols,
+            row_stride: self.row_stride,
+            col_stride: self.col_stride,
+        }
+    }
+
+    #[inline(always)]
+    fn index(&self, i: usize, j: usize) -> usize {
+        i * self.row_stride + j * self.col_stride
+    }
+}
+
+impl<'a, T> MatrixMut<'a, T> where T : Copy {
+    /// Return the element at row `i` and column `j` of this matrix.
+    #[inline(always)]
+    pub fn get(&self, i: usize, j: usize) -> T {
+        debug_assert!(i < self.rows && j < self.cols);
+        self.data[self.index(i, j)]
+    }
+
+    /// Set the element at row `i` and column `j` of this matrix to `value`.
+    #[inline(always)]
+    pub fn set(&mut self, i: usize, j: usize, value: T) {
+        debug_assert!(i < self.rows && j < self.cols);
+        let index = self.index(i, j);
+        self.data[index] = value;
+    }
+}
+
+/// Return the dot product of `a` and `b`.
+///
+/// # Panics
+///
+/// This function will panic if `a` and `b` have different lengths.
+///
+/// ```
+/// extern crate faster;
+/// use faster::linalg::dot;
+///
+/// # fn main() {
+/// assert_eq!(dot(&[1.0f32, 2.0, 3.0], &[4.0, 5.0, 6.0]), 32.0);
+/// # }
+/// ```
+pub fn dot<T>(a: &[T], b: &[T]) -> T where T : Real, T::Vector : RealVector {
+    assert_eq!(a.len(), b.len(), "the operands of a dot product must have the same length");
+    let width = T::Vector::WIDTH;
+    let mut acc = [T::Vector::splat(T::ZERO); UNROLL];
+    let mut a_iter = zero_padded(a);
+    let mut i = 0;
+
+    for vectors in a_iter.unroll(UNROLL) {
+        for (n, (acc, &a)) in acc.iter_mut().zip(vectors).enumerate() {
+            *acc = *acc + a * T::Vector::load(b, i + n * width);
+        }
+        i += vectors.len() * width;
+    }
+    // Both partial vectors are padded with zeroes, which add nothing
+    let mut b_iter = zero_padded(b);
+    b_iter.advance(i);
+    if let (Some((a, _)), Some((b, _))) = (a_iter.end(), b_iter.end()) {
+        acc[0] = acc[0] + a * b;
+    }
+
+    ((acc[0] + acc[1]) + (acc[2] + acc[3])).sum()
+}
+
+/// Add `alpha * x` to `y`.
+///
+/// # Panics
+///
+/// This function will panic if `x` and `y` have different lengths.
+///
+/// ```
+/// extern crate faster;
+/// use faster::linalg::axpy;
+///
+/// # fn main() {
+/// let mut y = [1.0f64, 1.0, 1.0];
+/// axpy(2.0, &[1.0, 2.0, 3.0], &mut y);
+/// assert_eq!(y, [3.0, 5.0, 7.0]);
+/// # }
+/// ```
+pub fn axpy<T>(alpha: T, x: &[T], y: &mut [T]) where T : Real, T::Vector : RealVector {
+    assert_eq!(x.len(), y.len(), "the operands of axpy must have the same length");
+    let width = T::Vector::WIDTH;
+    let a = T::Vector::splat(alpha);
+    let mut x_iter = zero_padded(x);
+    let mut i = 0;
+
+    for vectors in x_iter.unroll(UNROLL) {
+        for (n, &x) in vectors.iter().enumerate() {
+            let offset = i + n * width;
+            (T::Vector::load(y, offset) + a * x).store(y, offset);
+        }
+        i += vectors.len() * width;
+    }
+    for (y, &x) in y[i..].iter_mut().zip(&x[i..]) {
+        *y = *y + alpha * x;
+    }
+}
+
+/// Multiply each element of `x` by `alpha`.
+///
+/// ```
+/// extern crate faster;
+/// use faster::linalg::scal;
+///
+/// # fn main() {
+/// let mut x = [1.0f32, -2.0, 0.5];
+/// scal(4.0, &mut x);
+/// assert_eq!(x, [4.0, -8.0, 2.0]);
+/// # }
+/// ```
+pub fn scal<T>(alpha: T, x: &mut [T]) where T : Real, T::Vector : RealVector {
+    let width = T::Vector::WIDTH;
+    let len = x.len();
+    let a = T::Vector::splat(alpha);
+    let mut i = 0;
+
+    while i + width <= len {
+        (T::Vector::load(x, i) * a).store(x, i);
+        i += width;
+    }
+    for x in &mut x[i..] {
+        *x = *x * alpha;
+    }
+}
+
+/// Return the Euclidean norm of `x`.
+///
+/// The elements are divided by the largest magnitude among them before
+/// they're squared, so the result doesn't overflow or underflow unless the
+/// norm itself does.
+///
+/// ```
+/// extern crate faster;
+/// use faster::linalg::nrm2;
+///
+/// # fn main() {
+/// assert_eq!(nrm2(&[3.0f32, -4.0]), 5.0);
+/// assert!((nrm2(&[3e30f32, 4e30]) / 5e30 - 1.0).abs() < 1e-6);
+/// # }
+/// ```
+pub fn nrm2<T>(x: &[T]) -> T where T : Real, T::Vector : RealVector {
+    let width = T::Vector::WIDTH;
+    let len = x.len();
+    let body = len - len % width;
+
+    let mut max = T::Vector::splat(T::ZERO);
+    for i in (0..body).step_by(width) {
+        max = Cmp::max(&max, T::Vector::load(x, i).abs());
+    }
+    let mut scale = max.scalar_reduce(T::ZERO, |acc, s| if s > acc { s } else { acc });
+    for &s in &x[body..] {
+        let s = T::Vector::splat(s).abs().extract(0);
+        if s > scale {
+            scale = s;
+        }
+    }
+    // All zeroes, or an infinity, which stays infinite after scaling
+    if scale == T::ZERO || scale == T::INFINITY {
+        return scale;
+    }
+
+    let s = T::Vector::splat(scale);
+    let mut acc = [T::Vector::splat(T::ZERO); UNROLL];
+    let mut x_iter = zero_padded(x);
+    for vectors in x_iter.unroll(UNROLL) {
+        for (acc, &v) in acc.iter_mut().zip(vectors) {
+            let v = v / s;
+            *acc = *acc + v * v;
+        }
+    }
+    if let Some((v, _)) = x_iter.end() {
+        let v = v / s;
+        acc[0] = acc[0] + v * v;
+    }
+
+    let sum = ((acc[0] + acc[1]) + (acc[2] + acc[3])).sum();
+    scale * T::Vector::splat(sum).sqrt().extract(0)
+}
+
+/// Set `y` to `alpha * a * x + beta * y`. If `beta` is zero, `y` is only
+/// written to, so it may hold NaNs beforehand.
+///
+/// # Panics
+///
+/// This function will panic if `x` doesn't have as many elements as `a` has
+/// columns, or if `y` doesn't have as many elements as `a` has rows.
+///
+/// ```
+/// extern crate faster;
+/// use faster::linalg::{gemv, Matrix};
+///
+/// # fn main() {
+/// let a = [1.0f32, 2.0,
+///          3.0, 4.0,
+///          5.0, 6.0];
+/// let mut y = [1.0; 3];
+/// gemv(1.0, &Matrix::new(&a, 3, 2), &[1.0, -1.0], 2.0, &mut y);
+/// assert_eq!(y, [1.0, 1.0, 1.0]);
+/// # }
+/// ```
+pub fn gemv<T>(alpha: T, a: &Matrix<T>, x: &[T], beta: T, y: &mut [T])
+    where T : Real, T::Vector : RealVector {
+    assert_eq!(x.len(), a.cols, "x must have an element for each column of the matrix");
+    assert_eq!(y.len(), a.rows, "y must have an element for each row of the matrix");
+
+    if a.col_stride == 1 && a.cols > 0 {
+        // Each row is contiguous
+        for (i, y) in y.iter_mut().enumerate() {
+            let start = a.index(i, 0);
+            let ax = alpha * dot(&a.data[start..start + a.cols], x);
+            *y = if beta == T::ZERO { ax } else { ax + beta * *y };
+        }
+    } else if a.row_stride == 1 && a.rows > 0 {
+        // Each column is contiguous
+        if beta == T::ZERO {
+            for y in y.iter_mut() {
+                *y = T::ZERO;
+            }
+        } else {
+            scal(beta, y);
+        }
+        for (j, &x) in x.iter().enumerate() {
+            let start = a.index(0, j);
+            axpy(alpha * x, &a.data[start..start + a.rows], y);
+        }
+    } else {
+        for (i, y) in y.iter_mut().enumerate() {
+            let ax = (0..a.cols).fold(T::ZERO, |acc, j| acc + a.get(i, j) * x[j]);
+            let ax = alpha * ax;
+            *y = if beta == T::ZERO { ax } else { ax + beta * *y };
+        }
+    }
+}
+
+/// Set `c` to `alpha * a * b + beta * c`. If `beta` is zero, `c` is only
+/// written to, so it may hold NaNs beforehand.
+///
+/// The blocked kernel is used when `b` and `c` have unit column strides;
+/// `a` may have any layout.
+///
+/// # Panics
+///
+/// This function will panic if the dimensions of the matrices don't agree.
+///
+/// ```
+/// extern crate faster;
+/// use faster::linalg::{gemm, Matrix, MatrixMut};
+///
+/// # fn main() {
+/// let a = [1.0f64, 2.0,
+///          3.0, 4.0];
+/// let b = [5.0, 6.0,
+///          7.0, 8.0];
+/// let mut c = [0.0; 4];
+/// gemm(1.0, &Matrix::new(&a, 2, 2), &Matrix::new(&b, 2, 2), 0.0, &mut MatrixMut::new(&mut c, 2, 2));
+/// assert_eq!(c, [19.0, 22.0,
+///                43.0, 50.0]);
+/// # }
+/// ```
+pub fn gemm<T>(alpha: T, a: &Matrix<T>, b: &Matrix<T>, beta: T, c: &mut MatrixMut<T>)
+    where T : Real, T::Vector : RealVector {
+    assert_eq!(a.cols, b.rows, "the inner dimensions of a matrix product must agree");
+    assert_eq!(c.rows, a.rows, "the output of a matrix product must have a row for each row of a");
+    assert_eq!(c.cols, b.cols, "the output of a matrix product must have a column for each column of b");
+
+    let width = T::Vector::WIDTH;
+    let mut j = 0;
+    if b.col_stride == 1 && c.col_stride == 1 {
+        while j + width <= c.cols {
+            let vectors = if j + BLOCK_VECTORS * width <= c.cols { BLOCK_VECTORS } else { 1 };
+            let mut i = 0;
+            while i < c.rows {
+                let rows = BLOCK_ROWS.min(c.rows - i);
+                gemm_block(alpha, a, b, beta, c, (i, j), (rows, vectors));
+                i += rows;
+            }
+            j += vectors * width;
+        }
+    }
+
+    // The remaining columns, or every column of other layouts
+    for j in j..c.cols {
+        for i in 0..c.rows {
+            let ab = (0..a.cols).fold(T::ZERO, |acc, k| acc + a.get(i, k) * b.get(k, j));
+            let ab = alpha * ab;
+            let value = if beta == T::ZERO { ab } else { ab + beta * c.get(i, j) };
+            c.set(i, j, value);
+        }
+    }
+}
+
+// Compute the block of `c` with `rows` rows starting at row `i`, and
+// `vectors` vectors of columns starting at column `j`. `b` and `c` must have
+// unit column strides.
+#[inline(always)]
+fn gemm_block<T>(alpha: T, a: &Matrix<T>, b: &Matrix<T>, beta: T, c: &mut MatrixMut<T>,
+                 (i, j): (usize, usize), (rows, vectors): (usize, usize))
+    where T : Real, T::Vector : RealVector {
+    let width = T::Vector::WIDTH;
+    let mut acc = [[T::Vector::splat(T::ZERO); BLOCK_VECTORS]; BLOCK_ROWS];
+
+    for k in 0..a.cols {
+        let mut bk = [T::Vector::splat(T::ZERO); BLOCK_VECTORS];
+        for (v, bk) in bk.iter_mut().enumerate().take(vectors) {
+            *bk = T::Vector::load(b.data, b.index(k, j + v * width));
+        }
+        for (r, acc) in acc.iter_mut().enumerate().take(rows) {
+            let aik = T::Vector::splat(a.get(i + r, k));
+            for (acc, &bk) in acc.iter_mut().zip(bk.iter()).take(vectors) {
+                *acc = *acc + aik * bk;
+            }
+        }
+    }
+
+    let alpha = T::Vector::splat(alpha);
+    for (r, acc) in acc.iter().enumerate().take(rows) {
+        for (v, &acc) in acc.iter().enumerate().take(vectors) {
+            let index = c.index(i + r, j + v * width);
+            let value = if beta == T::ZERO {
+                alpha * acc
+            } else {
+                alpha * acc + T::Vector::splat(beta) * T::Vector::load(c.data, index)
+            };
+            value.store(c.data, index);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::rng;
+
+    // Small integers, so every sum and product is exact
+    fn data(seed: &mut u64, len: usize) -> Vec<f64> {
+        (0..len).map(|_| (rng(seed) % 17) as f64 - 8.0).collect()
+    }
+
+    #[test]
+    fn vector_kernels_match_scalar() {
+        let mut seed = 0x9E3779B97F4A7C15;
+        for len in 0..70 {
+            let x = data(&mut seed, len);
+            let y = data(&mut seed, len);
+            assert_eq!(dot(&x, &y), x.iter().zip(y.iter()).map(|(a, b)| a * b).sum::<f64>());
+
+            let xf = x.iter().map(|&x| x as f32).collect::<Vec<_>>();
+            let yf = y.iter().map(|&y| y as f32).collect::<Vec<_>>();
+            assert_eq!(dot(&xf, &yf), x.iter().zip(y.iter()).map(|(a, b)| a * b).sum::<f64>() as f32);
+
+            let mut z = y.clone();
+            axpy(-3.0, &x, &mut z);
+            assert_eq!(z, x.iter().zip(y.iter()).map(|(x, y)| y - 3.0 * x).collect::<Vec<_>>());
+
+            let mut z = xf.clone();
+            scal(0.5, &mut z);
+            assert_eq!(z, xf.iter().map(|x| x * 0.5).collect::<Vec<_>>());
+
+            let norm = x.iter().map(|x| x * x).sum::<f64>().sqrt();
+            assert!((nrm2(&x) - norm).abs() <= norm * 1e-13);
+        }
+    }
+
+    #[test]
+    fn nrm2_extremes() {
+        assert_eq!(nrm2::<f32>(&[]), 0.0);
+        assert_eq!(nrm2(&[0.0f64; 9]), 0.0);
+        assert_eq!(nrm2(&[1e300f64; 4]), 2e300);
+        assert_eq!(nrm2(&[1e-300f64; 4]), 2e-300);
+        assert_eq!(nrm2(&[1.0f32, ::std::f32::INFINITY, 2.0]), ::std::f32::INFINITY);
+        assert!(nrm2(&[1.0f32, 2.0, 3.0, 4.0, ::std::f32::NAN, 6.0]).is_nan());
+    }
+
+    // Return `alpha * a * b + beta * c`, one element at a time
+    fn naive_gemm(alpha: f64, a: &Matrix<f64>, b: &Matrix<f64>, beta: f64, c: &Matrix<f64>) -> Vec<f64> {
+        let mut ret = vec![0.0; a.rows() * b.cols()];
+        for i in 0..a.rows() {
+            for j in 0..b.cols() {
+                let ab = (0..a.cols()).map(|k| a.get(i, k) * b.get(k, j)).sum::<f64>();
+                ret[i * b.cols() + j] = alpha * ab + beta * c.get(i, j);
+            }
+        }
+        ret
+    }
+
+    #[test]
+    fn gemv_matches_scalar() {
+        let mut seed = 0xDA942042E4DD58B5;
+        for &(rows, cols) in &[(0, 3), (3, 0), (1, 1), (5, 7), (9, 33), (17, 4)] {
+            let elems = data(&mut seed, rows * cols);
+            let x = data(&mut seed, cols);
+            let y = data(&mut seed, rows);
+            let row_major = Matrix::new(&elems, rows, cols);
+            let col_major = Matrix::new(&elems, cols, rows).transpose();
+            // Every other row and column
+            let strided = Matrix::with_strides(&elems, rows / 2, cols / 2, cols * 2, 2);
+            for a in &[row_major, col_major, strided] {
+                for &beta in &[0.0, 2.0] {
+                    let (x, y) = (&x[..a.cols()], &y[..a.rows()]);
+                    let mut out = y.to_vec();
+                    gemv(3.0, a, x, beta, &mut out);
+                    let expected = naive_gemm(3.0, a, &Matrix::new(x, x.len(), 1), beta, &Matrix::new(y, y.len(), 1));
+                    assert_eq!(out, expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn gemm_matches_scalar() {
+        let mut seed = 0x2545F4914F6CDD1D;
+        for &(m, n, k) in &[(1, 1, 1), (4, 8, 3), (5, 17, 9), (9, 3, 2), (7, 35, 16), (0, 4, 4), (3, 3, 0)] {
+            let a = data(&mut seed, m * k);
+            let b = data(&mut seed, k * n);
+            let c = data(&mut seed, m * n);
+            let a_row = Matrix::new(&a, m, k);
+            let a_col = Matrix::new(&a, k, m).transpose();
+            let b_row = Matrix::new(&b, k, n);
+            let b_col = Matrix::new(&b, n, k).transpose();
+            for a in &[a_row, a_col] {
+                for b in &[b_row, b_col] {
+                    for &beta in &[0.0, -1.0] {
+                        let mut out = c.clone();
+                        gemm(2.0, a, b, beta, &mut MatrixMut::new(&mut out, m, n));
+                        assert_eq!(out, naive_gemm(2.0, a, b, beta, &Matrix::new(&c, m, n)));
+                    }
+                }
+            }
+        }
+    }
+}
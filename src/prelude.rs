@@ -14,3 +14,6 @@ pub use crate::intrin::prelude::*;
 pub use crate::zip::*;
 pub use crate::stride_zip::*;
 pub use crate::stride::*;
+pub use crate::windows::*;
+pub use crate::sort::*;
+pub use crate::hash::*;
@@ -0,0 +1,332 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Finding the structure of delimited text, for tokenizers of formats such
+//! as JSON and CSV.
+//!
+//! [`structural_bitmap`] classifies each 64-byte block of its input into
+//! bitmaps of quotes, separators and newlines, where bit i describes byte i
+//! of the block. Separators and newlines inside strings are masked out using
+//! the prefix xor of the quotes, which is a single carryless multiplication
+//! where `pclmulqdq` is available.
+//!
+//! [`structural_bitmap`]: fn.structural_bitmap.html
+
+use crate::arch::current::vecs::*;
+use crate::vecs::*;
+use crate::intrin::eq::*;
+use crate::intrin::movemask::*;
+use crate::intrin::prefix_xor::*;
+
+const BLOCK: usize = 64;
+const MAX_SEPARATORS: usize = 8;
+
+/// The bytes which [`structural_bitmap`] looks for.
+///
+/// [`structural_bitmap`]: fn.structural_bitmap.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Classes {
+    quote: Option<u8>,
+    escape: Option<u8>,
+    separators: [u8; MAX_SEPARATORS],
+    separator_count: usize,
+    newlines: bool,
+}
+
+impl Classes {
+    /// Return a set of classes which matches nothing.
+    pub fn new() -> Self {
+        Classes {
+            quote: None,
+            escape: None,
+            separators: [0; MAX_SEPARATORS],
+            separator_count: 0,
+            newlines: false,
+        }
+    }
+
+    /// Return the classes of JSON: `"` quotes strings, `\` escapes the byte
+    /// after it, and `{}[]:,` are separators. Newlines are marked too, for
+    /// formats with one document per line.
+    pub fn json() -> Self {
+        b"{}[]:,".iter().fold(Classes::new(), |c, &b| c.with_separator(b))
+            .with_quote(b'"')
+            .with_escape(b'\\')
+            .with_newlines(true)
+    }
+
+    /// Return the classes of CSV with the given delimiter: `"` quotes fields,
+    /// and a doubled quote inside a field stands for a single one.
+    pub fn csv(delimiter: u8) -> Self {
+        Classes::new()
+            .with_quote(b'"')
+            .with_separator(delimiter)
+            .with_newlines(true)
+    }
+
+    /// Use `quote` to begin and end strings.
+    pub fn with_quote(mut self, quote: u8) -> Self {
+        self.quote = Some(quote);
+        self
+    }
+
+    /// Use `escape` to stop the byte after it from ending a string.
+    pub fn with_escape(mut self, escape: u8) -> Self {
+        self.escape = Some(escape);
+        self
+    }
+
+    /// Mark `separator` outside of strings.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if more than eight separators are added.
+    pub fn with_separator(mut self, separator: u8) -> Self {
+        assert!(self.separator_count < MAX_SEPARATORS, "too many separators");
+        self.separators[self.separator_count] = separator;
+        self.separator_count += 1;
+        self
+    }
+
+    /// Mark line feeds outside of strings, if `newlines` is true.
+    pub fn with_newlines(mut self, newlines: bool) -> Self {
+        self.newlines = newlines;
+        self
+    }
+}
+
+impl Default for Classes {
+    fn default() -> Self {
+        Classes::new()
+    }
+}
+
+/// The bitmaps of a block of up to 64 bytes, where bit i describes byte i of
+/// the block.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Block {
+    /// The quotes which begin or end a string.
+    pub quotes: u64,
+    /// The bytes inside strings, including the opening quote but not the
+    /// closing one.
+    pub in_string: u64,
+    /// The separators outside of strings.
+    pub separators: u64,
+    /// The line feeds outside of strings.
+    pub newlines: u64,
+}
+
+impl Block {
+    /// Return the bytes which delimit tokens: quotes, and separators and
+    /// newlines outside of strings.
+    #[inline(always)]
+    pub fn structural(&self) -> u64 {
+        self.quotes | self.separators | self.newlines
+    }
+}
+
+/// An iterator over the [`Block`]s of a slice, returned by
+/// [`structural_bitmap`].
+///
+/// [`Block`]: struct.Block.html
+/// [`structural_bitmap`]: fn.structural_bitmap.html
+#[derive(Clone, Debug)]
+pub struct StructuralBitmap<'a> {
+    data: &'a [u8],
+    classes: Classes,
+    pos: usize,
+    // All ones if the previous block ended inside a string
+    prev_in_string: u64,
+    // One if the previous block ended with an unescaped escape byte
+    prev_escaped: u64,
+}
+
+/// Return an iterator over the bitmaps of each 64-byte block of `data`,
+/// marking the bytes in `classes`. The last block is shorter if the length
+/// of `data` isn't a multiple of 64, and the bits past its end are zero.
+///
+/// ```
+/// extern crate faster;
+/// use faster::scan::{structural_bitmap, Classes};
+///
+/// # fn main() {
+/// let csv = b"a,\"b,c\"\nd,e";
+/// let mut blocks = structural_bitmap(csv, &Classes::csv(b','));
+/// let block = blocks.next().unwrap();
+/// assert_eq!(block.quotes, 0b0100_0100);
+/// assert_eq!(block.separators, 0b10_0000_0010);
+/// assert_eq!(block.newlines, 0b1000_0000);
+/// assert!(blocks.next().is_none());
+/// assert!(!blocks.in_string());
+/// # }
+/// ```
+pub fn structural_bitmap<'a>(data: &'a [u8], classes: &Classes) -> StructuralBitmap<'a> {
+    StructuralBitmap {
+        data,
+        classes: *classes,
+        pos: 0,
+        prev_in_string: 0,
+        prev_escaped: 0,
+    }
+}
+
+impl<'a> StructuralBitmap<'a> {
+    /// Return whether the blocks returned so far end inside a string. Once
+    /// the iterator is exhausted, this means a string is unterminated.
+    pub fn in_string(&self) -> bool {
+        self.prev_in_string != 0
+    }
+
+    // Return a bitmap of the bytes of `block` which equal any of `bytes`
+    #[inline(always)]
+    fn matches(block: &[u8], bytes: &[u8]) -> u64 {
+        let mut ret = 0u64;
+        for i in (0..BLOCK).step_by(u8s::WIDTH) {
+            let v = u8s::load(block, i);
+            let mut mask = u8s(0);
+            for &b in bytes {
+                mask = mask | v.eq_mask(u8s(b));
+            }
+            ret |= (mask.movemask() as u64) << i;
+        }
+        ret
+    }
+}
+
+// Return the bytes which are escaped by an odd-length run of escape bytes,
+// after "Parsing Gigabytes of JSON per Second" by Geoff Langdale and Daniel
+// Lemire.
+#[inline(always)]
+fn find_escaped(escape: u64, prev_escaped: &mut u64) -> u64 {
+    const EVEN_BITS: u64 = 0x5555_5555_5555_5555;
+    let escape = escape & !*prev_escaped;
+    let follows_escape = escape << 1 | *prev_escaped;
+    let odd_starts = escape & !EVEN_BITS & !follows_escape;
+    let (even_starts, overflow) = odd_starts.overflowing_add(escape);
+    *prev_escaped = overflow as u64;
+    (EVEN_BITS ^ (even_starts << 1)) & follows_escape
+}
+
+impl<'a> Iterator for StructuralBitmap<'a> {
+    type Item = Block;
+
+    fn next(&mut self) -> Option<Block> {
+        if self.pos >= self.data.len() {
+            return None;
+        }
+
+        // Pad the last block with zeroes, and mask off the bits they set
+        let len = (self.data.len() - self.pos).min(BLOCK);
+        let mut padded = [0u8; BLOCK];
+        let block = if len == BLOCK {
+            &self.data[self.pos..self.pos + BLOCK]
+        } else {
+            padded[..len].copy_from_slice(&self.data[self.pos..]);
+            &padded[..]
+        };
+        let valid = if len == BLOCK { !0 } else { (1u64 << len) - 1 };
+        self.pos += len;
+
+        let classes = &self.classes;
+        let escaped = match classes.escape {
+            Some(escape) => find_escaped(Self::matches(block, &[escape]) & valid,
+                                         &mut self.prev_escaped),
+            None => 0,
+        };
+        let quotes = match classes.quote {
+            Some(quote) => Self::matches(block, &[quote]) & valid & !escaped,
+            None => 0,
+        };
+
+        let in_string = (quotes.prefix_xor() ^ self.prev_in_string) & valid;
+        self.prev_in_string = if (in_string >> (len - 1)) & 1 != 0 { !0 } else { 0 };
+
+        let separators = Self::matches(block, &classes.separators[..classes.separator_count]);
+        let newlines = if classes.newlines { Self::matches(block, b"\n") } else { 0 };
+        Some(Block {
+            quotes,
+            in_string,
+            separators: separators & valid & !in_string,
+            newlines: newlines & valid & !in_string,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.data.len() - self.pos + BLOCK - 1) / BLOCK;
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for StructuralBitmap<'a> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::rng;
+
+    // Classify `data` a byte at a time
+    fn scalar(data: &[u8], classes: &Classes) -> (Vec<Block>, bool) {
+        let mut blocks = vec![Block::default(); (data.len() + BLOCK - 1) / BLOCK];
+        let (mut in_string, mut escaped) = (false, false);
+        for (i, &b) in data.iter().enumerate() {
+            let block = &mut blocks[i / BLOCK];
+            let bit = 1u64 << (i % BLOCK);
+            if escaped {
+                escaped = false;
+            } else if Some(b) == classes.escape {
+                escaped = true;
+            } else if Some(b) == classes.quote {
+                block.quotes |= bit;
+                in_string = !in_string;
+            }
+            if in_string {
+                block.in_string |= bit;
+            } else if classes.separators[..classes.separator_count].contains(&b) {
+                block.separators |= bit;
+            } else if classes.newlines && b == b'\n' {
+                block.newlines |= bit;
+            }
+        }
+        (blocks, in_string)
+    }
+
+    #[test]
+    fn structural_bitmap_fuzz() {
+        let mut seed = 0xDA942042E4DD58B5;
+        let alphabet = b"\"\\{}[]:,\n ab";
+        for &classes in &[Classes::json(), Classes::csv(b','), Classes::csv(b'\t'), Classes::new()] {
+            for _ in 0..1000 {
+                let len = rng(&mut seed) as usize % 300;
+                let data = (0..len)
+                    .map(|_| alphabet[rng(&mut seed) as usize % alphabet.len()])
+                    .collect::<Vec<_>>();
+                let (expected, in_string) = scalar(&data, &classes);
+                let mut iter = structural_bitmap(&data, &classes);
+                assert_eq!(iter.len(), expected.len());
+                assert_eq!(iter.by_ref().collect::<Vec<_>>(), expected, "{:?}", data);
+                assert_eq!(iter.in_string(), in_string);
+            }
+        }
+    }
+
+    #[test]
+    fn structural_bitmap_escapes() {
+        // Runs of escapes crossing a block boundary
+        for run in 0..6 {
+            for start in 58..66 {
+                let mut data = vec![b' '; start];
+                data.push(b'"');
+                data.extend(::std::iter::repeat(b'\\').take(run));
+                data.extend_from_slice(b"\",\"");
+                let (expected, in_string) = scalar(&data, &Classes::json());
+                let mut iter = structural_bitmap(&data, &Classes::json());
+                assert_eq!(iter.by_ref().collect::<Vec<_>>(), expected);
+                assert_eq!(iter.in_string(), in_string);
+            }
+        }
+    }
+}
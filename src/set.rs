@@ -0,0 +1,349 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Operations on sets represented as sorted slices, such as posting lists.
+//!
+//! Every function writes its result into a caller-provided buffer and returns
+//! the number of elements written, so no allocation is required. Unless
+//! otherwise noted, the inputs must be sorted in ascending order and must not
+//! contain duplicates; the output is sorted and free of duplicates.
+//!
+//! Blocks of elements are compared all-against-all by comparing one block
+//! with every rotation of the other, after which the matching elements are
+//! compacted into the output.
+
+use crate::core::ops::BitOr;
+use crate::vecs::*;
+use crate::intrin::eq::*;
+use crate::intrin::shuffle::permute;
+use crate::sort::SortLanes;
+
+// Return a mask of the elements of `a` which are present anywhere in `b`.
+#[inline(always)]
+fn matches<V>(a: V, b: V) -> V where V : Packed + Eq<Out = V> + BitOr<Output = V> {
+    let mut mask = a.eq_mask(b);
+    for r in 1..V::WIDTH {
+        mask = mask | a.eq_mask(permute(b, |i| (i + r) % V::WIDTH));
+    }
+    mask
+}
+
+// Write the elements of `v` whose lanes in `mask` are set (or unset, if
+// `keep_set` is false) to `out`, returning the number of elements written.
+#[inline(always)]
+fn compact<T>(v: T::Vector, mask: T::Vector, keep_set: bool, out: &mut [T]) -> usize
+    where T : Packable + Default + PartialEq {
+    let mut o = 0;
+    for i in 0..T::Vector::WIDTH {
+        if (mask.extract(i) != T::default()) == keep_set {
+            out[o] = v.extract(i);
+            o += 1;
+        }
+    }
+    o
+}
+
+// Append `x` to the first `o` elements of `out` unless it is equal to the
+// last of them, returning the new length.
+#[inline(always)]
+fn push_unique<T>(out: &mut [T], o: usize, x: T) -> usize where T : Copy + PartialEq {
+    if o == 0 || out[o - 1] != x {
+        out[o] = x;
+        o + 1
+    } else {
+        o
+    }
+}
+
+// Append the sorted vector `v` to the first `o` elements of `out`, skipping
+// elements equal to their predecessor, and return the new length.
+#[inline(always)]
+fn push_unique_vector<T>(out: &mut [T], o: usize, v: T::Vector) -> usize
+    where T : Packable + Default + PartialEq,
+          T::Vector : Eq<Out = T::Vector> {
+    // The first lane is compared with itself here, and handled separately
+    let prev = permute(v, |i| if i == 0 { 0 } else { i - 1 });
+    let o = push_unique(out, o, v.extract(0));
+    o + compact(v, v.ne_mask(prev), true, &mut out[o..])
+}
+
+/// Write the elements present in both `a` and `b` to `out`, returning the
+/// number of elements written.
+///
+/// # Panics
+///
+/// Panics if the intersection does not fit in `out`. An `out` as long as the
+/// shorter of `a` and `b` is always sufficient.
+///
+/// # Examples
+///
+/// ```
+/// extern crate faster;
+/// use faster::*;
+/// use faster::set::intersect;
+///
+/// # fn main() {
+/// let mut out = [0u32; 5];
+/// let len = intersect(&[1, 3, 5, 7, 9], &[2, 3, 4, 5, 6, 9, 10], &mut out);
+/// assert_eq!(&out[..len], &[3, 5, 9]);
+/// # }
+/// ```
+pub fn intersect<T>(a: &[T], b: &[T], out: &mut [T]) -> usize
+    where T : Packable + Ord + Default,
+          T::Vector : Eq<Out = T::Vector> + BitOr<Output = T::Vector> {
+    let width = T::Vector::WIDTH;
+    let (mut i, mut j, mut o) = (0, 0, 0);
+
+    while i + width <= a.len() && j + width <= b.len() {
+        let va = T::Vector::load(a, i);
+        let vb = T::Vector::load(b, j);
+        o += compact(va, matches(va, vb), true, &mut out[o..]);
+
+        // Advance whichever block cannot match any later elements
+        let (amax, bmax) = (a[i + width - 1], b[j + width - 1]);
+        if amax <= bmax { i += width; }
+        if bmax <= amax { j += width; }
+    }
+
+    while i < a.len() && j < b.len() {
+        if a[i] < b[j] {
+            i += 1;
+        } else if b[j] < a[i] {
+            j += 1;
+        } else {
+            out[o] = a[i];
+            o += 1;
+            i += 1;
+            j += 1;
+        }
+    }
+    o
+}
+
+/// Write the elements present in `a` but not `b` to `out`, returning the
+/// number of elements written.
+///
+/// # Panics
+///
+/// Panics if the difference does not fit in `out`. An `out` as long as `a` is
+/// always sufficient.
+///
+/// # Examples
+///
+/// ```
+/// extern crate faster;
+/// use faster::*;
+/// use faster::set::difference;
+///
+/// # fn main() {
+/// let mut out = [0u32; 5];
+/// let len = difference(&[1, 3, 5, 7, 9], &[2, 3, 4, 5, 6, 9, 10], &mut out);
+/// assert_eq!(&out[..len], &[1, 7]);
+/// # }
+/// ```
+pub fn difference<T>(a: &[T], b: &[T], out: &mut [T]) -> usize
+    where T : Packable + Ord + Default,
+          T::Vector : Eq<Out = T::Vector> + BitOr<Output = T::Vector> {
+    let width = T::Vector::WIDTH;
+    let (mut i, mut j, mut o) = (0, 0, 0);
+    // The elements of the current block of `a` found in `b` so far
+    let mut found = T::Vector::splat(T::default());
+
+    while i + width <= a.len() && j + width <= b.len() {
+        let va = T::Vector::load(a, i);
+        found = found | matches(va, T::Vector::load(b, j));
+
+        let (amax, bmax) = (a[i + width - 1], b[j + width - 1]);
+        if amax <= bmax {
+            o += compact(va, found, false, &mut out[o..]);
+            found = T::Vector::splat(T::default());
+            i += width;
+        }
+        if bmax <= amax { j += width; }
+    }
+
+    for k in i..a.len() {
+        if k - i < width && found.extract(k - i) != T::default() {
+            continue;
+        }
+        while j < b.len() && b[j] < a[k] {
+            j += 1;
+        }
+        if j >= b.len() || b[j] != a[k] {
+            out[o] = a[k];
+            o += 1;
+        }
+    }
+    o
+}
+
+/// Write the elements present in either `a` or `b` to `out`, returning the
+/// number of elements written.
+///
+/// # Panics
+///
+/// Panics if the union does not fit in `out`. An `out` as long as `a` and `b`
+/// combined is always sufficient.
+///
+/// # Examples
+///
+/// ```
+/// extern crate faster;
+/// use faster::*;
+/// use faster::set::union;
+///
+/// # fn main() {
+/// let mut out = [0u32; 12];
+/// let len = union(&[1, 3, 5, 7, 9], &[2, 3, 4, 5, 6, 9, 10], &mut out);
+/// assert_eq!(&out[..len], &[1, 2, 3, 4, 5, 6, 7, 9, 10]);
+/// # }
+/// ```
+pub fn union<T>(a: &[T], b: &[T], out: &mut [T]) -> usize
+    where T : Packable + Ord + Default,
+          T::Vector : Eq<Out = T::Vector> + SortLanes {
+    let width = T::Vector::WIDTH;
+    let (mut i, mut j, mut o) = (0, 0, 0);
+    // A vector of elements which are merged but not yet written, of which
+    // the first `p` have been written
+    let (mut pending, mut p) = (T::Vector::splat(T::default()), width);
+
+    if a.len() >= width && b.len() >= width {
+        let (lo, hi) = T::Vector::load(a, 0).merge_sorted(T::Vector::load(b, 0));
+        o = push_unique_vector(out, o, lo);
+        pending = hi;
+        i = width;
+        j = width;
+
+        while i + width <= a.len() && j + width <= b.len() {
+            // Take the next block from whichever input has the smaller head
+            let next = if a[i] <= b[j] {
+                i += width;
+                T::Vector::load(a, i - width)
+            } else {
+                j += width;
+                T::Vector::load(b, j - width)
+            };
+            let (lo, hi) = pending.merge_sorted(next);
+            o = push_unique_vector(out, o, lo);
+            pending = hi;
+        }
+        p = 0;
+    }
+
+    while p < width || i < a.len() || j < b.len() {
+        let x = if p < width
+            && (i >= a.len() || pending.extract(p) <= a[i])
+            && (j >= b.len() || pending.extract(p) <= b[j]) {
+            p += 1;
+            pending.extract(p - 1)
+        } else if i < a.len() && (j >= b.len() || a[i] <= b[j]) {
+            i += 1;
+            a[i - 1]
+        } else {
+            j += 1;
+            b[j - 1]
+        };
+        o = push_unique(out, o, x);
+    }
+    o
+}
+
+/// Write the elements of `data`, which must be sorted in ascending order, to
+/// `out`, omitting elements equal to their predecessor. Return the number of
+/// elements written.
+///
+/// # Panics
+///
+/// Panics if `out` is shorter than the deduplicated `data`.
+///
+/// # Examples
+///
+/// ```
+/// extern crate faster;
+/// use faster::*;
+/// use faster::set::dedup_sorted;
+///
+/// # fn main() {
+/// let mut out = [0u64; 9];
+/// let len = dedup_sorted(&[1, 1, 2, 3, 3, 3, 8, 9, 9], &mut out);
+/// assert_eq!(&out[..len], &[1, 2, 3, 8, 9]);
+/// # }
+/// ```
+pub fn dedup_sorted<T>(data: &[T], out: &mut [T]) -> usize
+    where T : Packable + PartialEq + Default,
+          T::Vector : Eq<Out = T::Vector> {
+    let width = T::Vector::WIDTH;
+    let (mut i, mut o) = (0, 0);
+
+    while i + width <= data.len() {
+        o = push_unique_vector(out, o, T::Vector::load(data, i));
+        i += width;
+    }
+    for &x in &data[i..] {
+        o = push_unique(out, o, x);
+    }
+    o
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+    use crate::test_util::rng;
+
+    macro_rules! test_set {
+        ($el:tt, $name:ident) => {
+            #[test]
+            fn $name() {
+                let mut seed = 0x9E3779B97F4A7C15;
+
+                for &(alen, blen, range) in [(0, 0, 8), (0, 10, 8), (3, 40, 64), (40, 3, 64),
+                                             (100, 100, 150), (100, 100, 1000), (257, 1000, 2000),
+                                             (1000, 1000, 1100), (500, 2000, 100000)].iter() {
+                    let a = (0..alen).map(|_| (rng(&mut seed) % range) as $el).collect::<BTreeSet<$el>>();
+                    let b = (0..blen).map(|_| (rng(&mut seed) % range) as $el).collect::<BTreeSet<$el>>();
+                    let (av, bv) = (a.iter().cloned().collect::<Vec<_>>(), b.iter().cloned().collect::<Vec<_>>());
+                    let mut out = vec![0 as $el; av.len() + bv.len()];
+
+                    let len = intersect(&av, &bv, &mut out);
+                    assert_eq!(&out[..len], &a.intersection(&b).cloned().collect::<Vec<_>>()[..]);
+                    let len = intersect(&bv, &av, &mut out);
+                    assert_eq!(&out[..len], &a.intersection(&b).cloned().collect::<Vec<_>>()[..]);
+
+                    let len = difference(&av, &bv, &mut out);
+                    assert_eq!(&out[..len], &a.difference(&b).cloned().collect::<Vec<_>>()[..]);
+                    let len = difference(&bv, &av, &mut out);
+                    assert_eq!(&out[..len], &b.difference(&a).cloned().collect::<Vec<_>>()[..]);
+
+                    let len = union(&av, &bv, &mut out);
+                    assert_eq!(&out[..len], &a.union(&b).cloned().collect::<Vec<_>>()[..]);
+
+                    let mut dup = av.iter().chain(bv.iter()).cloned().collect::<Vec<_>>();
+                    dup.sort();
+                    let mut expected = dup.clone();
+                    expected.dedup();
+                    let len = dedup_sorted(&dup, &mut out);
+                    assert_eq!(&out[..len], &expected[..]);
+                }
+            }
+        }
+    }
+
+    test_set!(u32, set_u32);
+    test_set!(u64, set_u64);
+
+    #[test]
+    fn intersect_identical() {
+        let a = (0..1000u32).map(|x| x * 3).collect::<Vec<_>>();
+        let mut out = vec![0u32; 1000];
+        assert_eq!(intersect(&a, &a, &mut out), 1000);
+        assert_eq!(out, a);
+        assert_eq!(difference(&a, &a, &mut out), 0);
+        assert_eq!(union(&a, &a, &mut out), 1000);
+        assert_eq!(out, a);
+    }
+}
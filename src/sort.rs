@@ -0,0 +1,296 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Sorting networks for vectors, and sorting of slices built upon them.
+//!
+//! The networks are bitonic, and only require [`Cmp`] and lane permutations,
+//! so they are available for every vector type with a `min` and `max`. The
+//! order of NaNs within a sorted floating-point vector or slice is
+//! unspecified.
+//!
+//! [`Cmp`]: ../intrin/cmp/trait.Cmp.html
+
+use crate::vecs::*;
+use crate::intrin::cmp::*;
+use crate::intrin::shuffle::{blend, permute};
+
+/// Sorting networks over the elements of a single vector.
+pub trait SortLanes : Packed + Cmp {
+    /// Return a vector containing the elements of `self` in ascending order.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// assert_eq!(i32s::interleave(3, -1).sort_lanes(), i32s::halfs(-1, 3));
+    /// # }
+    /// ```
+    fn sort_lanes(&self) -> Self;
+
+    /// Merge `self` and `other`, which must each be sorted in ascending
+    /// order, returning a vector containing the smallest `Self::WIDTH`
+    /// elements and a vector containing the largest `Self::WIDTH` elements.
+    /// Both returned vectors are sorted in ascending order.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// assert_eq!(u32s(4).merge_sorted(u32s(2)), (u32s(2), u32s(4)));
+    /// # }
+    /// ```
+    fn merge_sorted(&self, other: Self) -> (Self, Self);
+}
+
+// Perform one step of a bitonic network, in which each element is compared
+// with the element `dist` lanes away. Elements in blocks of `block` lanes are
+// sorted in alternating directions, starting with ascending.
+#[inline(always)]
+fn bitonic_step<V>(v: V, dist: usize, block: usize) -> V where V : Packed + Cmp {
+    let partner = permute(v, |i| i ^ dist);
+    let (lo, hi) = (v.min(partner), v.max(partner));
+    blend(lo, hi, |i| ((i & dist) == 0) == ((i & block) == 0))
+}
+
+// Sort a bitonic sequence in ascending order.
+#[inline(always)]
+fn bitonic_clean<V>(mut v: V) -> V where V : Packed + Cmp {
+    let mut dist = V::WIDTH / 2;
+    while dist > 0 {
+        v = bitonic_step(v, dist, V::WIDTH * 2);
+        dist /= 2;
+    }
+    v
+}
+
+impl<T> SortLanes for T where T : Packed + Cmp {
+    #[inline(always)]
+    fn sort_lanes(&self) -> Self {
+        let mut v = *self;
+        let mut block = 2;
+        while block <= Self::WIDTH {
+            let mut dist = block / 2;
+            while dist > 0 {
+                v = bitonic_step(v, dist, block);
+                dist /= 2;
+            }
+            block *= 2;
+        }
+        v
+    }
+
+    #[inline(always)]
+    fn merge_sorted(&self, other: Self) -> (Self, Self) {
+        // Reversing `other` makes the concatenation of the vectors bitonic
+        let rev = permute(other, |i| Self::WIDTH - 1 - i);
+        (bitonic_clean(self.min(rev)), bitonic_clean(self.max(rev)))
+    }
+}
+
+/// Sort `data` in ascending order, using `buf` as scratch space. `buf` must
+/// be at least as long as `data`. This sort is not stable.
+///
+/// # Panics
+///
+/// Panics if `buf` is shorter than `data`.
+///
+/// # Examples
+///
+/// ```
+/// extern crate faster;
+/// use faster::*;
+///
+/// # fn main() {
+/// let mut data = [5u32, 3, 9, 1, 0, 7, 2, 2, 8, 6, 4];
+/// let mut buf = [0u32; 11];
+/// simd_sort_with_buffer(&mut data, &mut buf);
+/// assert_eq!(data, [0, 1, 2, 2, 3, 4, 5, 6, 7, 8, 9]);
+/// # }
+/// ```
+pub fn simd_sort_with_buffer<T>(data: &mut [T], buf: &mut [T])
+    where T : Packable + PartialOrd, T::Vector : SortLanes {
+    assert!(buf.len() >= data.len());
+    let width = T::Vector::WIDTH;
+    let len = data.len();
+    let body = len - len % width;
+
+    for i in (0..body).step_by(width) {
+        T::Vector::load(data, i).sort_lanes().store(data, i);
+    }
+    insertion_sort(&mut data[body..]);
+
+    // Merge runs of vectors bottom-up, bouncing between `data` and `buf`
+    let mut run = width;
+    let mut in_buf = false;
+    while run < body {
+        {
+            let (src, dst) = if in_buf { (&buf[..body], &mut data[..body]) }
+                             else { (&data[..body], &mut buf[..body]) };
+            for start in (0..body).step_by(run * 2) {
+                let mid = (start + run).min(body);
+                let end = (start + run * 2).min(body);
+                merge_runs(&src[start..mid], &src[mid..end], &mut dst[start..end]);
+            }
+        }
+        in_buf = !in_buf;
+        run *= 2;
+    }
+    if in_buf {
+        data[..body].copy_from_slice(&buf[..body]);
+    }
+
+    if body > 0 && body < len {
+        merge_scalar(&data[..body], &data[body..], &mut buf[..len]);
+        data.copy_from_slice(&buf[..len]);
+    }
+}
+
+/// Sort `data` in ascending order. This sort is not stable, and allocates a
+/// buffer as large as `data`.
+///
+/// # Examples
+///
+/// ```
+/// extern crate faster;
+/// use faster::*;
+///
+/// # #[cfg(not(feature = "std"))]
+/// # fn main() { }
+///
+/// # #[cfg(feature = "std")]
+/// # fn main() {
+/// let mut data = vec![2.5f32, -1.0, 8.0, 0.0, 3.25];
+/// simd_sort(&mut data);
+/// assert_eq!(data, vec![-1.0, 0.0, 2.5, 3.25, 8.0]);
+/// # }
+/// ```
+#[cfg(feature = "std")]
+pub fn simd_sort<T>(data: &mut [T])
+    where T : Packable + PartialOrd, T::Vector : SortLanes {
+    let mut buf = data.to_vec();
+    simd_sort_with_buffer(data, &mut buf);
+}
+
+// Merge two sorted runs whose lengths are multiples of the vector width into
+// `out`, one vector at a time.
+#[inline(always)]
+fn merge_runs<T>(a: &[T], b: &[T], out: &mut [T])
+    where T : Packable + PartialOrd, T::Vector : SortLanes {
+    let width = T::Vector::WIDTH;
+    if b.is_empty() {
+        out.copy_from_slice(a);
+        return;
+    }
+
+    let (lo, mut hi) = T::Vector::load(a, 0).merge_sorted(T::Vector::load(b, 0));
+    lo.store(out, 0);
+    let (mut ia, mut ib, mut o) = (width, width, width);
+
+    while ia < a.len() || ib < b.len() {
+        // Take the next vector from whichever run has the smaller head
+        let next = if ib >= b.len() || (ia < a.len() && a[ia] <= b[ib]) {
+            ia += width;
+            T::Vector::load(a, ia - width)
+        } else {
+            ib += width;
+            T::Vector::load(b, ib - width)
+        };
+        let (lo, h) = hi.merge_sorted(next);
+        lo.store(out, o);
+        hi = h;
+        o += width;
+    }
+    hi.store(out, o);
+}
+
+fn merge_scalar<T>(a: &[T], b: &[T], out: &mut [T]) where T : Copy + PartialOrd {
+    let (mut ia, mut ib) = (0, 0);
+    for o in out.iter_mut() {
+        if ib >= b.len() || (ia < a.len() && a[ia] <= b[ib]) {
+            *o = a[ia];
+            ia += 1;
+        } else {
+            *o = b[ib];
+            ib += 1;
+        }
+    }
+}
+
+fn insertion_sort<T>(data: &mut [T]) where T : Copy + PartialOrd {
+    for i in 1..data.len() {
+        let x = data[i];
+        let mut j = i;
+        while j > 0 && data[j - 1] > x {
+            data[j] = data[j - 1];
+            j -= 1;
+        }
+        data[j] = x;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::test_util::rng;
+
+    macro_rules! test_sort {
+        ($vec:tt, $el:tt, $conv:expr, $cmp:expr, $name:ident) => {
+            #[test]
+            fn $name() {
+                let mut seed = 0x2545F4914F6CDD1D;
+
+                for _ in 0..64 {
+                    let mut v = $vec($conv(0));
+                    for i in 0..v.width() {
+                        v = v.replace(i, $conv(rng(&mut seed)));
+                    }
+                    let mut expected = vec![v.extract(0); v.width()];
+                    v.store(&mut expected, 0);
+                    expected.sort_unstable_by($cmp);
+                    assert_eq!(v.sort_lanes(), $vec::load(&expected, 0));
+                }
+
+                for len in (0..100).chain(vec![255, 256, 257, 1000, 4096]) {
+                    let mut data = (0..len).map(|_| $conv(rng(&mut seed) % 512)).collect::<Vec<$el>>();
+                    let mut expected = data.clone();
+                    expected.sort_unstable_by($cmp);
+                    simd_sort(&mut data);
+                    assert_eq!(data, expected);
+                }
+            }
+        }
+    }
+
+    test_sort!(u32s, u32, |x: u64| x as u32, |a: &u32, b: &u32| a.cmp(b), sort_u32s);
+    test_sort!(i32s, i32, |x: u64| x as i32, |a: &i32, b: &i32| a.cmp(b), sort_i32s);
+    test_sort!(u64s, u64, |x: u64| x, |a: &u64, b: &u64| a.cmp(b), sort_u64s);
+    test_sort!(f32s, f32, |x: u64| x as i32 as f32 / 7.0, |a: &f32, b: &f32| a.partial_cmp(b).unwrap(), sort_f32s);
+    test_sort!(f64s, f64, |x: u64| x as i64 as f64 / 7.0, |a: &f64, b: &f64| a.partial_cmp(b).unwrap(), sort_f64s);
+
+    #[test]
+    fn merge_sorted_u32s() {
+        let a = u32s::interleave(1, 3).sort_lanes();
+        let b = u32s::interleave(2, 4).sort_lanes();
+        let (lo, hi) = a.merge_sorted(b);
+        let mut out = vec![0u32; u32s::WIDTH * 2];
+        lo.store(&mut out, 0);
+        hi.store(&mut out, u32s::WIDTH);
+        let mut expected = out.clone();
+        expected.sort_unstable();
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn sort_with_buffer_presorted() {
+        let mut data = (0..300u32).rev().collect::<Vec<u32>>();
+        let mut buf = vec![0u32; 300];
+        simd_sort_with_buffer(&mut data, &mut buf);
+        assert_eq!(data, (0..300u32).collect::<Vec<u32>>());
+    }
+}
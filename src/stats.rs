@@ -0,0 +1,371 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Summary statistics of slices of floats, computed in one pass.
+//!
+//! Each lane of a vector keeps its own running mean and sum of squared
+//! deviations, updated with Welford's method in `f64`. The lanes are only
+//! combined when a [`Summary`] is read. Combining them in a different order
+//! changes the results only by rounding, and two [`Accumulator`]s over
+//! different data can be merged the same way as the lanes.
+//!
+//! [`Summary`]: struct.Summary.html
+//! [`Accumulator`]: struct.Accumulator.html
+
+use crate::arch::current::vecs::*;
+use crate::vecs::*;
+use crate::intrin::cmp::*;
+use crate::intrin::upcast::*;
+use crate::linalg::{Real, RealVector};
+use crate::core::f32::{INFINITY, NEG_INFINITY};
+
+/// The statistics of a collection of values.
+///
+/// For an empty collection, the mean and variance are NaN, the minimum is
+/// infinity and the maximum is negative infinity.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Summary {
+    /// The number of values.
+    pub count: u64,
+    /// The arithmetic mean of the values.
+    pub mean: f64,
+    /// The population variance of the values.
+    pub variance: f64,
+    /// The smallest value.
+    pub min: f32,
+    /// The largest value.
+    pub max: f32,
+}
+
+impl Summary {
+    /// Return the sample variance of the values, which divides by one fewer
+    /// than their number.
+    pub fn sample_variance(&self) -> f64 {
+        self.variance * self.count as f64 / (self.count as f64 - 1.0)
+    }
+
+    /// Return the population standard deviation of the values.
+    #[cfg(feature = "std")]
+    pub fn std_dev(&self) -> f64 {
+        self.variance.sqrt()
+    }
+}
+
+// The running statistics of one stream of values.
+#[derive(Clone, Copy, Debug)]
+struct Moments {
+    count: u64,
+    mean: f64,
+    m2: f64,
+    min: f32,
+    max: f32,
+}
+
+impl Moments {
+    const EMPTY: Moments = Moments {
+        count: 0,
+        mean: 0.0,
+        m2: 0.0,
+        min: INFINITY,
+        max: NEG_INFINITY,
+    };
+
+    #[inline(always)]
+    fn push(&mut self, x: f32) {
+        self.count += 1;
+        let delta = f64::from(x) - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (f64::from(x) - self.mean);
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
+    }
+
+    // Combine these statistics with those of other values, with the pairwise
+    // update of Chan et al.
+    #[inline(always)]
+    fn merge(&mut self, other: &Moments) {
+        if other.count > 0 {
+            let count = self.count + other.count;
+            let delta = other.mean - self.mean;
+            self.mean += delta * (other.count as f64 / count as f64);
+            self.m2 += other.m2 + delta * delta * (self.count as f64 * other.count as f64 / count as f64);
+            self.count = count;
+        }
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+}
+
+/// A running computation of the [`Summary`] of a stream of `f32`s, which
+/// may be fed in slices of any length.
+///
+/// ```
+/// extern crate faster;
+/// use faster::stats::Accumulator;
+///
+/// # fn main() {
+/// let (mut a, mut b) = (Accumulator::new(), Accumulator::new());
+/// a.update(&[2.0, 4.0, 4.0, 4.0]);
+/// b.update(&[5.0, 5.0, 7.0, 9.0]);
+/// a.merge(&b);
+/// let summary = a.summary();
+/// assert_eq!((summary.count, summary.min, summary.max), (8, 2.0, 9.0));
+/// assert!((summary.mean - 5.0).abs() < 1e-12);
+/// assert!((summary.variance - 4.0).abs() < 1e-12);
+/// # }
+/// ```
+///
+/// [`Summary`]: struct.Summary.html
+#[derive(Clone, Copy, Debug)]
+pub struct Accumulator {
+    // The number of values seen by each lane
+    count: u64,
+    // The statistics of each lane, with the lanes of an f32s split in halves
+    mean: [f64s; 2],
+    m2: [f64s; 2],
+    min: f32s,
+    max: f32s,
+    // The statistics of the values which didn't fill a vector
+    rest: Moments,
+}
+
+impl Default for Accumulator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Accumulator {
+    /// Return an accumulator which hasn't seen any values.
+    pub fn new() -> Self {
+        Accumulator {
+            count: 0,
+            mean: [f64s(0.0); 2],
+            m2: [f64s(0.0); 2],
+            min: f32s(INFINITY),
+            max: f32s(NEG_INFINITY),
+            rest: Moments::EMPTY,
+        }
+    }
+
+    /// Add the values of `data` to the stream.
+    pub fn update(&mut self, data: &[f32]) {
+        let width = f32s::WIDTH;
+        let mut i = 0;
+        while i + width <= data.len() {
+            let v = f32s::load(data, i);
+            let (lo, hi): (f64s, f64s) = v.upcast();
+            self.count += 1;
+            let count = f64s(self.count as f64);
+            for (x, (mean, m2)) in [lo, hi].iter().zip(self.mean.iter_mut().zip(self.m2.iter_mut())) {
+                let delta = *x - *mean;
+                *mean = *mean + delta / count;
+                *m2 = *m2 + delta * (*x - *mean);
+            }
+            self.min = Cmp::min(&self.min, v);
+            self.max = Cmp::max(&self.max, v);
+            i += width;
+        }
+        for &x in &data[i..] {
+            self.rest.push(x);
+        }
+    }
+
+    /// Add the values seen by `other` to the stream, as if they had been
+    /// passed to `update`.
+    pub fn merge(&mut self, other: &Accumulator) {
+        if other.count > 0 {
+            let count = self.count + other.count;
+            let weight = f64s(other.count as f64 / count as f64);
+            let cross = f64s(self.count as f64 * other.count as f64 / count as f64);
+            for i in 0..2 {
+                let delta = other.mean[i] - self.mean[i];
+                self.mean[i] = self.mean[i] + delta * weight;
+                self.m2[i] = self.m2[i] + other.m2[i] + delta * delta * cross;
+            }
+            self.count = count;
+        }
+        self.min = Cmp::min(&self.min, other.min);
+        self.max = Cmp::max(&self.max, other.max);
+        self.rest.merge(&other.rest);
+    }
+
+    /// Return the statistics of every value seen so far.
+    pub fn summary(&self) -> Summary {
+        let half = f64s::WIDTH;
+        let mut total = self.rest;
+        for lane in 0..f32s::WIDTH {
+            total.merge(&Moments {
+                count: self.count,
+                mean: self.mean[lane / half].extract(lane % half),
+                m2: self.m2[lane / half].extract(lane % half),
+                min: self.min.extract(lane),
+                max: self.max.extract(lane),
+            });
+        }
+
+        let (mean, variance) = if total.count == 0 {
+            (crate::core::f64::NAN, crate::core::f64::NAN)
+        } else {
+            (total.mean, total.m2 / total.count as f64)
+        };
+        Summary { count: total.count, mean, variance, min: total.min, max: total.max }
+    }
+}
+
+/// Return the count, mean, variance, minimum and maximum of `data`.
+///
+/// ```
+/// extern crate faster;
+/// use faster::stats::describe;
+///
+/// # fn main() {
+/// let summary = describe(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+/// assert_eq!((summary.count, summary.min, summary.max), (5, 1.0, 5.0));
+/// assert!((summary.mean - 3.0).abs() < 1e-12);
+/// assert!((summary.variance - 2.0).abs() < 1e-12);
+/// assert!((summary.sample_variance() - 2.5).abs() < 1e-12);
+/// # }
+/// ```
+pub fn describe(data: &[f32]) -> Summary {
+    let mut acc = Accumulator::new();
+    acc.update(data);
+    acc.summary()
+}
+
+/// Return the sum of `data`, with Kahan's compensation for the low bits
+/// lost by each addition.
+///
+/// Each lane of a vector sums its own elements with compensation, and the
+/// lanes and their compensations are summed the same way at the end, so the
+/// error doesn't grow with the length of `data` as it does for a plain sum.
+///
+/// ```
+/// extern crate faster;
+/// use faster::stats::sum_compensated;
+///
+/// # fn main() {
+/// let mut data = vec![1.0f32; 10001];
+/// data[0] = 1e8;
+/// assert_eq!(sum_compensated(&data), 1e8 + 10000.0);
+/// assert_ne!(data.iter().sum::<f32>(), 1e8 + 10000.0);
+/// # }
+/// ```
+pub fn sum_compensated<T>(data: &[T]) -> T where T : Real, T::Vector : RealVector {
+    let width = T::Vector::WIDTH;
+    let (mut sum, mut comp) = (T::Vector::splat(T::ZERO), T::Vector::splat(T::ZERO));
+    let mut i = 0;
+    while i + width <= data.len() {
+        let y = T::Vector::load(data, i) - comp;
+        let t = sum + y;
+        comp = (t - sum) - y;
+        sum = t;
+        i += width;
+    }
+
+    let (mut total, mut c) = (T::ZERO, T::ZERO);
+    let mut add = |x: T| {
+        let y = x - c;
+        let t = total + y;
+        c = (t - total) - y;
+        total = t;
+    };
+    for lane in 0..width {
+        add(sum.extract(lane));
+        add(T::ZERO - comp.extract(lane));
+    }
+    for &x in &data[i..] {
+        add(x);
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::rng;
+
+    // Return the mean and population variance of `data`, in two passes
+    fn reference(data: &[f32]) -> (f64, f64) {
+        let n = data.len() as f64;
+        let mean = data.iter().map(|&x| f64::from(x)).sum::<f64>() / n;
+        (mean, data.iter().map(|&x| (f64::from(x) - mean) * (f64::from(x) - mean)).sum::<f64>() / n)
+    }
+
+    fn random(seed: &mut u64, len: usize) -> Vec<f32> {
+        (0..len).map(|_| (rng(seed) % 100000) as f32 / 7.0 + 1e4).collect()
+    }
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() <= 1e-9 * b.abs().max(1.0), "{} != {}", a, b);
+    }
+
+    #[test]
+    fn describe_matches_reference() {
+        let mut seed = 0x9E3779B97F4A7C15;
+        for len in (1..70).chain(vec![1000, 4099]) {
+            let data = random(&mut seed, len);
+            let summary = describe(&data);
+            let (mean, variance) = reference(&data);
+            assert_eq!(summary.count, len as u64);
+            assert_close(summary.mean, mean);
+            assert_close(summary.variance, variance);
+            assert_eq!(summary.min, data.iter().cloned().fold(::std::f32::INFINITY, f32::min));
+            assert_eq!(summary.max, data.iter().cloned().fold(::std::f32::NEG_INFINITY, f32::max));
+        }
+
+        let empty = describe(&[]);
+        assert_eq!(empty.count, 0);
+        assert!(empty.mean.is_nan() && empty.variance.is_nan());
+        assert_eq!((empty.min, empty.max), (::std::f32::INFINITY, ::std::f32::NEG_INFINITY));
+    }
+
+    #[test]
+    fn accumulators_merge() {
+        let mut seed = 0xDA942042E4DD58B5;
+        let data = random(&mut seed, 3000);
+        let (mean, variance) = reference(&data);
+        let whole = describe(&data);
+
+        // Slices of awkward lengths, some fed to the same accumulator
+        let mut accs = vec![Accumulator::new(); 5];
+        let mut pos = 0;
+        for k in 0.. {
+            let end = (pos + k * 13 % 101).min(data.len());
+            accs[k % 5].update(&data[pos..end]);
+            pos = end;
+            if pos == data.len() {
+                break;
+            }
+        }
+        let mut merged = Accumulator::default();
+        for acc in &accs {
+            merged.merge(acc);
+        }
+        let summary = merged.summary();
+        assert_eq!(summary.count, whole.count);
+        assert_close(summary.mean, mean);
+        assert_close(summary.variance, variance);
+        assert_eq!((summary.min, summary.max), (whole.min, whole.max));
+    }
+
+    #[test]
+    fn sum_compensated_is_accurate() {
+        let mut seed = 0x2545F4914F6CDD1D;
+        for len in 0..200 {
+            let data = (0..len).map(|_| (rng(&mut seed) % 2000000) as f32 / 1000.0 - 1000.0).collect::<Vec<_>>();
+            let exact = data.iter().map(|&x| f64::from(x)).sum::<f64>();
+            let sum = sum_compensated(&data);
+            assert!((f64::from(sum) - exact).abs() <= 2.0 * f64::from(::std::f32::EPSILON) * exact.abs().max(1.0), "{} {}", sum, exact);
+        }
+
+        // Each 1 is too small to change 2^53 on its own
+        let mut data = vec![1.0f64; 65];
+        data[0] = 9007199254740992.0;
+        assert_eq!(sum_compensated(&data), 9007199254741056.0);
+    }
+}
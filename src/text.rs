@@ -0,0 +1,821 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Validation and processing of text.
+//!
+//! The ASCII helpers compare every lane against the ranges of a character
+//! class at once, and don't need the standard library.
+
+use crate::arch::current::vecs::*;
+use crate::core::fmt;
+use crate::core::ops::BitOr;
+use crate::vecs::*;
+use crate::iters::*;
+use crate::into_iters::*;
+use crate::zip::*;
+use crate::intrin::cmp::*;
+use crate::intrin::eq::*;
+use crate::intrin::lookup::*;
+use crate::intrin::movemask::*;
+use crate::intrin::saturating_sub::*;
+
+// Return a mask of the lanes of `v` between `lo` and `hi`, inclusive.
+#[inline(always)]
+pub(crate) fn in_range(v: u8s, lo: u8, hi: u8) -> u8s {
+    Cmp::min(&Cmp::max(&v, u8s(lo)), u8s(hi)).eq_mask(v)
+}
+
+/// A set of ASCII character classes, which may be combined with `|`.
+///
+/// ```
+/// extern crate faster;
+/// use faster::*;
+/// use faster::text::CharClass;
+///
+/// # fn main() {
+/// let class = CharClass::DIGIT | CharClass::WHITESPACE;
+/// assert!(class.matches(b'7') && class.matches(b'\n') && !class.matches(b'x'));
+/// assert_eq!(class.mask(u8s(b' ')), u8s(0xFF));
+/// assert_eq!(class.mask(u8s(b'.')), u8s(0));
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CharClass(u8);
+
+impl CharClass {
+    /// The digits `0` to `9`.
+    pub const DIGIT: CharClass = CharClass(1 << 0);
+    /// The letters `A` to `Z`.
+    pub const UPPER: CharClass = CharClass(1 << 1);
+    /// The letters `a` to `z`.
+    pub const LOWER: CharClass = CharClass(1 << 2);
+    /// The whitespace of `u8::is_ascii_whitespace`: space, tab, line feed,
+    /// form feed and carriage return.
+    pub const WHITESPACE: CharClass = CharClass(1 << 3);
+    /// The letters in either case.
+    pub const ALPHA: CharClass = CharClass(1 << 1 | 1 << 2);
+    /// The letters in either case and the digits.
+    pub const ALPHANUMERIC: CharClass = CharClass(1 << 0 | 1 << 1 | 1 << 2);
+
+    /// Return whether every class in `other` is also in `self`.
+    #[inline(always)]
+    pub fn contains(self, other: CharClass) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Return whether `b` belongs to any class in `self`.
+    #[inline(always)]
+    pub fn matches(self, b: u8) -> bool {
+        (self.contains(CharClass::DIGIT) && b.is_ascii_digit())
+            || (self.contains(CharClass::UPPER) && b.is_ascii_uppercase())
+            || (self.contains(CharClass::LOWER) && b.is_ascii_lowercase())
+            || (self.contains(CharClass::WHITESPACE) && b.is_ascii_whitespace())
+    }
+
+    /// Return a vector which is all ones in the lanes of `v` which belong to
+    /// any class in `self`, and zero elsewhere.
+    #[inline(always)]
+    pub fn mask(self, v: u8s) -> u8s {
+        let mut mask = u8s(0);
+        if self.contains(CharClass::DIGIT) {
+            mask = mask | in_range(v, b'0', b'9');
+        }
+        if self.contains(CharClass::ALPHA) {
+            // Setting 0x20 folds the uppercase letters onto the lowercase
+            mask = mask | in_range(v | u8s(0x20), b'a', b'z');
+        } else if self.contains(CharClass::UPPER) {
+            mask = mask | in_range(v, b'A', b'Z');
+        } else if self.contains(CharClass::LOWER) {
+            mask = mask | in_range(v, b'a', b'z');
+        }
+        if self.contains(CharClass::WHITESPACE) {
+            mask = mask | v.eq_mask(u8s(b' ')) | v.eq_mask(u8s(b'\t')) | v.eq_mask(u8s(b'\n'))
+                | v.eq_mask(u8s(0x0C)) | v.eq_mask(u8s(b'\r'));
+        }
+        mask
+    }
+}
+
+impl BitOr for CharClass {
+    type Output = CharClass;
+
+    #[inline(always)]
+    fn bitor(self, other: CharClass) -> CharClass {
+        CharClass(self.0 | other.0)
+    }
+}
+
+/// Return the number of bytes in `data` which belong to any class in
+/// `class`.
+///
+/// ```
+/// extern crate faster;
+/// use faster::text::{count_class, CharClass};
+///
+/// # fn main() {
+/// assert_eq!(count_class(b"faster 0.6.0", CharClass::DIGIT), 3);
+/// # }
+/// ```
+pub fn count_class(data: &[u8], class: CharClass) -> usize {
+    // Padding lanes are zero, which belongs to no class
+    data.simd_iter(u8s(0))
+        .simd_reduce(0, |acc, v| acc + class.mask(v).movemask().count_ones() as usize)
+}
+
+/// Return the number of ASCII whitespace bytes in `data`.
+///
+/// ```
+/// extern crate faster;
+/// use faster::text::count_whitespace;
+///
+/// # fn main() {
+/// assert_eq!(count_whitespace(b" a\tb\r\n"), 4);
+/// # }
+/// ```
+pub fn count_whitespace(data: &[u8]) -> usize {
+    count_class(data, CharClass::WHITESPACE)
+}
+
+/// Return whether every byte in `data` is ASCII.
+///
+/// ```
+/// extern crate faster;
+/// use faster::text::is_ascii;
+///
+/// # fn main() {
+/// assert!(is_ascii(b"faster"));
+/// assert!(!is_ascii("fäster".as_bytes()));
+/// # }
+/// ```
+pub fn is_ascii(data: &[u8]) -> bool {
+    data.simd_iter(u8s(0))
+        .simd_reduce(u8s(0), |acc, v| acc | v)
+        .movemask() == 0
+}
+
+/// Convert every lowercase ASCII letter in `data` to uppercase, leaving all
+/// other bytes unchanged.
+///
+/// ```
+/// extern crate faster;
+/// use faster::text::to_ascii_uppercase_in_place;
+///
+/// # fn main() {
+/// let mut text = *b"Grr, fast!";
+/// to_ascii_uppercase_in_place(&mut text);
+/// assert_eq!(&text, b"GRR, FAST!");
+/// # }
+/// ```
+pub fn to_ascii_uppercase_in_place(data: &mut [u8]) {
+    data.simd_iter_mut(u8s(0))
+        .simd_for_each(|v| *v = *v ^ (in_range(*v, b'a', b'z') & u8s(0x20)));
+}
+
+/// Convert every uppercase ASCII letter in `data` to lowercase, leaving all
+/// other bytes unchanged.
+///
+/// ```
+/// extern crate faster;
+/// use faster::text::to_ascii_lowercase_in_place;
+///
+/// # fn main() {
+/// let mut text = *b"Grr, FAST!";
+/// to_ascii_lowercase_in_place(&mut text);
+/// assert_eq!(&text, b"grr, fast!");
+/// # }
+/// ```
+pub fn to_ascii_lowercase_in_place(data: &mut [u8]) {
+    data.simd_iter_mut(u8s(0))
+        .simd_for_each(|v| *v = *v ^ (in_range(*v, b'A', b'Z') & u8s(0x20)));
+}
+
+/// Return whether `a` and `b` are equal when ASCII letters are compared
+/// without regard to case, like `<[u8]>::eq_ignore_ascii_case`.
+///
+/// ```
+/// extern crate faster;
+/// use faster::text::eq_ignore_ascii_case;
+///
+/// # fn main() {
+/// assert!(eq_ignore_ascii_case(b"Content-Type", b"content-type"));
+/// assert!(!eq_ignore_ascii_case(b"[", b"{"));
+/// # }
+/// ```
+pub fn eq_ignore_ascii_case(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    // Lanes may differ only by 0x20, and only where both are letters
+    let diff = (a.simd_iter(u8s(0)), b.simd_iter(u8s(0))).zip()
+        .simd_reduce(u8s(0), |acc, (x, y)| {
+            let folded = in_range(x | u8s(0x20), b'a', b'z') & u8s(0x20);
+            acc | ((x | folded) ^ (y | folded))
+        });
+    diff == u8s(0)
+}
+
+// Return a mask of the ASCII whitespace in the vector of `data` at `pos`,
+// and the mask of every lane of a vector.
+#[inline(always)]
+fn whitespace_mask(data: &[u8], pos: usize) -> (u32, u32) {
+    let ws = CharClass::WHITESPACE.mask(u8s::load(data, pos)).movemask();
+    (ws, !0 >> (32 - u8s::WIDTH as u32))
+}
+
+/// Return `data` without its leading ASCII whitespace.
+///
+/// ```
+/// extern crate faster;
+/// use faster::text::trim_ascii_whitespace_start;
+///
+/// # fn main() {
+/// assert_eq!(trim_ascii_whitespace_start(b" \t fast \r\n"), b"fast \r\n");
+/// # }
+/// ```
+pub fn trim_ascii_whitespace_start(data: &[u8]) -> &[u8] {
+    let width = u8s::WIDTH;
+    let mut start = 0;
+    while start + width <= data.len() {
+        let (ws, all) = whitespace_mask(data, start);
+        if ws != all {
+            return &data[start + (!ws).trailing_zeros() as usize..];
+        }
+        start += width;
+    }
+    while start < data.len() && data[start].is_ascii_whitespace() {
+        start += 1;
+    }
+    &data[start..]
+}
+
+/// Return `data` without its trailing ASCII whitespace.
+///
+/// ```
+/// extern crate faster;
+/// use faster::text::trim_ascii_whitespace_end;
+///
+/// # fn main() {
+/// assert_eq!(trim_ascii_whitespace_end(b" \t fast \r\n"), b" \t fast");
+/// # }
+/// ```
+pub fn trim_ascii_whitespace_end(data: &[u8]) -> &[u8] {
+    let width = u8s::WIDTH;
+    let mut end = data.len();
+    while end >= width {
+        let (ws, all) = whitespace_mask(data, end - width);
+        if ws != all {
+            // Keep everything up to the last lane which isn't whitespace
+            return &data[..end - width + 32 - (!ws & all).leading_zeros() as usize];
+        }
+        end -= width;
+    }
+    while end > 0 && data[end - 1].is_ascii_whitespace() {
+        end -= 1;
+    }
+    &data[..end]
+}
+
+/// Return `data` without its leading and trailing ASCII whitespace.
+///
+/// ```
+/// extern crate faster;
+/// use faster::text::trim_ascii_whitespace;
+///
+/// # fn main() {
+/// assert_eq!(trim_ascii_whitespace(b" \t fast \r\n"), b"fast");
+/// assert_eq!(trim_ascii_whitespace(b" \n "), b"");
+/// # }
+/// ```
+pub fn trim_ascii_whitespace(data: &[u8]) -> &[u8] {
+    trim_ascii_whitespace_end(trim_ascii_whitespace_start(data))
+}
+
+/// An error returned when a byte slice is not valid UTF-8. It carries the
+/// same information as `core::str::Utf8Error`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Utf8Error {
+    valid_up_to: usize,
+    error_len: Option<u8>,
+}
+
+impl Utf8Error {
+    /// Return the index in the given bytes up to which valid UTF-8 was
+    /// verified.
+    pub fn valid_up_to(&self) -> usize {
+        self.valid_up_to
+    }
+
+    /// Return the length of the invalid byte sequence at `valid_up_to`, or
+    /// `None` if the input ended in the middle of a character.
+    pub fn error_len(&self) -> Option<usize> {
+        self.error_len.map(|len| len as usize)
+    }
+}
+
+impl fmt::Display for Utf8Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.error_len {
+            Some(len) => write!(f, "invalid utf-8 sequence of {} bytes from index {}",
+                                len, self.valid_up_to),
+            None => write!(f, "incomplete utf-8 byte sequence from index {}",
+                           self.valid_up_to),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Utf8Error {}
+
+/// Return the number of lines in `data`, counted like `str::lines`: a line
+/// ends at each `\n`, and a final line without one counts too.
+///
+/// ```
+/// extern crate faster;
+/// use faster::text::count_lines;
+///
+/// # fn main() {
+/// assert_eq!(count_lines(b"one\r\ntwo\nthree"), 3);
+/// assert_eq!(count_lines(b"one\ntwo\n"), 2);
+/// assert_eq!(count_lines(b""), 0);
+/// # }
+/// ```
+pub fn count_lines(data: &[u8]) -> usize {
+    let newlines = data.simd_iter(u8s(0))
+        .simd_reduce(0, |acc, v| acc + v.eq_mask(u8s(b'\n')).movemask().count_ones() as usize);
+    match data.last() {
+        Some(&b'\n') | None => newlines,
+        Some(_) => newlines + 1,
+    }
+}
+
+// Return a mask of the line feeds in the vector of `data` at `pos`, padding
+// it with zeroes if it runs past the end.
+#[inline(always)]
+fn newline_mask(data: &[u8], pos: usize) -> u32 {
+    let v = if pos + u8s::WIDTH <= data.len() {
+        u8s::load(data, pos)
+    } else {
+        let mut padded = [0u8; 64];
+        padded[..data.len() - pos].copy_from_slice(&data[pos..]);
+        u8s::load(&padded, 0)
+    };
+    v.eq_mask(u8s(b'\n')).movemask()
+}
+
+/// An iterator over the offsets at which the lines of a slice begin,
+/// returned by [`line_starts`].
+///
+/// [`line_starts`]: fn.line_starts.html
+#[derive(Clone, Debug)]
+pub struct LineStarts<'a> {
+    data: &'a [u8],
+    // The offset of the vector which `mask` was taken from
+    pos: usize,
+    // The line feeds in that vector which we haven't yet returned
+    mask: u32,
+    started: bool,
+}
+
+/// Return an iterator over the offsets at which the lines of `data` begin,
+/// with lines delimited as in [`count_lines`].
+///
+/// ```
+/// extern crate faster;
+/// use faster::text::line_starts;
+///
+/// # fn main() {
+/// let starts = line_starts(b"one\r\ntwo\n\nfour").collect::<Vec<_>>();
+/// assert_eq!(starts, vec![0, 5, 9, 10]);
+/// # }
+/// ```
+///
+/// [`count_lines`]: fn.count_lines.html
+pub fn line_starts(data: &[u8]) -> LineStarts {
+    LineStarts {
+        data,
+        pos: 0,
+        mask: if data.is_empty() { 0 } else { newline_mask(data, 0) },
+        started: false,
+    }
+}
+
+impl<'a> Iterator for LineStarts<'a> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        if !self.started {
+            self.started = true;
+            if !self.data.is_empty() {
+                return Some(0);
+            }
+        }
+        loop {
+            if self.mask != 0 {
+                let start = self.pos + self.mask.trailing_zeros() as usize + 1;
+                self.mask &= self.mask - 1;
+                // A line feed at the very end doesn't begin a line
+                return if start < self.data.len() { Some(start) } else { None };
+            }
+            self.pos += u8s::WIDTH;
+            if self.pos >= self.data.len() {
+                return None;
+            }
+            self.mask = newline_mask(self.data, self.pos);
+        }
+    }
+}
+
+/// Write the offsets at which the lines of `data` begin into `out`, and
+/// return the number written. Stops early if `out` is full.
+///
+/// ```
+/// extern crate faster;
+/// use faster::text::line_starts_into;
+///
+/// # fn main() {
+/// let mut out = [0; 2];
+/// assert_eq!(line_starts_into(b"a\nb\nc", &mut out), 2);
+/// assert_eq!(out, [0, 2]);
+/// # }
+/// ```
+pub fn line_starts_into(data: &[u8], out: &mut [usize]) -> usize {
+    let mut len = 0;
+    for (o, start) in out.iter_mut().zip(line_starts(data)) {
+        *o = start;
+        len += 1;
+    }
+    len
+}
+
+/// Return the offsets at which the lines of `data` begin.
+#[cfg(feature = "std")]
+pub fn line_starts_vec(data: &[u8]) -> Vec<usize> {
+    let mut ret = Vec::with_capacity(count_lines(data));
+    ret.extend(line_starts(data));
+    ret
+}
+
+/// An iterator over the lines of a slice, returned by [`lines`].
+///
+/// [`lines`]: fn.lines.html
+#[derive(Clone, Debug)]
+pub struct Lines<'a> {
+    data: &'a [u8],
+    starts: LineStarts<'a>,
+    next: Option<usize>,
+}
+
+/// Return an iterator over the lines of `data`, without their trailing `\n`
+/// or `\r\n`, like `str::lines`.
+///
+/// ```
+/// extern crate faster;
+/// use faster::text::lines;
+///
+/// # fn main() {
+/// let lines = lines(b"one\r\ntwo\n\nfour").collect::<Vec<_>>();
+/// assert_eq!(lines, vec![&b"one"[..], b"two", b"", b"four"]);
+/// # }
+/// ```
+pub fn lines(data: &[u8]) -> Lines {
+    let mut starts = line_starts(data);
+    let next = starts.next();
+    Lines { data, starts, next }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = &'a [u8];
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a [u8]> {
+        let start = self.next?;
+        self.next = self.starts.next();
+        let mut end = self.next.unwrap_or(self.data.len());
+        if end > start && self.data[end - 1] == b'\n' {
+            end -= 1;
+            if end > start && self.data[end - 1] == b'\r' {
+                end -= 1;
+            }
+        }
+        Some(&self.data[start..end])
+    }
+}
+
+// The error classes detected by looking at a byte and its predecessor, after
+// "Validating UTF-8 In Less Than One Instruction Per Byte" by John Keiser and
+// Daniel Lemire.
+const TOO_SHORT: u8 = 1 << 0;      // 11______ 0_______ or 11______ 11______
+const TOO_LONG: u8 = 1 << 1;       // 0_______ 10______
+const OVERLONG_3: u8 = 1 << 2;     // 11100000 100_____
+const TOO_LARGE: u8 = 1 << 3;      // 11110100 1001____ or 11110100 101_____
+const SURROGATE: u8 = 1 << 4;      // 11101101 101_____
+const OVERLONG_2: u8 = 1 << 5;     // 1100000_ 10______
+const TOO_LARGE_1000: u8 = 1 << 6; // 11110101 1000____ or 1111011_ 1000____
+const OVERLONG_4: u8 = 1 << 6;     // 11110000 1000____
+const TWO_CONTS: u8 = 1 << 7;      // 10______ 10______
+const CARRY: u8 = TOO_SHORT | TOO_LONG | TWO_CONTS;
+
+// Indexed by the high nibble of the previous byte
+const BYTE_1_HIGH: [u8; 16] = [
+    TOO_LONG, TOO_LONG, TOO_LONG, TOO_LONG,
+    TOO_LONG, TOO_LONG, TOO_LONG, TOO_LONG,
+    TWO_CONTS, TWO_CONTS, TWO_CONTS, TWO_CONTS,
+    TOO_SHORT | OVERLONG_2,
+    TOO_SHORT,
+    TOO_SHORT | OVERLONG_3 | SURROGATE,
+    TOO_SHORT | TOO_LARGE | TOO_LARGE_1000 | OVERLONG_4,
+];
+
+// Indexed by the low nibble of the previous byte
+const BYTE_1_LOW: [u8; 16] = [
+    CARRY | OVERLONG_3 | OVERLONG_2 | OVERLONG_4,
+    CARRY | OVERLONG_2,
+    CARRY,
+    CARRY,
+    CARRY | TOO_LARGE,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000 | SURROGATE,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+];
+
+// Indexed by the high nibble of the current byte
+const BYTE_2_HIGH: [u8; 16] = [
+    TOO_SHORT, TOO_SHORT, TOO_SHORT, TOO_SHORT,
+    TOO_SHORT, TOO_SHORT, TOO_SHORT, TOO_SHORT,
+    TOO_LONG | OVERLONG_2 | TWO_CONTS | OVERLONG_3 | TOO_LARGE_1000 | OVERLONG_4,
+    TOO_LONG | OVERLONG_2 | TWO_CONTS | OVERLONG_3 | TOO_LARGE,
+    TOO_LONG | OVERLONG_2 | TWO_CONTS | SURROGATE | TOO_LARGE,
+    TOO_LONG | OVERLONG_2 | TWO_CONTS | SURROGATE | TOO_LARGE,
+    TOO_SHORT, TOO_SHORT, TOO_SHORT, TOO_SHORT,
+];
+
+// The largest vector we may need to check, plus the three bytes preceding it
+const MAX_BLOCK: usize = 3 + 64;
+
+// Return a vector which is nonzero in every lane where the byte in `data` at
+// `pos` plus that lane's index ends an invalid sequence. `data` must have
+// three bytes before `pos` and a vector's worth of bytes from `pos`.
+#[inline(always)]
+fn check_block(data: &[u8], pos: usize) -> u8s {
+    let input = u8s::load(data, pos);
+    let prev1 = u8s::load(data, pos - 1);
+    let prev2 = u8s::load(data, pos - 2);
+    let prev3 = u8s::load(data, pos - 3);
+
+    let special = (prev1 >> u8s(4)).lookup_nibbles(&BYTE_1_HIGH)
+        & prev1.lookup_nibbles(&BYTE_1_LOW)
+        & (input >> u8s(4)).lookup_nibbles(&BYTE_2_HIGH);
+
+    // Two continuations in a row are only valid as the third or fourth byte
+    // of a sequence, where the tables leave only TWO_CONTS set
+    let must_be_cont = (prev2.saturating_sub(u8s(0xE0 - 0x80))
+                        | prev3.saturating_sub(u8s(0xF0 - 0x80))) & u8s(0x80);
+    must_be_cont ^ special
+}
+
+/// Validate that `data` is UTF-8, returning the same error as
+/// `core::str::from_utf8` if it isn't.
+///
+/// ```
+/// extern crate faster;
+/// use faster::text::validate_utf8;
+///
+/// # fn main() {
+/// assert!(validate_utf8("Grüße, Jürgen ❤".as_bytes()).is_ok());
+/// assert_eq!(validate_utf8(b"abc\xFFdef").unwrap_err().valid_up_to(), 3);
+/// assert_eq!(validate_utf8(b"abc\xE2\x82").unwrap_err().error_len(), None);
+/// # }
+/// ```
+pub fn validate_utf8(data: &[u8]) -> Result<(), Utf8Error> {
+    let width = u8s::WIDTH;
+    let len = data.len();
+    let mut block = [0u8; MAX_BLOCK];
+    let mut pos = 0;
+
+    // Run past the end by three bytes, so sequences which are cut short are
+    // checked against the zeroes after them.
+    while pos < len + 3 {
+        let error = if pos >= 3 && pos + width <= len {
+            // Skip blocks which are entirely ASCII, along with the three
+            // bytes before them
+            let ascii = (u8s::load(data, pos) | u8s::load(data, pos - 3)).movemask() == 0;
+            !ascii && check_block(data, pos) != u8s(0)
+        } else {
+            // Copy the block and the bytes before it into a zero-padded
+            // buffer
+            for b in block.iter_mut() {
+                *b = 0;
+            }
+            let start = pos.saturating_sub(3);
+            let end = (pos + width).min(len);
+            if start < end {
+                let offset = start + 3 - pos;
+                block[offset..offset + end - start].copy_from_slice(&data[start..end]);
+            }
+            check_block(&block, 3) != u8s(0)
+        };
+
+        if error {
+            return validate_scalar(data, pos);
+        }
+        pos += width;
+    }
+    Ok(())
+}
+
+// Find the error in `data`, which is valid up to the characters ending in
+// the three bytes before `pos`.
+#[cold]
+fn validate_scalar(data: &[u8], pos: usize) -> Result<(), Utf8Error> {
+    // Restart from the first character which begins in those three bytes.
+    // Any continuation bytes before it belong to a character we've checked.
+    let end = pos.min(data.len());
+    let mut start = pos.saturating_sub(3).min(end);
+    while start < end && data[start] & 0xC0 == 0x80 {
+        start += 1;
+    }
+
+    match crate::core::str::from_utf8(&data[start..]) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(Utf8Error {
+            valid_up_to: start + e.valid_up_to(),
+            error_len: e.error_len().map(|len| len as u8),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::rng;
+
+    fn check(data: &[u8]) {
+        let expected = ::std::str::from_utf8(data)
+            .map(|_| ())
+            .map_err(|e| (e.valid_up_to(), e.error_len()));
+        let actual = validate_utf8(data)
+            .map_err(|e| (e.valid_up_to(), e.error_len()));
+        assert_eq!(actual, expected, "{:x?}", data);
+    }
+
+    #[test]
+    fn trim_matches_scalar() {
+        let mut seed = 0x853C49E6748FEA9B;
+        for len in 0..150 {
+            // Whitespace at both ends, of every length, around a few bytes
+            let data = (0..len).map(|i| {
+                let edge = i < len / 3 || i >= len - len / 3;
+                match rng(&mut seed) % if edge { 40 } else { 3 } {
+                    0 => rng(&mut seed) as u8,
+                    1 => b'x',
+                    _ => b" \t\n\x0C\r"[rng(&mut seed) as usize % 5],
+                }
+            }).collect::<Vec<_>>();
+            let first = data.iter().position(|b| !b.is_ascii_whitespace()).unwrap_or(len);
+            let last = data.iter().rposition(|b| !b.is_ascii_whitespace()).map_or(0, |i| i + 1);
+
+            assert_eq!(trim_ascii_whitespace_start(&data), &data[first..]);
+            assert_eq!(trim_ascii_whitespace_end(&data), &data[..last]);
+            assert_eq!(trim_ascii_whitespace(&data), &data[first..last.max(first)]);
+        }
+    }
+
+    #[test]
+    fn char_class_mask() {
+        let classes = [CharClass::DIGIT, CharClass::UPPER, CharClass::LOWER,
+                       CharClass::WHITESPACE, CharClass::ALPHA, CharClass::ALPHANUMERIC,
+                       CharClass::DIGIT | CharClass::LOWER | CharClass::WHITESPACE];
+        for &class in classes.iter() {
+            for b in 0..=255u8 {
+                let expected = if class.matches(b) { 0xFF } else { 0 };
+                assert_eq!(class.mask(u8s(b)), u8s(expected), "{:?} {}", class, b);
+            }
+        }
+    }
+
+    #[test]
+    fn ascii_helpers() {
+        let mut seed = 0x2545F4914F6CDD1D;
+        for len in 0..200 {
+            let data = (0..len).map(|_| match rng(&mut seed) % 4 {
+                0 => b" \t\n\x0B\x0C\r"[rng(&mut seed) as usize % 6],
+                1 => rng(&mut seed) as u8,
+                _ => rng(&mut seed) as u8 & 0x7F,
+            }).collect::<Vec<_>>();
+
+            assert_eq!(is_ascii(&data), data.is_ascii());
+            assert_eq!(count_whitespace(&data),
+                       data.iter().filter(|b| b.is_ascii_whitespace()).count());
+            assert_eq!(count_class(&data, CharClass::ALPHANUMERIC),
+                       data.iter().filter(|b| b.is_ascii_alphanumeric()).count());
+
+            let mut upper = data.clone();
+            to_ascii_uppercase_in_place(&mut upper);
+            assert_eq!(upper, data.to_ascii_uppercase());
+            let mut lower = data.clone();
+            to_ascii_lowercase_in_place(&mut lower);
+            assert_eq!(lower, data.to_ascii_lowercase());
+
+            assert!(eq_ignore_ascii_case(&upper, &lower));
+            for i in 0..len {
+                let mut other = lower.clone();
+                other[i] ^= 0x20;
+                assert_eq!(eq_ignore_ascii_case(&data, &other),
+                           data.eq_ignore_ascii_case(&other));
+            }
+        }
+    }
+
+    #[test]
+    fn line_offsets() {
+        let mut seed = 0x9E3779B97F4A7C15;
+        for len in 0..200 {
+            let data = (0..len).map(|_| b"ab\r\n"[rng(&mut seed) as usize % 4])
+                .collect::<Vec<_>>();
+            let text = ::std::str::from_utf8(&data).unwrap();
+            let expected = text.lines().map(|l| l.as_bytes()).collect::<Vec<_>>();
+            assert_eq!(count_lines(&data), expected.len());
+            assert_eq!(lines(&data).collect::<Vec<_>>(), expected);
+
+            let starts = expected.iter()
+                .map(|l| l.as_ptr() as usize - data.as_ptr() as usize)
+                .collect::<Vec<_>>();
+            assert_eq!(line_starts(&data).collect::<Vec<_>>(), starts);
+            assert_eq!(line_starts_vec(&data), starts);
+            let mut out = [0; 8];
+            let written = line_starts_into(&data, &mut out);
+            assert_eq!(&out[..written], &starts[..starts.len().min(8)]);
+        }
+    }
+
+    #[test]
+    fn validate_utf8_valid() {
+        let text = "Hello, wörld! Ελληνικά, 日本語, 🦀🦀🦀 and some more ASCII text \
+                    to fill a couple of vectors ... ¡¿ñ€𝄞";
+        for i in 0..text.len() {
+            for j in i..text.len() {
+                if text.is_char_boundary(i) && text.is_char_boundary(j) {
+                    check(text[i..j].as_bytes());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn validate_utf8_sequences() {
+        let cases: &[&[u8]] = &[
+            b"\x80", b"\xBF", b"\xC0\x80", b"\xC1\xBF", b"\xC2\x80", b"\xDF\xBF",
+            b"\xE0\x80\x80", b"\xE0\x9F\xBF", b"\xE0\xA0\x80", b"\xED\x9F\xBF",
+            b"\xED\xA0\x80", b"\xEF\xBF\xBF", b"\xF0\x8F\xBF\xBF", b"\xF0\x90\x80\x80",
+            b"\xF4\x8F\xBF\xBF", b"\xF4\x90\x80\x80", b"\xF5\x80\x80\x80", b"\xFF",
+            b"\xC2", b"\xE2\x82", b"\xF0\x9F\xA6", b"\xC2\xC2\x80", b"\xE2\x82\xAC\x80",
+            b"\xF0\x9F\xA6\x80\x80",
+        ];
+        for case in cases {
+            for prefix in 0..70 {
+                for suffix in &[0, 1, 5, 40] {
+                    let mut data = vec![b'a'; prefix];
+                    data.extend_from_slice(case);
+                    data.extend(::std::iter::repeat(b'z').take(*suffix));
+                    check(&data);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn validate_utf8_fuzz() {
+        let mut seed = 0xDA942042E4DD58B5;
+        let valid = "aé€🦀".as_bytes();
+        for _ in 0..4000 {
+            let len = rng(&mut seed) as usize % 200;
+            let mut data = Vec::with_capacity(len);
+            while data.len() < len {
+                // Mostly valid characters, with the occasional random byte
+                match rng(&mut seed) % 16 {
+                    0 => data.push(rng(&mut seed) as u8),
+                    1 => data.push(0x80 | (rng(&mut seed) as u8 & 0x3F)),
+                    n => {
+                        let start = [0, 1, 3, 6][n as usize % 4];
+                        let end = [1, 3, 6, 10][n as usize % 4];
+                        data.extend_from_slice(&valid[start..end]);
+                    }
+                }
+            }
+            check(&data);
+        }
+    }
+}
@@ -0,0 +1,250 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Sliding windows of vectors over an array.
+//!
+//! Each window holds up to [`MAX_WINDOW`] vectors loaded one element apart,
+//! which suits stencils and FIR filters where every output reads several
+//! neighbouring inputs.
+//!
+//! [`MAX_WINDOW`]: constant.MAX_WINDOW.html
+
+use crate::iters::{SIMDArray, SIMDObject};
+use crate::zip::{SIMDZippedObject, SIMDZippedIterable, SIMDZippedIterator};
+use crate::core::iter::{Iterator, ExactSizeIterator};
+use crate::core::ops::Deref;
+use crate::vecs::*;
+
+/// The most vectors a [`Window`] may hold.
+///
+/// [`Window`]: struct.Window.html
+pub const MAX_WINDOW: usize = 8;
+
+/// A group of vectors loaded at consecutive scalar offsets of an array. The
+/// nth vector of the window holds the elements n places after those of the
+/// first, and can be read by indexing the window.
+#[derive(Clone, Copy, Debug)]
+pub struct Window<V> where V : Packed {
+    vectors: [V; MAX_WINDOW],
+    len: usize,
+}
+
+impl<V> Deref for Window<V> where V : Packed {
+    type Target = [V];
+
+    #[inline(always)]
+    fn deref(&self) -> &[V] {
+        &self.vectors[..self.len]
+    }
+}
+
+/// A slice-backed iterator which returns a window of vectors at each
+/// position of its array. Elements past the end of the array are taken from
+/// the default vector.
+#[derive(Clone)]
+pub struct SIMDWindows<'a, A> where A : 'a + SIMDArray {
+    iter: &'a A,
+    pos: usize,
+    size: usize,
+    default: <A as SIMDObject>::Vector
+}
+
+pub trait Windows<A> where A : SIMDArray {
+    /// Return an iterator of windows of `size` vectors, the nth of which
+    /// holds the elements n places after those of the first. There is one
+    /// window for every vector of the array, so mapping over the windows and
+    /// collecting gives one element per element of the array. Elements which
+    /// would be read past the end of the array are taken from `default`.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # #[cfg(not(feature = "std"))]
+    /// # fn main() { }
+    ///
+    /// # #[cfg(feature = "std")]
+    /// # fn main() {
+    /// let data = [1.0f32, 2.0, 3.0, 4.0, 5.0];
+    /// let sums = (&data[..]).simd_windows(3, f32s(0.0))
+    ///     .simd_map(|w| w[0] + w[1] + w[2])
+    ///     .scalar_collect();
+    /// assert_eq!(sums, vec![6.0, 9.0, 12.0, 9.0, 5.0]);
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `size` is zero or greater than
+    /// [`MAX_WINDOW`].
+    ///
+    /// [`MAX_WINDOW`]: constant.MAX_WINDOW.html
+    fn simd_windows(&self, size: usize, default: <A as SIMDObject>::Vector) -> SIMDWindows<A>;
+}
+
+impl<A> Windows<A> for A where A : SIMDArray {
+    #[inline(always)]
+    fn simd_windows(&self, size: usize, default: <A as SIMDObject>::Vector) -> SIMDWindows<A> {
+        assert!((1..=MAX_WINDOW).contains(&size), "a window holds between 1 and {} vectors", MAX_WINDOW);
+        SIMDWindows {
+            iter: self,
+            pos: 0,
+            size,
+            default
+        }
+    }
+}
+
+impl<'a, A> SIMDWindows<'a, A> where A : 'a + SIMDArray {
+    // Return a vector whose elements from `skip` on are those of the array
+    // from `offset` on, with the default vector filling every other element.
+    #[inline(always)]
+    fn load_padded(&self, offset: usize, skip: usize) -> <A as SIMDObject>::Vector {
+        let mut ret = self.default;
+        for i in skip..self.width() {
+            if offset + i - skip < self.iter.scalar_len() {
+                ret = ret.replace(i, unsafe {
+                    self.iter.load_scalar_unchecked(offset + i - skip)
+                });
+            }
+        }
+        ret
+    }
+}
+
+impl<'a, A> Iterator for SIMDWindows<'a, A> where A : 'a + SIMDArray {
+    type Item = Window<<A as SIMDObject>::Vector>;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos + self.width() <= self.iter.scalar_len() {
+            let mut ret = SIMDZippedIterable::default(self);
+            if self.pos + self.width() + self.size - 1 <= self.iter.scalar_len() {
+                for (n, v) in ret.vectors[..self.size].iter_mut().enumerate() {
+                    *v = unsafe { self.iter.load_unchecked(self.pos + n) };
+                }
+            } else {
+                // The last few windows reach past the end of the array
+                for (n, v) in ret.vectors[..self.size].iter_mut().enumerate() {
+                    *v = self.load_padded(self.pos + n, 0);
+                }
+            }
+            let width = self.width(); // Appease borrow checker
+            self.advance(width);
+            Some(ret)
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, A> ExactSizeIterator for SIMDWindows<'a, A> where A : 'a + SIMDArray {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.iter.scalar_len() / self.width()
+    }
+}
+
+impl<'a, A> SIMDZippedObject for SIMDWindows<'a, A> where A : 'a + SIMDArray {
+    type Vectors = Window<<A as SIMDObject>::Vector>;
+    type Scalars = <A as SIMDObject>::Scalar;
+
+    #[inline(always)]
+    fn width(&self) -> usize {
+        <A as SIMDObject>::Vector::WIDTH
+    }
+
+    #[inline(always)]
+    fn size(&self) -> usize {
+        <A as SIMDObject>::Scalar::SIZE
+    }
+}
+
+impl<'a, A> SIMDZippedIterable for SIMDWindows<'a, A> where A : 'a + SIMDArray {
+    #[inline(always)]
+    fn scalar_pos(&self) -> usize {
+        self.pos
+    }
+
+    #[inline(always)]
+    fn scalar_len(&self) -> usize {
+        self.iter.scalar_len()
+    }
+
+    #[inline(always)]
+    fn advance(&mut self, amount: usize) {
+        self.pos += amount;
+    }
+
+    #[inline(always)]
+    fn default(&self) -> Self::Vectors {
+        Window {
+            vectors: [self.default; MAX_WINDOW],
+            len: self.size,
+        }
+    }
+}
+
+impl<'a, A> SIMDZippedIterator for SIMDWindows<'a, A> where A : 'a + SIMDArray {
+    #[inline(always)]
+    fn end(&mut self) -> Option<(Self::Vectors, usize)> {
+        if self.pos < self.iter.scalar_len() {
+            // Right-align the partial windows, as the other iterators do
+            let empty_amt = self.width() - (self.iter.scalar_len() - self.pos);
+            let mut ret = SIMDZippedIterable::default(self);
+            for (n, v) in ret.vectors[..self.size].iter_mut().enumerate() {
+                *v = self.load_padded(self.pos + n, empty_amt);
+            }
+            self.finalize();
+            Some((ret, empty_amt))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn windows_match_scalar() {
+        for len in 0..70 {
+            let data = (0..len as u32).map(|x| x * x + 1).collect::<Vec<_>>();
+            for size in 1..=MAX_WINDOW {
+                let sums = (&data[..]).simd_windows(size, u32s(0))
+                    .simd_map(|w| w.iter().fold(u32s(0), |acc, &v| acc + v))
+                    .scalar_collect();
+                let expected = (0..len)
+                    .map(|i| data[i..].iter().take(size).sum::<u32>())
+                    .collect::<Vec<_>>();
+                assert_eq!(sums, expected);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn windows_use_default() {
+        let data = [1i32, 2, 3];
+        let diffs = (&data[..]).simd_windows(2, i32s(10))
+            .simd_map(|w| w[1] - w[0])
+            .scalar_collect();
+        assert_eq!(diffs, vec![1, 1, 7]);
+    }
+
+    #[test]
+    fn windows_reduce() {
+        let data = [1.0f64; 37];
+        let total = (&data[..]).simd_windows(3, f64s(0.0))
+            .simd_reduce(f64s(0.0), |acc, w| acc + w[0] * w[1] * w[2])
+            .sum();
+        assert_eq!(total, 35.0);
+    }
+}
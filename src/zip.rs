@@ -7,6 +7,8 @@
 
 use crate::iters::{SIMDIterator, SIMDIterable, SIMDObject, UnsafeIterator, SIMDSized};
 use crate::vecs::{Packed, Packable};
+use crate::intrin::merge::Merge;
+use crate::intrin::shuffle::{blend, permute};
 
 /// A macro which takes a number n and an expression, and returns a tuple
 /// containing n copies of the expression. Only works for numbers less than or
@@ -50,6 +52,34 @@ pub struct SIMDZipMap<I, F> where I : SIMDZippedIterator {
     func: F,
 }
 
+/// A lazy mapping iterator which applies its function to a stream of tuples of
+/// vectors, and yields the tuples of vectors it returns.
+pub struct SIMDZipTupleMap<I, F> where I : SIMDZippedIterator {
+    iter: I,
+    func: F,
+}
+
+/// A tuple of vectors of the same type, whose elements can be interleaved
+/// into a single slice.
+pub trait PackedTuple : Sized + Copy {
+    type Scalar : Packable;
+    type Scalars;
+
+    /// The number of vectors in this tuple.
+    const LEN: usize;
+
+    /// The number of elements in each vector of this tuple.
+    const WIDTH: usize;
+
+    /// Return a tuple of default vectors.
+    fn default() -> Self;
+
+    /// Write element `skip` of each vector in order to the start of `out`,
+    /// followed by element `skip + 1` of each vector, and so on until `out`
+    /// is full.
+    fn interleave(&self, skip: usize, out: &mut [Self::Scalar]);
+}
+
 /// A trait which can transform a collection of iterators into a `Zip`
 pub trait IntoSIMDZip : Sized {
     /// Return an iterator which may iterate over `self` in lockstep.
@@ -143,6 +173,34 @@ pub trait SIMDZippedIterator : SIMDZippedIterable {
         }
     }
 
+    /// Return an iterator which calls `func` on vectors of elements, and
+    /// which may itself be zipped, mapped or interleaved.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # #[cfg(not(feature = "std"))]
+    /// # fn main() { }
+    ///
+    /// # #[cfg(feature = "std")]
+    /// # fn main() {
+    /// let rgb = [10u8, 20, 30, 40, 50, 60];
+    /// let bgr = (&rgb[..]).stride_three(tuplify!(3, u8s(0))).zip()
+    ///     .simd_map_tuple(|(r, g, b)| (b, g, r))
+    ///     .interleave_collect();
+    /// assert_eq!(bgr, vec![30, 20, 10, 60, 50, 40]);
+    /// # }
+    /// ```
+    #[inline(always)]
+    fn simd_map_tuple<A, F>(self, func: F) -> SIMDZipTupleMap<Self, F>
+        where F : FnMut(Self::Vectors) -> A, A : PackedTuple {
+        SIMDZipTupleMap {
+            iter: self,
+            func: func,
+        }
+    }
+
     /// Pack and run `func` over the iterator, returning no value and not
     /// modifying the iterator.
     #[inline(always)]
@@ -370,6 +428,236 @@ where I : SIMDZippedIterator, F : FnMut(I::Vectors) -> A, A : Packed {
     }
 }
 
+impl<I, F, A> Iterator for SIMDZipTupleMap<I, F>
+    where I : SIMDZippedIterator, F : FnMut(I::Vectors) -> A, A : PackedTuple {
+    type Item = A;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(&mut self.func)
+    }
+}
+
+impl<I, F, A> ExactSizeIterator for SIMDZipTupleMap<I, F>
+    where I : SIMDZippedIterator, F : FnMut(I::Vectors) -> A, A : PackedTuple {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+impl<I, F, A> SIMDZippedObject for SIMDZipTupleMap<I, F>
+    where I : SIMDZippedIterator, F : FnMut(I::Vectors) -> A, A : PackedTuple {
+    type Vectors = A;
+    type Scalars = A::Scalars;
+
+    #[inline(always)]
+    fn width(&self) -> usize {
+        A::WIDTH
+    }
+
+    #[inline(always)]
+    fn size(&self) -> usize {
+        A::Scalar::SIZE
+    }
+}
+
+impl<I, F, A> SIMDZippedIterable for SIMDZipTupleMap<I, F>
+    where I : SIMDZippedIterator, F : FnMut(I::Vectors) -> A, A : PackedTuple {
+    #[inline(always)]
+    fn scalar_pos(&self) -> usize {
+        self.iter.scalar_pos()
+    }
+
+    #[inline(always)]
+    fn advance(&mut self, amount: usize) {
+        self.iter.advance(amount)
+    }
+
+    #[inline(always)]
+    fn default(&self) -> Self::Vectors {
+        <A as PackedTuple>::default()
+    }
+}
+
+impl<I, F, A> SIMDZippedIterator for SIMDZipTupleMap<I, F>
+    where I : SIMDZippedIterator, F : FnMut(I::Vectors) -> A, A : PackedTuple {
+    #[inline(always)]
+    fn end(&mut self) -> Option<(Self::Vectors, usize)> {
+        let (v, n) = self.iter.end()?;
+        let nr = n * self.iter.size() / self.size();
+        Some(((self.func)(v), nr))
+    }
+}
+
+/// A trait which can write a stream of tuples of vectors to a single slice,
+/// with the elements of each tuple interleaved. This is the inverse of
+/// striding a slice and zipping the strides.
+pub trait IntoInterleaved<T> : SIMDZippedObject where T : Packable {
+    /// Take an iterator of tuples of SIMD vectors, and store the elements of
+    /// each tuple in a Vec, interleaved in the order of the tuple.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # #[cfg(not(feature = "std"))]
+    /// # fn main() { }
+    ///
+    /// # #[cfg(feature = "std")]
+    /// # fn main() {
+    /// let evens = [0u32, 2, 4, 6, 8];
+    /// let odds = [1u32, 3, 5, 7, 9];
+    /// let all = (evens.simd_iter(u32s(0)), odds.simd_iter(u32s(0))).zip()
+    ///     .interleave_collect();
+    /// assert_eq!(all, (0..10).collect::<Vec<_>>());
+    /// # }
+    /// ```
+    #[cfg(feature = "std")]
+    fn interleave_collect(&mut self) -> Vec<T>;
+
+    /// Take an iterator of tuples of SIMD vectors, and store the elements of
+    /// each tuple in `fill`, interleaved in the order of the tuple.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `fill` is shorter than the number of
+    /// elements in the iterator times the number of vectors in each tuple.
+    fn interleave_fill<'a>(&mut self, fill: &'a mut [T]) -> &'a mut [T];
+}
+
+impl<T, I> IntoInterleaved<T> for I
+    where I : SIMDZippedIterator, I::Vectors : PackedTuple<Scalar = T>, T : Packable {
+    #[inline(always)]
+    #[cfg(feature = "std")]
+    fn interleave_collect(&mut self) -> Vec<T> {
+        let step = <I::Vectors as PackedTuple>::WIDTH * <I::Vectors as PackedTuple>::LEN;
+        let mut ret = Vec::with_capacity((self.len() + 1) * step);
+
+        while let Some(v) = self.next() {
+            let offset = ret.len();
+            ret.reserve(step);
+            unsafe { ret.set_len(offset + step); }
+            v.interleave(0, &mut ret[offset..]);
+        }
+
+        if let Some((v, n)) = self.end() {
+            let offset = ret.len();
+            let len = (<I::Vectors as PackedTuple>::WIDTH - n) * <I::Vectors as PackedTuple>::LEN;
+            ret.reserve(len);
+            unsafe { ret.set_len(offset + len); }
+            v.interleave(n, &mut ret[offset..]);
+        }
+        ret
+    }
+
+    #[inline(always)]
+    fn interleave_fill<'a>(&mut self, fill: &'a mut [T]) -> &'a mut [T] {
+        let step = <I::Vectors as PackedTuple>::WIDTH * <I::Vectors as PackedTuple>::LEN;
+        let mut offset = 0;
+
+        while let Some(v) = self.next() {
+            v.interleave(0, &mut fill[offset..offset + step]);
+            offset += step;
+        }
+
+        if let Some((v, n)) = self.end() {
+            let len = (<I::Vectors as PackedTuple>::WIDTH - n) * <I::Vectors as PackedTuple>::LEN;
+            v.interleave(n, &mut fill[offset..offset + len]);
+        }
+        fill
+    }
+}
+
+// Return a vector whose element n is element (j * WIDTH + n) / len of `v`,
+// which is where the elements of `v` go in vector `j` of the interleaving of
+// `len` vectors.
+#[inline(always)]
+fn spread<V>(v: V, j: usize, len: usize) -> V where V : Packed {
+    permute(v, |n| (j * V::WIDTH + n) / len)
+}
+
+// Return vector `j` of the interleaving of a pair of vectors, taking the even
+// lanes from the first and the odd lanes from the second.
+#[inline(always)]
+fn join_two<V>(t: &(V, V), j: usize) -> V where V : Packed + Merge {
+    spread(t.0, j, 2).merge_interleaved(spread(t.1, j, 2))
+}
+
+// Return vector `j` of the interleaving of four vectors, joining the first
+// two and the last two as pairs and taking alternate pairs of lanes from
+// each.
+#[inline(always)]
+fn join_four<V>(t: &(V, V, V, V), j: usize) -> V where V : Packed + Merge {
+    blend(spread(t.0, j, 4).merge_interleaved(spread(t.1, j, 4)),
+          spread(t.2, j, 4).merge_interleaved(spread(t.3, j, 4)),
+          |n| (j * V::WIDTH + n) % 4 < 2)
+}
+
+macro_rules! impl_packed_tuple {
+    // Whole tuples of 2 or 4 vectors are interleaved a vector at a time
+    (merge $len:expr, ($($v:ident),*), ($($n:tt),*), $join:ident) => (
+        impl<V> PackedTuple for ($($v),*) where V : Packed + Merge {
+            type Scalar = V::Scalar;
+            type Scalars = ($($v::Scalar),*);
+
+            const LEN: usize = $len;
+            const WIDTH: usize = V::WIDTH;
+
+            #[inline(always)]
+            fn default() -> Self {
+                ($(<$v as Packed>::default()),*)
+            }
+
+            #[inline(always)]
+            fn interleave(&self, skip: usize, out: &mut [Self::Scalar]) {
+                if skip == 0 && out.len() == $len * V::WIDTH {
+                    for j in 0..$len {
+                        $join(self, j).store(out, j * V::WIDTH);
+                    }
+                } else {
+                    for (i, record) in out.chunks_exact_mut($len).enumerate() {
+                        $(record[$n] = self.$n.extract(skip + i);)*
+                    }
+                }
+            }
+        }
+    );
+    ($len:expr, ($($v:ident),*), ($($n:tt),*)) => (
+        impl<V> PackedTuple for ($($v),*) where V : Packed {
+            type Scalar = V::Scalar;
+            type Scalars = ($($v::Scalar),*);
+
+            const LEN: usize = $len;
+            const WIDTH: usize = V::WIDTH;
+
+            #[inline(always)]
+            fn default() -> Self {
+                ($(<$v as Packed>::default()),*)
+            }
+
+            #[inline(always)]
+            fn interleave(&self, skip: usize, out: &mut [Self::Scalar]) {
+                for (i, record) in out.chunks_exact_mut($len).enumerate() {
+                    $(record[$n] = self.$n.extract(skip + i);)*
+                }
+            }
+        }
+    );
+}
+
+impl_packed_tuple!(merge 2, (V, V), (0, 1), join_two);
+impl_packed_tuple!(3, (V, V, V), (0, 1, 2));
+impl_packed_tuple!(merge 4, (V, V, V, V), (0, 1, 2, 3), join_four);
+impl_packed_tuple!(5, (V, V, V, V, V), (0, 1, 2, 3, 4));
+impl_packed_tuple!(6, (V, V, V, V, V, V), (0, 1, 2, 3, 4, 5));
+impl_packed_tuple!(7, (V, V, V, V, V, V, V), (0, 1, 2, 3, 4, 5, 6));
+impl_packed_tuple!(8, (V, V, V, V, V, V, V, V), (0, 1, 2, 3, 4, 5, 6, 7));
+impl_packed_tuple!(9, (V, V, V, V, V, V, V, V, V), (0, 1, 2, 3, 4, 5, 6, 7, 8));
+impl_packed_tuple!(10, (V, V, V, V, V, V, V, V, V, V), (0, 1, 2, 3, 4, 5, 6, 7, 8, 9));
+impl_packed_tuple!(11, (V, V, V, V, V, V, V, V, V, V, V), (0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10));
+impl_packed_tuple!(12, (V, V, V, V, V, V, V, V, V, V, V, V), (0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11));
+
 impl_iter_zip!((A, B),
                (AA, BB),
                (1));
@@ -75,4 +75,67 @@ mod tests {
         assert_eq!(result, 2 * 3 * 129);
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn interleave_strided_map() {
+        for pixels in 0..100 {
+            let rgb = (0..pixels * 3).map(|x| (x % 200) as u8).collect::<Vec<_>>();
+            let bgr = (&rgb[..]).stride_three(tuplify!(3, u8s(0))).zip()
+                .simd_map_tuple(|(r, g, b)| (b, g + u8s(1), r))
+                .interleave_collect();
+            let expected = rgb.chunks(3).flat_map(|p| vec![p[2], p[1] + 1, p[0]]).collect::<Vec<_>>();
+            assert_eq!(bgr, expected);
+
+            let mut filled = vec![0u8; rgb.len()];
+            (&rgb[..]).stride_three(tuplify!(3, u8s(0))).zip()
+                .simd_map_tuple(|(r, g, b)| (b, g + u8s(1), r))
+                .interleave_fill(&mut filled);
+            assert_eq!(filled, expected);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn interleave_zipped_iters() {
+        for len in 0..70 {
+            let a = (0..len).map(|x| x as f32).collect::<Vec<_>>();
+            let b = (0..len).map(|x| -(x as f32)).collect::<Vec<_>>();
+            let c = vec![0.5f32; len];
+            let interleaved = (a.simd_iter(f32s(0.0)), b.simd_iter(f32s(0.0)), c.simd_iter(f32s(0.0)), a.simd_iter(f32s(0.0))).zip()
+                .interleave_collect();
+            assert_eq!(interleaved.len(), len * 4);
+            for (i, record) in interleaved.chunks(4).enumerate() {
+                assert_eq!(record, &[a[i], b[i], c[i], a[i]]);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn interleave_pairs_and_quads() {
+        for len in 0..40 {
+            let a = (0..len as u64).collect::<Vec<_>>();
+            let b = (0..len as u64).map(|x| x + 1000).collect::<Vec<_>>();
+            let pairs = (a.simd_iter(u64s(0)), b.simd_iter(u64s(0))).zip().interleave_collect();
+            assert_eq!(pairs, a.iter().zip(&b).flat_map(|(&x, &y)| vec![x, y]).collect::<Vec<_>>());
+
+            let c = (0..len as u16).collect::<Vec<_>>();
+            let d = (0..len as u16).map(|x| x * 3).collect::<Vec<_>>();
+            let mut quads = vec![0u16; len * 4];
+            (c.simd_iter(u16s(0)), d.simd_iter(u16s(0)), d.simd_iter(u16s(0)), c.simd_iter(u16s(0))).zip()
+                .interleave_fill(&mut quads);
+            assert_eq!(quads, c.iter().zip(&d).flat_map(|(&x, &y)| vec![x, y, y, x]).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn interleave_stride_zip() {
+        // Destriding then interleaving gives back the input
+        let data = (0..128u8).collect::<Vec<_>>();
+        let mut out = [0u8; 128];
+        (&data[..]).simd_iter(u8s(0)).stride_zip().interleave_fill(&mut out);
+        assert_eq!(&out[..], &data[..]);
+    }
+
 }